@@ -60,6 +60,7 @@ fn bench_vec_box_push(c: &mut Criterion) {
     );
 }
 
+#[allow(clippy::same_item_push)]
 fn bench_vec_value_push(c: &mut Criterion) {
     c.bench_with_input(
         BenchmarkId::new("vec_value::push", BIG_SAMPLE_SIZE),
@@ -92,7 +93,6 @@ fn bench_bucket_vec_push_get(c: &mut Criterion) {
 
 fn bench_bucket_vec_get_fast_config(c: &mut Criterion) {
     let vec = (0..BIG_SAMPLE_SIZE)
-        .into_iter()
         .map(|value| value as i32)
         .collect::<BucketVec<i32, EqualSizeConfig>>();
     c.bench_with_input(
@@ -101,7 +101,7 @@ fn bench_bucket_vec_get_fast_config(c: &mut Criterion) {
         |b, vec| {
             b.iter(|| {
                 for i in 0..vec.len() {
-                    black_box(vec.get(i).map(|val| *val));
+                    black_box(vec.get(i).copied());
                 }
             });
         },
@@ -110,7 +110,6 @@ fn bench_bucket_vec_get_fast_config(c: &mut Criterion) {
 
 fn bench_bucket_vec_get_medium_config(c: &mut Criterion) {
     let vec = (0..BIG_SAMPLE_SIZE)
-        .into_iter()
         .map(|value| value as i32)
         .collect::<BucketVec<i32, QuadraticConfig>>();
     c.bench_with_input(
@@ -119,7 +118,7 @@ fn bench_bucket_vec_get_medium_config(c: &mut Criterion) {
         |b, vec| {
             b.iter(|| {
                 for i in 0..vec.len() {
-                    black_box(vec.get(i).map(|val| *val));
+                    black_box(vec.get(i).copied());
                 }
             });
         },
@@ -128,7 +127,6 @@ fn bench_bucket_vec_get_medium_config(c: &mut Criterion) {
 
 fn bench_bucket_vec_get_slow_config(c: &mut Criterion) {
     let vec = (0..BIG_SAMPLE_SIZE)
-        .into_iter()
         .map(|value| value as i32)
         .collect::<BucketVec<i32, C5g1x5Config>>();
     c.bench_with_input(
@@ -137,7 +135,7 @@ fn bench_bucket_vec_get_slow_config(c: &mut Criterion) {
         |b, vec| {
             b.iter(|| {
                 for i in 0..vec.len() {
-                    black_box(vec.get(i).map(|val| *val));
+                    black_box(vec.get(i).copied());
                 }
             });
         },
@@ -146,7 +144,6 @@ fn bench_bucket_vec_get_slow_config(c: &mut Criterion) {
 
 fn bench_vec_box_get(c: &mut Criterion) {
     let vec = (0..BIG_SAMPLE_SIZE)
-        .into_iter()
         .map(|value| Box::new(value as i32))
         .collect::<Vec<Box<i32>>>();
     c.bench_with_input(
@@ -170,7 +167,7 @@ fn bench_vec_value_get(c: &mut Criterion) {
         |b, vec| {
             b.iter(|| {
                 for i in 0..vec.len() {
-                    black_box(vec.get(i).map(|val| *val));
+                    black_box(vec.get(i).copied());
                 }
             });
         },
@@ -179,7 +176,6 @@ fn bench_vec_value_get(c: &mut Criterion) {
 
 fn bench_bucket_vec_iter(c: &mut Criterion) {
     let vec = (0..BIG_SAMPLE_SIZE)
-        .into_iter()
         .map(|value| value as i32)
         .collect::<BucketVec<i32, QuadraticConfig>>();
     c.bench_with_input(
@@ -197,7 +193,6 @@ fn bench_bucket_vec_iter(c: &mut Criterion) {
 
 fn bench_vec_box_iter(c: &mut Criterion) {
     let vec = (0..BIG_SAMPLE_SIZE)
-        .into_iter()
         .map(|value| Box::new(value as i32))
         .collect::<Vec<Box<i32>>>();
     c.bench_with_input(
@@ -230,7 +225,6 @@ fn bench_vec_value_iter(c: &mut Criterion) {
 
 fn bench_bucket_vec_iter_rev(c: &mut Criterion) {
     let vec = (0..BIG_SAMPLE_SIZE)
-        .into_iter()
         .map(|value| value as i32)
         .collect::<BucketVec<i32, QuadraticConfig>>();
     c.bench_with_input(
@@ -248,7 +242,6 @@ fn bench_bucket_vec_iter_rev(c: &mut Criterion) {
 
 fn bench_vec_box_iter_rev(c: &mut Criterion) {
     let vec = (0..BIG_SAMPLE_SIZE)
-        .into_iter()
         .map(|value| Box::new(value as i32))
         .collect::<Vec<Box<i32>>>();
     c.bench_with_input(
@@ -302,7 +295,6 @@ fn bench_bucket_vec_iter_mut(c: &mut Criterion) {
 
 fn bench_vec_box_iter_mut(c: &mut Criterion) {
     let vec = (0..BIG_SAMPLE_SIZE)
-        .into_iter()
         .map(|value| Box::new(value as i32))
         .collect::<Vec<Box<i32>>>();
     c.bench_with_input(
@@ -341,6 +333,32 @@ fn bench_vec_value_iter_mut(c: &mut Criterion) {
     );
 }
 
+fn bench_bucket_vec_contains(c: &mut Criterion) {
+    let vec = (0..BIG_SAMPLE_SIZE)
+        .map(|value| value as i32)
+        .collect::<BucketVec<i32, QuadraticConfig>>();
+    c.bench_with_input(
+        BenchmarkId::new("bucket_vec::contains", BIG_SAMPLE_SIZE),
+        &vec,
+        |b, vec| {
+            b.iter(|| black_box(vec.contains(black_box(&-1))));
+        },
+    );
+}
+
+fn bench_vec_value_contains(c: &mut Criterion) {
+    let vec = (0..BIG_SAMPLE_SIZE)
+        .map(|value| value as i32)
+        .collect::<Vec<i32>>();
+    c.bench_with_input(
+        BenchmarkId::new("vec_value::contains", BIG_SAMPLE_SIZE),
+        &vec,
+        |b, vec| {
+            b.iter(|| black_box(vec.contains(black_box(&-1))));
+        },
+    );
+}
+
 criterion_group!(
     bench_push,
     bench_bucket_vec_push,
@@ -374,10 +392,16 @@ criterion_group!(
     bench_vec_box_iter_mut,
     bench_vec_value_iter_mut,
 );
+criterion_group!(
+    bench_contains,
+    bench_bucket_vec_contains,
+    bench_vec_value_contains,
+);
 criterion_main!(
     bench_push,
     bench_get,
     bench_iter,
     bench_iter_rev,
     bench_iter_mut,
+    bench_contains,
 );
@@ -0,0 +1,318 @@
+//! Derive and attribute macros for the `bucket_vec` crate.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    meta::ParseNestedMeta, parse_macro_input, Data, DeriveInput, Error, Fields, Lit, LitInt,
+};
+
+/// Derives a struct-of-arrays container storing every field of the struct in
+/// its own bucket vector.
+///
+/// For a struct `Foo` this generates `FooSoA<C = DefaultConfig>` with:
+///
+/// - `push(&mut self, value: Foo) -> usize` pushing every field onto its column
+/// - `<field>_column(&self) -> &BucketVec<Field, C>` granting shared access to a column
+/// - `<field>_get_mut(&mut self, index: usize) -> Option<&mut Field>`
+/// - `<field>_iter_mut(&mut self) -> IterMut<Field>`
+///
+/// All columns share the same configuration and index space.
+/// The per-field methods carry a suffix so that fields such as `len` do not
+/// collide with the methods of the container.
+#[proc_macro_derive(BucketSoA)]
+pub fn bucket_soa(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `BucketVecConfig` for the annotated type.
+///
+/// The constraints of the configuration are validated when the attribute is
+/// expanded instead of when the configuration is first used.
+///
+/// # Arguments
+///
+/// - `starting_capacity = <integer>` (required)
+/// - `growth_rate = <number>` (required)
+/// - `max_bucket_capacity = <integer>`
+/// - `round_to_power_of_two` or `round_to_multiple_of = <integer>`
+///
+/// # Example
+///
+/// ```ignore
+/// #[bucket_config(starting_capacity = 8, growth_rate = 1.5)]
+/// pub enum MyConfig {}
+/// ```
+#[proc_macro_attribute]
+pub fn bucket_config(args: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let mut config = ConfigArgs::default();
+    let parser = syn::meta::parser(|meta| config.parse(meta));
+    parse_macro_input!(args with parser);
+    expand_config(config, input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The arguments of the `bucket_config` attribute.
+#[derive(Default)]
+struct ConfigArgs {
+    /// The capacity of the first bucket.
+    starting_capacity: Option<LitInt>,
+    /// The growth rate of the bucket capacities.
+    growth_rate: Option<(f64, Span)>,
+    /// The capacity at which bucket capacities stop growing.
+    max_bucket_capacity: Option<LitInt>,
+    /// The rounding of bucket capacities.
+    rounding: Option<TokenStream2>,
+}
+
+impl ConfigArgs {
+    /// Parses a single argument of the attribute.
+    fn parse(&mut self, meta: ParseNestedMeta) -> Result<(), Error> {
+        if meta.path.is_ident("starting_capacity") {
+            self.starting_capacity = Some(parse_positive(&meta)?);
+        } else if meta.path.is_ident("growth_rate") {
+            let (growth_rate, span) = match meta.value()?.parse::<Lit>()? {
+                Lit::Float(lit) => (lit.base10_parse::<f64>()?, lit.span()),
+                Lit::Int(lit) => (lit.base10_parse::<f64>()?, lit.span()),
+                lit => return Err(Error::new_spanned(lit, "expected a number")),
+            };
+            if growth_rate < 1.0 {
+                return Err(Error::new(
+                    span,
+                    "`growth_rate` must be larger than or equal to 1",
+                ));
+            }
+            self.growth_rate = Some((growth_rate, span));
+        } else if meta.path.is_ident("max_bucket_capacity") {
+            self.max_bucket_capacity = Some(parse_positive(&meta)?);
+        } else if meta.path.is_ident("round_to_power_of_two") {
+            self.set_rounding(&meta, quote! { PowerOfTwo })?;
+        } else if meta.path.is_ident("round_to_multiple_of") {
+            let quantum = parse_positive(&meta)?;
+            self.set_rounding(&meta, quote! { MultipleOf(#quantum) })?;
+        } else {
+            return Err(meta.error("unsupported bucket_config argument"));
+        }
+        Ok(())
+    }
+
+    /// Sets the rounding of bucket capacities unless already set.
+    fn set_rounding(
+        &mut self,
+        meta: &ParseNestedMeta,
+        rounding: TokenStream2,
+    ) -> Result<(), Error> {
+        if self.rounding.is_some() {
+            return Err(meta.error("bucket capacities can only be rounded in one way"));
+        }
+        self.rounding = Some(rounding);
+        Ok(())
+    }
+}
+
+/// Parses the integer value of the argument which must be at least `1`.
+fn parse_positive(meta: &ParseNestedMeta) -> Result<LitInt, Error> {
+    let lit = meta.value()?.parse::<LitInt>()?;
+    if lit.base10_parse::<usize>()? == 0 {
+        return Err(Error::new_spanned(
+            &lit,
+            "value must be larger than or equal to 1",
+        ));
+    }
+    Ok(lit)
+}
+
+fn expand_config(config: ConfigArgs, input: DeriveInput) -> Result<TokenStream2, Error> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "bucket_config does not support generic types",
+        ));
+    }
+    let starting_capacity = config
+        .starting_capacity
+        .ok_or_else(|| Error::new_spanned(&input.ident, "missing `starting_capacity` argument"))?;
+    let (growth_rate, span) = config
+        .growth_rate
+        .ok_or_else(|| Error::new_spanned(&input.ident, "missing `growth_rate` argument"))?;
+    let growth_rate = {
+        let mut lit = Literal::f64_unsuffixed(growth_rate);
+        lit.set_span(span);
+        lit
+    };
+    let max_bucket_capacity = config.max_bucket_capacity.map(|max_bucket_capacity| {
+        quote! {
+            const MAX_BUCKET_CAPACITY: ::core::option::Option<usize> =
+                ::core::option::Option::Some(#max_bucket_capacity);
+        }
+    });
+    let rounding = config.rounding.map(|rounding| {
+        quote! {
+            const CAPACITY_ROUNDING: ::bucket_vec::CapacityRounding =
+                ::bucket_vec::CapacityRounding::#rounding;
+        }
+    });
+    let name = &input.ident;
+    Ok(quote! {
+        #input
+
+        impl ::bucket_vec::BucketVecConfig for #name {
+            const STARTING_CAPACITY: usize = #starting_capacity;
+            const GROWTH_RATE: f64 = #growth_rate;
+            #max_bucket_capacity
+            #rounding
+        }
+    })
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "BucketSoA does not support generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "BucketSoA requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "BucketSoA can only be derived for structs",
+            ))
+        }
+    };
+    let vis = &input.vis;
+    let name = &input.ident;
+    let soa = format_ident!("{}SoA", name);
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("named fields have identifiers"))
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let field_vis = fields.iter().map(|field| &field.vis).collect::<Vec<_>>();
+    let column = idents
+        .iter()
+        .map(|ident| format_ident!("{}_column", ident))
+        .collect::<Vec<_>>();
+    let get_mut = idents
+        .iter()
+        .map(|ident| format_ident!("{}_get_mut", ident))
+        .collect::<Vec<_>>();
+    let iter_mut = idents
+        .iter()
+        .map(|ident| format_ident!("{}_iter_mut", ident))
+        .collect::<Vec<_>>();
+    let first = idents.first();
+    let len = match first {
+        Some(first) => quote! { self.#first.len() },
+        None => quote! { self.len },
+    };
+    let push_len = match first {
+        Some(_) => quote! {},
+        None => quote! { self.len += 1; },
+    };
+    let len_field = match first {
+        Some(_) => quote! {},
+        None => quote! { len: usize, },
+    };
+    let len_init = match first {
+        Some(_) => quote! {},
+        None => quote! { len: 0, },
+    };
+    let doc = format!(
+        "Struct-of-arrays bucket vector storing every field of [`{}`] in its own column.",
+        name
+    );
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #soa<C = ::bucket_vec::DefaultConfig> {
+            #( #idents: ::bucket_vec::BucketVec<#types, C>, )*
+            #len_field
+        }
+
+        impl<C> ::core::default::Default for #soa<C> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<C> #soa<C> {
+            /// Creates a new empty struct-of-arrays bucket vector.
+            ///
+            /// # Note
+            ///
+            /// This does not allocate any heap memory.
+            #vis fn new() -> Self {
+                Self {
+                    #( #idents: ::bucket_vec::BucketVec::new(), )*
+                    #len_init
+                }
+            }
+
+            /// Returns the number of elements stored in every column.
+            #vis fn len(&self) -> usize {
+                #len
+            }
+
+            /// Returns `true` if the struct-of-arrays bucket vector is empty.
+            #vis fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            #(
+                /// Returns the column of the field.
+                #field_vis fn #column(&self) -> &::bucket_vec::BucketVec<#types, C> {
+                    &self.#idents
+                }
+
+                /// Returns an iterator over exclusive references to the field of all elements.
+                #field_vis fn #iter_mut(&mut self) -> ::bucket_vec::IterMut<'_, #types> {
+                    self.#idents.iter_mut()
+                }
+            )*
+        }
+
+        impl<C> #soa<C>
+        where
+            C: ::bucket_vec::BucketVecConfig,
+        {
+            /// Pushes every field of the value onto its column and returns
+            /// the index of the value.
+            ///
+            /// # Note
+            ///
+            /// This operation will never move other elements, reallocates or
+            /// otherwise invalidate pointers of elements contained by the
+            /// columns.
+            #vis fn push(&mut self, value: #name) -> usize {
+                let index = self.len();
+                #( self.#idents.push(value.#idents); )*
+                #push_len
+                index
+            }
+
+            #(
+                /// Returns an exclusive reference to the field of the element
+                /// at the given index if any.
+                #field_vis fn #get_mut(&mut self, index: usize) -> ::core::option::Option<&mut #types> {
+                    self.#idents.get_mut(index)
+                }
+            )*
+        }
+    })
+}
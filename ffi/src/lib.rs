@@ -0,0 +1,142 @@
+//! # Bucket Vector FFI
+//!
+//! C compatible API over opaque bucket vector handles.
+//!
+//! This lives in its own crate since exporting unmangled symbols and handing
+//! out raw pointers to C is inherently `unsafe`, whereas the `bucket_vec`
+//! crate itself is 100% `unsafe` Rust free.
+//!
+//! Handles are created by `bucketvec_<type>_new` and must be released by
+//! passing them to `bucketvec_<type>_free` exactly once. Pointers obtained
+//! from `bucketvec_<type>_get_ptr` stay valid until the handle is freed since
+//! bucket vectors never move their elements.
+//!
+//! C has neither generics nor overloading and exported symbols must be unique.
+//! Every supported element type therefore gets its own handle type and set of
+//! functions with the element type embedded in their names, such as
+//! `bucketvec_u8_new` and `bucketvec_u64_new`, instead of a single `bucketvec_new`.
+//!
+//! All functions accept null handles.
+//!
+//! # Safety
+//!
+//! The exported functions are `#[no_mangle]` and thus count as `unsafe` code.
+//! The C caller must uphold the contracts Rust cannot check:
+//!
+//! - Non-null handles must have been returned by the matching `_new` function
+//!   and must not have been freed, yet.
+//! - Handles must not be used concurrently from multiple threads.
+//! - Pointers returned by `_get_ptr` must not be used after their handle has
+//!   been freed and must not be used to access an element while another
+//!   function is called on its handle.
+
+use bucket_vec::BucketVec;
+use core::{ffi::c_void, ptr};
+
+#[cfg(test)]
+mod tests;
+
+macro_rules! impl_ffi_for {
+    (
+        $(#[$docs:meta])*
+        $handle:ident($elem:ty) {
+            new: $new:ident,
+            push: $push:ident,
+            get_ptr: $get_ptr:ident,
+            len: $len:ident,
+            free: $free:ident $(,)?
+        }
+    ) => {
+        $(#[$docs])*
+        #[derive(Debug, Default)]
+        pub struct $handle {
+            vec: BucketVec<$elem>,
+        }
+
+        /// Creates a new empty bucket vector and returns its handle.
+        #[no_mangle]
+        pub extern "C" fn $new() -> Box<$handle> {
+            Box::default()
+        }
+
+        /// Pushes the value onto the bucket vector and returns its index.
+        ///
+        /// Returns `usize::MAX` and drops the value if the handle is null.
+        #[no_mangle]
+        pub extern "C" fn $push(handle: Option<&mut $handle>, value: $elem) -> usize {
+            match handle {
+                Some(handle) => {
+                    let index = handle.vec.len();
+                    handle.vec.push(value);
+                    index
+                }
+                None => usize::MAX,
+            }
+        }
+
+        /// Returns a pointer to the element at the given index.
+        ///
+        /// Returns null if the handle is null or the index is out of bounds.
+        ///
+        /// # Safety
+        ///
+        /// The pointer stays valid until the handle is freed.
+        /// Dereferencing it is up to the C caller, see the crate level docs.
+        #[no_mangle]
+        pub extern "C" fn $get_ptr(handle: Option<&mut $handle>, index: usize) -> *mut $elem {
+            handle
+                .and_then(|handle| handle.vec.get_mut(index))
+                .map(|elem| elem as *mut $elem)
+                .unwrap_or(ptr::null_mut())
+        }
+
+        /// Returns the number of elements stored in the bucket vector.
+        ///
+        /// Returns 0 if the handle is null.
+        #[no_mangle]
+        pub extern "C" fn $len(handle: Option<&$handle>) -> usize {
+            handle.map(|handle| handle.vec.len()).unwrap_or(0)
+        }
+
+        /// Frees the bucket vector and all of its elements.
+        ///
+        /// Does nothing if the handle is null.
+        #[no_mangle]
+        pub extern "C" fn $free(handle: Option<Box<$handle>>) {
+            drop(handle)
+        }
+    };
+}
+
+impl_ffi_for! {
+    /// Opaque handle to a bucket vector of bytes.
+    BucketVecU8(u8) {
+        new: bucketvec_u8_new,
+        push: bucketvec_u8_push,
+        get_ptr: bucketvec_u8_get_ptr,
+        len: bucketvec_u8_len,
+        free: bucketvec_u8_free,
+    }
+}
+
+impl_ffi_for! {
+    /// Opaque handle to a bucket vector of 64-bit unsigned integers.
+    BucketVecU64(u64) {
+        new: bucketvec_u64_new,
+        push: bucketvec_u64_push,
+        get_ptr: bucketvec_u64_get_ptr,
+        len: bucketvec_u64_len,
+        free: bucketvec_u64_free,
+    }
+}
+
+impl_ffi_for! {
+    /// Opaque handle to a bucket vector of untyped pointers.
+    BucketVecPtr(*mut c_void) {
+        new: bucketvec_ptr_new,
+        push: bucketvec_ptr_push,
+        get_ptr: bucketvec_ptr_get_ptr,
+        len: bucketvec_ptr_len,
+        free: bucketvec_ptr_free,
+    }
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn ffi_works() {
+    let mut handle = bucketvec_u64_new();
+    assert_eq!(bucketvec_u64_len(Some(&handle)), 0);
+    assert!(bucketvec_u64_get_ptr(Some(&mut handle), 0).is_null());
+    let first = bucketvec_u64_push(Some(&mut handle), 42);
+    let first_ptr = bucketvec_u64_get_ptr(Some(&mut handle), first);
+    for value in 0..1000 {
+        assert_eq!(
+            bucketvec_u64_push(Some(&mut handle), value),
+            value as usize + 1
+        );
+    }
+    assert_eq!(bucketvec_u64_len(Some(&handle)), 1001);
+    assert_eq!(bucketvec_u64_get_ptr(Some(&mut handle), first), first_ptr);
+    assert!(bucketvec_u64_get_ptr(Some(&mut handle), 1001).is_null());
+    bucketvec_u64_free(Some(handle));
+    bucketvec_u64_free(None);
+
+    let mut bytes = bucketvec_u8_new();
+    let index = bucketvec_u8_push(Some(&mut bytes), 7);
+    assert!(!bucketvec_u8_get_ptr(Some(&mut bytes), index).is_null());
+    assert_eq!(bucketvec_u8_len(Some(&bytes)), 1);
+    bucketvec_u8_free(Some(bytes));
+
+    let mut ptrs = bucketvec_ptr_new();
+    bucketvec_ptr_push(Some(&mut ptrs), core::ptr::null_mut());
+    assert_eq!(bucketvec_ptr_len(Some(&ptrs)), 1);
+    bucketvec_ptr_free(Some(ptrs));
+
+    // Null handles are tolerated by every function.
+    assert_eq!(bucketvec_u64_push(None, 1), usize::MAX);
+    assert!(bucketvec_u64_get_ptr(None, 0).is_null());
+    assert_eq!(bucketvec_u64_len(None), 0);
+}
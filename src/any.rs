@@ -0,0 +1,200 @@
+//! Arena that stores values of different types.
+
+use super::{BucketVec, BucketVecConfig, DefaultConfig};
+use core::{
+    any::{Any, TypeId},
+    cmp::Ordering,
+    fmt, hash,
+    marker::PhantomData,
+    sync::atomic::{self, AtomicUsize},
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeMap};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// The identifier handed out to the next created arena.
+static NEXT_ARENA: AtomicUsize = AtomicUsize::new(0);
+
+/// A handle to a value of type `T` stored in a [`BucketAny`] arena.
+///
+/// Carries the identifier of the arena that returned it so that handles
+/// never resolve to values of another arena.
+pub struct AnyKey<T> {
+    /// The index of the referred to value among the values of type `T`.
+    index: usize,
+    /// The identifier of the arena storing the referred to value.
+    arena: usize,
+    /// Marker for the type of the referred to value.
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> AnyKey<T> {
+    /// Returns the index of the referred to value among the values of type `T`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<T> fmt::Debug for AnyKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AnyKey")
+            .field("index", &self.index)
+            .field("arena", &self.arena)
+            .finish()
+    }
+}
+
+impl<T> Copy for AnyKey<T> {}
+
+impl<T> Clone for AnyKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> PartialEq for AnyKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.arena == other.arena
+    }
+}
+
+impl<T> Eq for AnyKey<T> {}
+
+impl<T> PartialOrd for AnyKey<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for AnyKey<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.arena, self.index).cmp(&(other.arena, other.index))
+    }
+}
+
+impl<T> hash::Hash for AnyKey<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.arena.hash(state);
+    }
+}
+
+/// An arena that stores values of different types.
+///
+/// The values of every type are stored unboxed in a bucket vector of their
+/// own so that references to them stay valid across insertions of values
+/// of any type. Inserting a value returns a typed [`AnyKey`] resolving to it.
+#[derive(Debug)]
+pub struct BucketAny<C = DefaultConfig> {
+    /// The identifier of the arena stored in its keys.
+    id: usize,
+    /// The bucket vector of every type stored in the arena.
+    vecs: BTreeMap<TypeId, Box<dyn Any>>,
+    /// The number of values of all types.
+    len: usize,
+    /// The configuration of the bucket vectors.
+    config: PhantomData<fn() -> C>,
+}
+
+impl<C> Default for BucketAny<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> BucketAny<C> {
+    /// Creates a new empty arena.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_ARENA.fetch_add(1, atomic::Ordering::Relaxed),
+            vecs: BTreeMap::new(),
+            len: 0,
+            config: PhantomData,
+        }
+    }
+
+    /// Returns the number of values of all types stored in the arena.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the arena is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<C> BucketAny<C>
+where
+    C: BucketVecConfig + 'static,
+{
+    /// Returns the bucket vector storing all values of type `T` if any.
+    pub fn of_type<T>(&self) -> Option<&BucketVec<T, C>>
+    where
+        T: 'static,
+    {
+        self.vecs.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    /// Inserts the value and returns the key resolving to it.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other values, reallocates or otherwise
+    /// invalidate pointers of values of any type contained by the arena.
+    pub fn insert<T>(&mut self, new_value: T) -> AnyKey<T>
+    where
+        T: 'static,
+    {
+        let vec = self
+            .vecs
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(<BucketVec<T, C>>::new()))
+            .downcast_mut::<BucketVec<T, C>>()
+            .expect("encountered bucket vector of mismatching type");
+        let key = AnyKey {
+            index: vec.len(),
+            arena: self.id,
+            marker: PhantomData,
+        };
+        vec.push(new_value);
+        self.len += 1;
+        key
+    }
+
+    /// Returns a shared reference to the value of the key if any.
+    ///
+    /// Returns `None` for keys returned by other arenas.
+    pub fn get<T>(&self, key: AnyKey<T>) -> Option<&T>
+    where
+        T: 'static,
+    {
+        if key.arena != self.id {
+            return None;
+        }
+        self.of_type::<T>()?.get(key.index)
+    }
+
+    /// Returns an exclusive reference to the value of the key if any.
+    ///
+    /// Returns `None` for keys returned by other arenas.
+    pub fn get_mut<T>(&mut self, key: AnyKey<T>) -> Option<&mut T>
+    where
+        T: 'static,
+    {
+        if key.arena != self.id {
+            return None;
+        }
+        self.vecs
+            .get_mut(&TypeId::of::<T>())?
+            .downcast_mut::<BucketVec<T, C>>()?
+            .get_mut(key.index)
+    }
+}
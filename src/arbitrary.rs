@@ -0,0 +1,21 @@
+use super::{BucketVec, BucketVecConfig};
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+impl<'a, T, C> Arbitrary<'a> for BucketVec<T, C>
+where
+    T: Arbitrary<'a>,
+    C: BucketVecConfig,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.arbitrary_iter()?.collect()
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        u.arbitrary_take_rest_iter()?.collect()
+    }
+
+    #[inline]
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
@@ -0,0 +1,137 @@
+use super::{
+    with_layout::{LayoutHeader, WithLayout},
+    BucketVec, BucketVecConfig,
+};
+use bincode::{
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    BorrowDecode, Decode, Encode,
+};
+use core::convert::TryFrom;
+
+impl<T, C> Encode for BucketVec<T, C>
+where
+    T: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        // Encoded the same way as `Vec<T>` for interoperability.
+        (self.len() as u64).encode(encoder)?;
+        for elem in self {
+            elem.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the length prefix of an encoded bucket vector.
+///
+/// Claims the bytes of the announced elements so that configured decoding
+/// limits are respected before anything is allocated.
+fn decode_len<T, D: Decoder>(decoder: &mut D) -> Result<usize, DecodeError> {
+    let len = u64::decode(decoder)?;
+    let len = usize::try_from(len).map_err(|_| DecodeError::OutsideUsizeRange(len))?;
+    decoder.claim_container_read::<T>(len)?;
+    Ok(len)
+}
+
+impl<Context, T, C> Decode<Context> for BucketVec<T, C>
+where
+    C: BucketVecConfig,
+    T: Decode<Context>,
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = decode_len::<T, D>(decoder)?;
+        let mut vec = Self::new();
+        vec.reserve(len);
+        for _ in 0..len {
+            // The bytes of all elements have already been claimed up front.
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+            vec.push(<T as Decode<Context>>::decode(decoder)?);
+        }
+        Ok(vec)
+    }
+}
+
+impl<'de, Context, T, C> BorrowDecode<'de, Context> for BucketVec<T, C>
+where
+    C: BucketVecConfig,
+    T: BorrowDecode<'de, Context>,
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let len = decode_len::<T, D>(decoder)?;
+        let mut vec = Self::new();
+        vec.reserve(len);
+        for _ in 0..len {
+            // The bytes of all elements have already been claimed up front.
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+            vec.push(<T as BorrowDecode<'de, Context>>::borrow_decode(decoder)?);
+        }
+        Ok(vec)
+    }
+}
+
+impl<T, C> Encode for WithLayout<T, C>
+where
+    T: Encode,
+    C: BucketVecConfig,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let header = LayoutHeader::of::<C>();
+        header.version.encode(encoder)?;
+        header.starting_capacity.encode(encoder)?;
+        header.growth_rate.encode(encoder)?;
+        header.max_bucket_capacity.encode(encoder)?;
+        header.rounding.encode(encoder)?;
+        header.rounding_quantum.encode(encoder)?;
+        header.geometric.encode(encoder)?;
+        self.0.encode(encoder)
+    }
+}
+
+/// Decodes the layout header of a self-describing bucket vector and checks
+/// it against the configuration.
+fn decode_layout_header<C, D>(decoder: &mut D) -> Result<(), DecodeError>
+where
+    C: BucketVecConfig,
+    D: Decoder,
+{
+    let header = LayoutHeader {
+        version: u8::decode(decoder)?,
+        starting_capacity: u64::decode(decoder)?,
+        growth_rate: f64::decode(decoder)?,
+        max_bucket_capacity: Option::<u64>::decode(decoder)?,
+        rounding: u8::decode(decoder)?,
+        rounding_quantum: u64::decode(decoder)?,
+        geometric: bool::decode(decoder)?,
+    };
+    header
+        .check::<C>()
+        .map_err(|_| DecodeError::Other("bucket vector layout does not match its configuration"))
+}
+
+impl<Context, T, C> Decode<Context> for WithLayout<T, C>
+where
+    C: BucketVecConfig,
+    T: Decode<Context>,
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decode_layout_header::<C, D>(decoder)?;
+        BucketVec::decode(decoder).map(WithLayout)
+    }
+}
+
+impl<'de, Context, T, C> BorrowDecode<'de, Context> for WithLayout<T, C>
+where
+    C: BucketVecConfig,
+    T: BorrowDecode<'de, Context>,
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        decode_layout_header::<C, D>(decoder)?;
+        BucketVec::borrow_decode(decoder).map(WithLayout)
+    }
+}
@@ -0,0 +1,37 @@
+use super::{BucketVec, BucketVecConfig};
+use borsh::{
+    io::{Error, ErrorKind, Read, Result, Write},
+    BorshDeserialize, BorshSerialize,
+};
+use core::convert::TryFrom;
+
+impl<T, C> BorshSerialize for BucketVec<T, C>
+where
+    T: BorshSerialize,
+{
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        // Encoded the same way as `Vec<T>` for interoperability.
+        let len = u32::try_from(self.len())
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "bucket vector length exceeds u32"))?;
+        len.serialize(writer)?;
+        for elem in self {
+            elem.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, C> BorshDeserialize for BucketVec<T, C>
+where
+    C: BucketVecConfig,
+    T: BorshDeserialize,
+{
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let len = <u32 as BorshDeserialize>::deserialize_reader(reader)?;
+        let mut vec = Self::new();
+        for _ in 0..len {
+            vec.push(<T as BorshDeserialize>::deserialize_reader(reader)?);
+        }
+        Ok(vec)
+    }
+}
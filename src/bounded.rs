@@ -0,0 +1,145 @@
+//! Bucket vector with a fixed capacity that overwrites its oldest elements.
+
+use super::{BucketVec, BucketVecConfig, DefaultConfig};
+
+/// A bucket vector with a fixed capacity that acts as a ring buffer once full.
+///
+/// Pushing onto a full bounded bucket vector overwrites its oldest element
+/// in place instead of allocating. The slots therefore never move and only
+/// the values stored in them rotate. Elements are indexed from the oldest
+/// to the newest one.
+#[derive(Debug, Clone)]
+pub struct BoundedBucketVec<T, C = DefaultConfig> {
+    /// The slots of the ring buffer.
+    values: BucketVec<T, C>,
+    /// The maximum number of elements.
+    capacity: usize,
+    /// The slot of the oldest element.
+    ///
+    /// Always zero until the bounded bucket vector is full.
+    head: usize,
+}
+
+impl<T, C> BoundedBucketVec<T, C> {
+    /// Creates a new empty bounded bucket vector holding at most `capacity` elements.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: BucketVec::new(),
+            capacity,
+            head: 0,
+        }
+    }
+
+    /// Returns the maximum number of elements of the bounded bucket vector.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of elements stored in the bounded bucket vector.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the bounded bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` if the next push overwrites the oldest element.
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.capacity
+    }
+
+    /// Returns an iterator over shared references to the elements from the
+    /// oldest to the newest one.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        let (newest, oldest) = (self.values.iter(), self.values.iter());
+        oldest.skip(self.head).chain(newest.take(self.head))
+    }
+
+    /// Returns the underlying bucket vector with the elements in slot order.
+    pub fn as_bucket_vec(&self) -> &BucketVec<T, C> {
+        &self.values
+    }
+
+    /// Returns the slot of the element at the given index.
+    fn slot(&self, index: usize) -> Option<usize> {
+        let len = self.values.len();
+        if index >= len {
+            return None;
+        }
+        Some((self.head + index) % len)
+    }
+}
+
+impl<T, C> BoundedBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the element at the given index if any.
+    ///
+    /// The oldest element has index zero.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(self.slot(index)?)
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    ///
+    /// The oldest element has index zero.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let slot = self.slot(index)?;
+        self.values.get_mut(slot)
+    }
+
+    /// Returns a shared reference to the oldest element if any.
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a shared reference to the newest element if any.
+    pub fn last(&self) -> Option<&T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    /// Pushes the value and returns the overwritten oldest element if the
+    /// bounded bucket vector was full.
+    ///
+    /// # Note
+    ///
+    /// The value is returned back if the capacity is zero.
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bounded bucket vector.
+    pub fn push(&mut self, new_value: T) -> Option<T> {
+        if self.capacity == 0 {
+            return Some(new_value);
+        }
+        if !self.is_full() {
+            self.values.push(new_value);
+            return None;
+        }
+        let head = self.head;
+        self.head = (head + 1) % self.capacity;
+        self.values
+            .get_mut(head)
+            .map(|slot| core::mem::replace(slot, new_value))
+    }
+}
+
+impl<T, C> Extend<T> for BoundedBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
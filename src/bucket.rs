@@ -8,12 +8,24 @@ use std::vec;
 use alloc::vec;
 
 /// An fixed capacity bucket within the bucket vector.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Bucket<T> {
     /// The entries of this bucket.
     entries: Vec<T>,
 }
 
+impl<T> Clone for Bucket<T>
+where
+    T: Clone,
+{
+    /// Clones the bucket preserving its capacity.
+    fn clone(&self) -> Self {
+        let mut entries = Vec::with_capacity(self.capacity());
+        entries.extend_from_slice(&self.entries);
+        Self { entries }
+    }
+}
+
 impl<T> Bucket<T> {
     /// Creates a new emtpy bucket with a fixed capacity.
     ///
@@ -26,6 +38,20 @@ impl<T> Bucket<T> {
         }
     }
 
+    /// Creates a new empty bucket with a fixed capacity.
+    ///
+    /// Returns `None` if allocating the entries fails.
+    pub fn try_new(capacity: usize) -> Option<Self> {
+        let mut entries = Vec::new();
+        entries.try_reserve_exact(capacity).ok()?;
+        Some(Self { entries })
+    }
+
+    /// Creates a new bucket from the given entries keeping their capacity.
+    pub fn from_vec(entries: Vec<T>) -> Self {
+        Self { entries }
+    }
+
     /// Returns the current length of the entry.
     pub fn len(&self) -> usize {
         self.entries.len()
@@ -74,13 +100,69 @@ impl<T> Bucket<T> {
         self.entries.push(new_value);
     }
 
+    /// Removes the last value of the entry and returns it if any.
+    pub fn pop(&mut self) -> Option<T> {
+        self.entries.pop()
+    }
+
+    /// Shortens the entry to the given length dropping all values beyond it.
+    pub fn truncate(&mut self, len: usize) {
+        self.entries.truncate(len)
+    }
+
+    /// Clones all of the given values into the fixed capacity entry.
+    ///
+    /// # Panics
+    ///
+    /// If the values do not fit into the spare capacity of the entry.
+    pub fn extend_from_slice(&mut self, values: &[T])
+    where
+        T: Clone,
+    {
+        if values.len() > self.capacity() - self.len() {
+            panic!("entry has not enough spare capacity")
+        }
+        self.entries.extend_from_slice(values);
+    }
+
+    /// Pushes `count` clones of the given value into the fixed capacity entry.
+    ///
+    /// # Panics
+    ///
+    /// If the clones do not fit into the spare capacity of the entry.
+    pub fn push_repeated(&mut self, count: usize, value: T)
+    where
+        T: Clone,
+    {
+        if count > self.capacity() - self.len() {
+            panic!("entry has not enough spare capacity")
+        }
+        let len = self.len();
+        self.entries.resize(len + count, value);
+    }
+
+    /// Returns the entries of the bucket as shared slice.
+    pub fn as_slice(&self) -> &[T] {
+        &self.entries
+    }
+
+    /// Returns the entries of the bucket as exclusive slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.entries
+    }
+
+    /// Returns the entries of the bucket as vector.
+    pub fn into_vec(self) -> Vec<T> {
+        self.entries
+    }
+
     /// Returns an iterator over the entries of the bucket.
-    pub fn iter(&self) -> core::slice::Iter<T> {
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
         self.entries.iter()
     }
 
     /// Returns an iterator over the entries of the bucket.
-    pub fn iter_mut(&mut self) -> core::slice::IterMut<T> {
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
         self.entries.iter_mut()
     }
 }
@@ -97,13 +179,27 @@ impl<T> IntoIterator for Bucket<T> {
 impl<T> core::ops::Index<usize> for Bucket<T> {
     type Output = T;
 
+    #[track_caller]
     fn index(&self, index: usize) -> &Self::Output {
-        self.get(index).expect("index out of bounds")
+        let len = self.len();
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "entry index {} is out of bounds for bucket of length {}",
+                index, len
+            )
+        })
     }
 }
 
 impl<T> core::ops::IndexMut<usize> for Bucket<T> {
+    #[track_caller]
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        self.get_mut(index).expect("index out of bounds")
+        let len = self.len();
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!(
+                "entry index {} is out of bounds for bucket of length {}",
+                index, len
+            )
+        })
     }
 }
@@ -0,0 +1,131 @@
+//! Bucket vector whose buckets are allocated from a `bumpalo` arena.
+
+use super::{config, BucketVecConfig, DefaultConfig};
+use bumpalo::{collections::Vec as BumpVec, Bump};
+use core::marker::PhantomData;
+
+/// A bucket vector whose buckets are allocated from a [`Bump`] arena.
+///
+/// All memory is freed wholesale once the arena is reset or dropped which is
+/// useful for many short-lived bucket vectors.
+#[derive(Debug)]
+pub struct BumpBucketVec<'bump, T, C = DefaultConfig> {
+    /// The number of elements stored in the bucket vector.
+    len: usize,
+    /// The entry vector.
+    buckets: BumpVec<'bump, BumpVec<'bump, T>>,
+    /// The config phantom data.
+    config: PhantomData<fn() -> C>,
+}
+
+impl<'bump, T, C> BumpBucketVec<'bump, T, C> {
+    /// Creates a new empty bucket vector allocating from the given arena.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any memory.
+    pub fn new_in(bump: &'bump Bump) -> Self {
+        Self {
+            len: 0,
+            buckets: BumpVec::new_in(bump),
+            config: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements stored in the bucket vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator that yields shared references to the elements of the bucket vector.
+    pub fn iter(&self) -> BumpIter<'_, 'bump, T> {
+        BumpIter {
+            entries: self.buckets.iter().flatten(),
+        }
+    }
+}
+
+impl<'bump, T, C> BumpBucketVec<'bump, T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns the bucket and entry indices of the element at the given index.
+    fn bucket_entry_indices(index: usize) -> (usize, usize) {
+        if core::mem::size_of::<T>() == 0 {
+            // Zero-sized elements are all stored in the first bucket.
+            return (0, index);
+        }
+        config::bucket_entry_indices::<C>(index)
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        let (x, y) = Self::bucket_entry_indices(index);
+        self.buckets.get(x)?.get(y)
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let (x, y) = Self::bucket_entry_indices(index);
+        self.buckets.get_mut(x)?.get_mut(y)
+    }
+
+    /// Pushes a new element onto the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push(&mut self, new_value: T) {
+        if let Some(bucket) = self.buckets.last_mut() {
+            if bucket.len() < bucket.capacity() {
+                bucket.push(new_value);
+                self.len += 1;
+                return;
+            }
+        }
+        let capacity = config::allocate_bucket::<C>(self.buckets.len());
+        let mut bucket = BumpVec::with_capacity_in(capacity, self.buckets.bump());
+        bucket.push(new_value);
+        self.buckets.push(bucket);
+        self.len += 1;
+    }
+}
+
+/// An iterator yielding shared references to the elements of a bump bucket vector.
+#[derive(Debug, Clone)]
+pub struct BumpIter<'a, 'bump, T> {
+    /// The entries of all buckets.
+    entries: core::iter::Flatten<core::slice::Iter<'a, BumpVec<'bump, T>>>,
+}
+
+impl<'a, 'bump, T> Iterator for BumpIter<'a, 'bump, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a, 'bump, T> DoubleEndedIterator for BumpIter<'a, 'bump, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back()
+    }
+}
+
+impl<'a, 'bump, T> core::iter::FusedIterator for BumpIter<'a, 'bump, T> {}
@@ -0,0 +1,95 @@
+//! Zero-copy byte views of bucket vectors with plain old data elements.
+
+use super::{storage::BucketSchedule, BucketVec, BucketVecConfig};
+use bytemuck::{Pod, Zeroable};
+
+impl<T, C> BucketVec<T, C>
+where
+    T: Pod,
+{
+    /// Returns the bytes of the elements of every bucket in order.
+    ///
+    /// This allows to dump the bucket vector without serializing its
+    /// elements one by one.
+    pub fn bucket_bytes(&self) -> impl Iterator<Item = &[u8]> {
+        self.buckets
+            .iter()
+            .map(|bucket| bytemuck::cast_slice(bucket.as_slice()))
+    }
+
+    /// Returns the mutable bytes of the elements of every bucket in order.
+    pub fn bucket_bytes_mut(&mut self) -> impl Iterator<Item = &mut [u8]> {
+        self.buckets
+            .iter_mut()
+            .map(|bucket| bytemuck::cast_slice_mut(bucket.as_mut_slice()))
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    T: Pod,
+    C: BucketVecConfig,
+{
+    /// Pushes the elements encoded by the given bytes onto the bucket vector.
+    ///
+    /// The bytes are copied in bulk into the spare capacity of the buckets
+    /// and do not need to be aligned for the element type.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    ///
+    /// # Panics
+    ///
+    /// If the number of bytes is not a multiple of the element size.
+    /// For zero-sized elements this is the case for any non-empty bytes.
+    // `usize::is_multiple_of` requires Rust 1.87.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn extend_from_bytes(&mut self, bytes: &[u8]) {
+        let size = core::mem::size_of::<T>();
+        assert!(
+            if size == 0 {
+                bytes.is_empty()
+            } else {
+                bytes.len() % size == 0
+            },
+            "number of bytes must be a multiple of the element size"
+        );
+        let mut bytes = bytes;
+        while !bytes.is_empty() {
+            self.ensure_spare_bucket();
+            let bucket = self
+                .buckets
+                .last_mut()
+                .expect("encountered missing bucket with spare capacity");
+            let start = bucket.len();
+            let count = core::cmp::min(bucket.capacity() - start, bytes.len() / size);
+            bucket.push_repeated(count, T::zeroed());
+            let (head, tail) = bytes.split_at(count * size);
+            bytemuck::cast_slice_mut::<T, u8>(&mut bucket.as_mut_slice()[start..])
+                .copy_from_slice(head);
+            bytes = tail;
+            self.len += count;
+            self.sync_spare();
+        }
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    T: Zeroable + Clone,
+    C: BucketVecConfig,
+{
+    /// Pushes `count` zeroed elements onto the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// The zeroed elements fill the spare capacity of the buckets in bulk
+    /// which makes pre-extending by millions of elements cheap.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push_zeroed(&mut self, count: usize) {
+        self.push_repeated(count, T::zeroed())
+    }
+}
@@ -0,0 +1,28 @@
+//! Arena-style API for byte bucket vectors.
+
+use super::{BucketVec, BucketVecConfig};
+
+impl<C> BucketVec<u8, C>
+where
+    C: BucketVecConfig,
+{
+    /// Pushes the given bytes so that they are stored contiguously within a
+    /// single bucket and returns the stored bytes.
+    ///
+    /// # Note
+    ///
+    /// If the bytes do not fit into the spare capacity of the last bucket it
+    /// is filled up with zeros and so is every following bucket until one is
+    /// large enough to hold the bytes. The zeros count towards the length of
+    /// the bucket vector.
+    ///
+    /// # Panics
+    ///
+    /// If no following bucket is large enough to hold all bytes.
+    pub fn alloc_slice(&mut self, bytes: &[u8]) -> &[u8] {
+        let (_, stored) = self
+            .push_slice_padded(bytes, &0)
+            .expect("bytes exceed the capacity of every following bucket");
+        stored
+    }
+}
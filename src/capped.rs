@@ -0,0 +1,149 @@
+//! Bucket vector whose length is bounded at compile time.
+
+use super::{storage::BucketStorage, BucketVec, BucketVecConfig, DefaultConfig, Iter, IterMut};
+use core::convert::TryFrom;
+
+/// A bucket vector holding at most `MAX_LEN` elements.
+///
+/// Pushing onto a full capped bucket vector fails and hands the value back.
+/// Since the maximum length is known at compile time the encoded size is
+/// bounded as well which allows to implement `MaxEncodedLen` (requires the
+/// `scale-3` crate feature).
+#[derive(Debug)]
+pub struct CappedBucketVec<T, const MAX_LEN: usize, C = DefaultConfig> {
+    /// The elements of the capped bucket vector.
+    values: BucketVec<T, C>,
+}
+
+impl<T, const MAX_LEN: usize, C> Clone for CappedBucketVec<T, MAX_LEN, C>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<T, const MAX_LEN: usize, C> PartialEq for CappedBucketVec<T, MAX_LEN, C>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<T, const MAX_LEN: usize, C> Eq for CappedBucketVec<T, MAX_LEN, C> where T: Eq {}
+
+impl<T, const MAX_LEN: usize, C> Default for CappedBucketVec<T, MAX_LEN, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const MAX_LEN: usize, C> TryFrom<BucketVec<T, C>> for CappedBucketVec<T, MAX_LEN, C> {
+    type Error = BucketVec<T, C>;
+
+    /// Caps the bucket vector or returns it back if it holds more than
+    /// `MAX_LEN` elements.
+    fn try_from(values: BucketVec<T, C>) -> Result<Self, Self::Error> {
+        if values.len() > MAX_LEN {
+            return Err(values);
+        }
+        Ok(Self { values })
+    }
+}
+
+impl<T, const MAX_LEN: usize, C> CappedBucketVec<T, MAX_LEN, C> {
+    /// Creates a new empty capped bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            values: BucketVec::new(),
+        }
+    }
+
+    /// Returns the maximum number of elements of the capped bucket vector.
+    pub const fn max_len() -> usize {
+        MAX_LEN
+    }
+
+    /// Returns the number of elements stored in the capped bucket vector.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the capped bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` if the next push fails.
+    pub fn is_full(&self) -> bool {
+        self.values.len() == MAX_LEN
+    }
+
+    /// Returns an iterator over shared references to the elements.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.values.iter()
+    }
+
+    /// Returns an iterator over exclusive references to the elements.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.values.iter_mut()
+    }
+
+    /// Returns the underlying bucket vector.
+    pub fn as_bucket_vec(&self) -> &BucketVec<T, C> {
+        &self.values
+    }
+
+    /// Returns the underlying bucket vector.
+    pub fn into_bucket_vec(self) -> BucketVec<T, C> {
+        self.values
+    }
+}
+
+impl<T, const MAX_LEN: usize, C> CappedBucketVec<T, MAX_LEN, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.values.get_mut(index)
+    }
+
+    /// Pushes the value onto the capped bucket vector and returns its index.
+    ///
+    /// # Errors
+    ///
+    /// Returns the value back if the capped bucket vector is full.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the capped bucket vector.
+    pub fn try_push(&mut self, new_value: T) -> Result<usize, T> {
+        if self.is_full() {
+            return Err(new_value);
+        }
+        let index = self.values.len();
+        self.values.push(new_value);
+        Ok(index)
+    }
+
+    /// Removes the last element and returns it if any.
+    pub fn pop(&mut self) -> Option<T> {
+        self.values.pop()
+    }
+}
@@ -0,0 +1,94 @@
+//! Shared mutable view of the elements of a bucket vector.
+
+use super::{BucketVec, BucketVecConfig};
+use core::cell::Cell;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A view of the elements of a bucket vector as [`Cell`]s.
+///
+/// Since cells can be mutated through shared references the elements can
+/// be mutated by multiple cooperating closures at the same time.
+pub struct Cells<'a, T> {
+    /// The cells of every bucket.
+    buckets: Vec<&'a [Cell<T>]>,
+    /// The index of the first element of every bucket.
+    offsets: &'a [usize],
+    /// The number of elements viewed.
+    len: usize,
+}
+
+impl<'a, T> core::fmt::Debug for Cells<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Cells").field("len", &self.len).finish()
+    }
+}
+
+impl<'a, T> Clone for Cells<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            buckets: self.buckets.clone(),
+            offsets: self.offsets,
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, T> Cells<'a, T> {
+    /// Returns the number of elements viewed.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no elements are viewed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the cell of the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&'a Cell<T>> {
+        if index >= self.len {
+            return None;
+        }
+        let x = self.offsets.partition_point(|&offset| offset <= index) - 1;
+        self.buckets[x].get(index - self.offsets[x])
+    }
+
+    /// Returns an iterator over the cells of all elements in order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &'a Cell<T>> + '_ {
+        self.buckets.iter().flat_map(|cells| cells.iter())
+    }
+}
+
+impl<T, C> BucketVec<T, C> {
+    /// Returns a view of all elements as cells that can be mutated through
+    /// shared references.
+    ///
+    /// # Note
+    ///
+    /// Allocates a temporary buffer of one slice per bucket.
+    pub fn cells(&mut self) -> Cells<'_, T> {
+        let len = self.len();
+        let buckets = self
+            .buckets
+            .iter_mut()
+            .map(|bucket| Cell::from_mut(bucket.as_mut_slice()).as_slice_of_cells())
+            .collect();
+        Cells {
+            buckets,
+            offsets: &self.offsets,
+            len,
+        }
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns the cell of the element at the given index if any.
+    pub fn get_cell(&mut self, index: usize) -> Option<&Cell<T>> {
+        self.get_mut(index).map(Cell::from_mut)
+    }
+}
@@ -0,0 +1,57 @@
+//! Speculative pushes onto bucket vectors that can be rolled back.
+
+use super::{storage::BucketStorage, BucketVec};
+
+/// The recorded length of a bucket vector to roll back to.
+///
+/// Returned by [`BucketVec::checkpoint`] and accepted by
+/// [`BucketVec::rollback_to`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checkpoint {
+    /// The length of the bucket vector upon creation of the checkpoint.
+    len: usize,
+}
+
+impl Checkpoint {
+    /// Returns the length of the bucket vector upon creation of the checkpoint.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bucket vector was empty upon creation of the checkpoint.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T, C> BucketVec<T, C> {
+    /// Records the current length of the bucket vector.
+    ///
+    /// Pass the returned checkpoint to [`BucketVec::rollback_to`] in order to
+    /// drop all elements pushed in the meantime.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint { len: self.len() }
+    }
+
+    /// Drops all elements pushed since the checkpoint has been created.
+    ///
+    /// # Note
+    ///
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    ///
+    /// # Panics
+    ///
+    /// If the bucket vector has been shortened below the length recorded by
+    /// the checkpoint, e.g. by rolling back to an earlier checkpoint.
+    pub fn rollback_to(&mut self, checkpoint: Checkpoint) {
+        assert!(
+            checkpoint.len <= self.len(),
+            "checkpoint length {} exceeds bucket vector length {}",
+            checkpoint.len,
+            self.len()
+        );
+        self.truncate(checkpoint.len);
+    }
+}
@@ -0,0 +1,259 @@
+//! Append-only bucket vector that can be pushed to concurrently.
+
+use super::{config, frozen::directory_indices, BucketVecConfig, DefaultConfig};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::sync::OnceLock;
+
+/// The lazily initialized entries of a single bucket.
+type Entries<T> = Box<[OnceLock<T>]>;
+
+/// A node of the linked list of bucket directories.
+///
+/// The `k`th directory holds `2^k` buckets so that locating a bucket takes
+/// time logarithmic in the number of buckets.
+#[derive(Debug)]
+struct Directory<T> {
+    /// The lazily allocated buckets of this directory.
+    buckets: Box<[OnceLock<Entries<T>>]>,
+    /// The next directory holding twice as many buckets.
+    next: OnceLock<Box<Directory<T>>>,
+}
+
+impl<T> Directory<T> {
+    /// Creates a new directory with the given number of buckets.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate the buckets themselves.
+    fn new(len_buckets: usize) -> Self {
+        Self {
+            buckets: (0..len_buckets).map(|_| OnceLock::new()).collect(),
+            next: OnceLock::new(),
+        }
+    }
+}
+
+/// An append-only bucket vector that multiple threads can push to concurrently.
+///
+/// Pushing atomically reserves an index and initializes its entry exactly
+/// once. Buckets are allocated once by whichever thread requires them first
+/// before the index is reserved so that a push that panics while allocating
+/// leaves the bucket vector unchanged.
+/// Readers are wait-free and observe every element whose push has completed.
+///
+/// # Note
+///
+/// This is not lock-free: bucket allocation is blocking. Pushers that
+/// require a bucket that another thread is still allocating wait until the
+/// allocation has completed. Pushes into already allocated buckets never
+/// wait.
+///
+/// Locating an element takes time logarithmic in the number of buckets.
+#[derive(Debug)]
+pub struct ConcurrentBucketVec<T, C = DefaultConfig> {
+    /// The number of reserved indices.
+    len: AtomicUsize,
+    /// The first bucket directory.
+    directories: OnceLock<Box<Directory<T>>>,
+    /// The config phantom data.
+    config: PhantomData<fn() -> C>,
+}
+
+impl<T, C> Default for ConcurrentBucketVec<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> ConcurrentBucketVec<T, C> {
+    /// Creates a new empty concurrent bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            len: AtomicUsize::new(0),
+            directories: OnceLock::new(),
+            config: PhantomData,
+        }
+    }
+
+    /// Returns the number of indices reserved by pushes so far.
+    ///
+    /// # Note
+    ///
+    /// Pushes that are still in progress are included.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if no push has been started, yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the committed prefix of the bucket vector.
+    ///
+    /// Stops at the first element whose push has not completed, yet.
+    pub fn iter(&self) -> ConcurrentIter<'_, T, C> {
+        ConcurrentIter {
+            vec: self,
+            index: 0,
+            len: self.len(),
+        }
+    }
+}
+
+impl<T, C> ConcurrentBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns the entries of the bucket with the given index if allocated.
+    fn bucket(&self, bucket_index: usize) -> Option<&Entries<T>> {
+        let (directory_index, index) = directory_indices(bucket_index);
+        let mut directory = self.directories.get()?;
+        for _ in 0..directory_index {
+            directory = directory.next.get()?;
+        }
+        directory.buckets[index].get()
+    }
+
+    /// Returns the entries of the bucket with the given index.
+    ///
+    /// Allocates the bucket and its directories if necessary.
+    fn bucket_or_alloc(&self, bucket_index: usize) -> &Entries<T> {
+        let (directory_index, index) = directory_indices(bucket_index);
+        let mut directory = self.directories.get_or_init(|| Box::new(Directory::new(1)));
+        for _ in 0..directory_index {
+            let len_buckets = directory.buckets.len() * 2;
+            directory = directory
+                .next
+                .get_or_init(|| Box::new(Directory::new(len_buckets)));
+        }
+        directory.buckets[index].get_or_init(|| {
+            (0..config::allocate_bucket::<C>(bucket_index))
+                .map(|_| OnceLock::new())
+                .collect()
+        })
+    }
+
+    /// Pushes a new element onto the bucket vector and returns its index
+    /// together with a reference to it.
+    ///
+    /// # Note
+    ///
+    /// Blocks while another thread allocates the required bucket.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    ///
+    /// # Panics
+    ///
+    /// If allocating the required bucket panics, e.g. upon capacity overflow
+    /// or in the configuration hook. No index is reserved in this case.
+    pub fn push(&self, new_value: T) -> (usize, &T) {
+        let mut index = self.len.load(Ordering::Acquire);
+        let entry = loop {
+            // Allocate the bucket before publishing the index so that a
+            // panicking allocation never leaves behind a reserved hole.
+            let next = index.checked_add(1).expect("capacity overflow");
+            let (x, y) = config::bucket_entry_indices::<C>(index);
+            let entry = &self.bucket_or_alloc(x)[y];
+            match self
+                .len
+                .compare_exchange_weak(index, next, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => break entry,
+                Err(current) => index = current,
+            }
+        };
+        if entry.set(new_value).is_err() {
+            panic!("encountered already occupied entry")
+        }
+        (
+            index,
+            entry.get().expect("encountered missing pushed element"),
+        )
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    ///
+    /// Returns `None` if the push of the element has not completed, yet.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        let (x, y) = config::bucket_entry_indices::<C>(index);
+        self.bucket(x)?[y].get()
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+        let (x, y) = config::bucket_entry_indices::<C>(index);
+        let (directory_index, index) = directory_indices(x);
+        let mut directory = self.directories.get_mut()?;
+        for _ in 0..directory_index {
+            directory = directory.next.get_mut()?;
+        }
+        directory.buckets[index].get_mut()?[y].get_mut()
+    }
+}
+
+/// An iterator over the committed prefix of a concurrent bucket vector.
+///
+/// Only yields elements whose indices have been reserved before its creation.
+#[derive(Debug)]
+pub struct ConcurrentIter<'a, T, C> {
+    /// The iterated bucket vector.
+    vec: &'a ConcurrentBucketVec<T, C>,
+    /// The index of the next element to yield.
+    index: usize,
+    /// The index one past the last element to yield.
+    len: usize,
+}
+
+impl<'a, T, C> Clone for ConcurrentIter<'a, T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            vec: self.vec,
+            index: self.index,
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, T, C> Iterator for ConcurrentIter<'a, T, C>
+where
+    C: BucketVecConfig,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        match self.vec.get(self.index) {
+            Some(next) => {
+                self.index += 1;
+                Some(next)
+            }
+            None => {
+                // Stop at the first element that is still being pushed.
+                self.len = self.index;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.len - self.index))
+    }
+}
+
+impl<'a, T, C> core::iter::FusedIterator for ConcurrentIter<'a, T, C> where C: BucketVecConfig {}
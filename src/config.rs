@@ -1,6 +1,31 @@
-use crate::FloatExt;
+use crate::math;
 
 /// Basic configs of a bucket vector.
+///
+/// # Validation
+///
+/// Invalid configurations fail to compile as soon as they are used.
+///
+/// ```compile_fail
+/// # use bucket_vec::{BucketVec, BucketVecConfig};
+/// enum ZeroConfig {}
+///
+/// impl BucketVecConfig for ZeroConfig {
+///     const STARTING_CAPACITY: usize = 0;
+///     const GROWTH_RATE: f64 = 2.0;
+/// }
+///
+/// let mut vec = BucketVec::<i32, ZeroConfig>::new();
+/// vec.push(1);
+/// ```
+///
+/// This includes infinite growth rates, e.g. of a [`ConstConfig`] with a zero denominator.
+///
+/// ```compile_fail
+/// # use bucket_vec::{BucketVec, ConstConfig};
+/// let mut vec = BucketVec::<i32, ConstConfig<4, 2, 0>>::new();
+/// vec.push(1);
+/// ```
 pub trait BucketVecConfig {
     /// The capacity of the first entry of the bucket vector.
     ///
@@ -8,10 +33,145 @@ pub trait BucketVecConfig {
     const STARTING_CAPACITY: usize;
     /// The rate with which the buckets are extended in their capacity.
     ///
-    /// This value must be larger than or equal to `1`.
+    /// This value must be finite and larger than or equal to `1`.
     /// Bigger values increase the growth acceleration upon pushing elements.
     /// A value of `1` renders all buckets equally sized.
     const GROWTH_RATE: f64;
+    /// The capacity at which bucket capacities stop growing.
+    ///
+    /// Once a bucket reaches this capacity all subsequent buckets are
+    /// equally sized. This bounds the memory wasted by the last bucket.
+    /// If `None` bucket capacities grow without bounds.
+    ///
+    /// This value must be larger than or equal to `1`.
+    const MAX_BUCKET_CAPACITY: Option<usize> = None;
+    /// Whether the bucket capacities follow the geometric schedule determined
+    /// by `STARTING_CAPACITY` and `GROWTH_RATE`.
+    ///
+    /// Must be set to `false` if `bucket_capacity` is overridden.
+    /// Bucket vectors panic upon allocating any of the first buckets if their
+    /// capacities deviate from the geometric schedule while this is `true`.
+    const GEOMETRIC: bool = true;
+    /// How the capacities of the geometric schedule are rounded up.
+    ///
+    /// Fractional growth rates yield odd capacities such as `1013` whose
+    /// allocations leave slack in the size class of the allocator unused.
+    /// Rounding capacities up, e.g. to powers of two, puts this slack to use.
+    ///
+    /// Capacities are rounded after they have been capped by
+    /// `MAX_BUCKET_CAPACITY` so that rounded capacities may exceed it.
+    /// Ignored if `bucket_capacity` is overridden.
+    ///
+    /// # Note
+    ///
+    /// Rounded schedules look up elements in a table of total capacities
+    /// computed at compile time instead of using closed formulas. Schedules
+    /// that grow too slowly to be tabulated walk their buckets instead.
+    const CAPACITY_ROUNDING: CapacityRounding = CapacityRounding::Exact;
+
+    /// Returns the capacity of the bucket indexed by `bucket_index`.
+    ///
+    /// By default bucket capacities grow geometrically according to
+    /// `STARTING_CAPACITY` and `GROWTH_RATE`.
+    /// Overriding this allows for arbitrary growth schedules, e.g. plateauing
+    /// or table-driven ones, at the cost of slower element lookup for some
+    /// of the bucket vector types.
+    ///
+    /// The returned capacity must be larger than or equal to `1`.
+    ///
+    /// # Panics
+    ///
+    /// If the total capacity up to the indexed bucket overflows `usize`.
+    fn bucket_capacity(bucket_index: usize) -> usize {
+        geometric_bucket_capacity::<Self>(bucket_index)
+    }
+
+    /// Returns the largest capacity of any bucket or `None` if bucket
+    /// capacities grow without bounds.
+    ///
+    /// Must be overridden together with `bucket_capacity` if the custom
+    /// schedule is bounded.
+    fn max_bucket_capacity() -> Option<usize> {
+        let capacity = if math::is_unit_growth_rate(Self::GROWTH_RATE) {
+            let capacity = Self::STARTING_CAPACITY;
+            Some(Self::MAX_BUCKET_CAPACITY.map_or(capacity, |max| capacity.min(max)))
+        } else {
+            Self::MAX_BUCKET_CAPACITY
+        };
+        capacity.and_then(|capacity| Self::CAPACITY_ROUNDING.round(capacity))
+    }
+
+    /// Called whenever a bucket vector using this configuration allocates a
+    /// new bucket.
+    ///
+    /// Receives the index of the bucket within the capacity schedule and its
+    /// capacity. Does nothing by default.
+    ///
+    /// This allows to detect or log allocations, e.g. in the hot path of
+    /// real-time systems.
+    #[inline]
+    fn on_bucket_allocated(_bucket_index: usize, _capacity: usize) {}
+
+    /// Returns the number of buckets required to hold `len` elements.
+    fn buckets_needed(len: usize) -> usize
+    where
+        Self: Sized,
+    {
+        if len == 0 {
+            return 0;
+        }
+        bucket_entry_indices::<Self>(len - 1).0 + 1
+    }
+
+    /// Returns the total capacity of the first `len_buckets` buckets.
+    ///
+    /// # Panics
+    ///
+    /// If the total capacity overflows `usize`.
+    fn total_capacity_for(len_buckets: usize) -> usize
+    where
+        Self: Sized,
+    {
+        total_capacity::<Self>(len_buckets)
+    }
+
+    /// Returns the number of unused entries of a bucket vector holding
+    /// `len` elements.
+    fn wasted_for(len: usize) -> usize
+    where
+        Self: Sized,
+    {
+        Self::total_capacity_for(Self::buckets_needed(len)) - len
+    }
+}
+
+/// The size classes bucket capacities are rounded up to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CapacityRounding {
+    /// Bucket capacities are used as computed.
+    Exact,
+    /// Bucket capacities are rounded up to the next power of two.
+    PowerOfTwo,
+    /// Bucket capacities are rounded up to the next multiple of the quantum.
+    ///
+    /// The quantum must be larger than or equal to `1`.
+    MultipleOf(usize),
+}
+
+impl CapacityRounding {
+    /// Returns the capacity rounded up to its size class.
+    ///
+    /// Returns `None` if the rounded capacity overflows `usize`.
+    pub const fn round(self, capacity: usize) -> Option<usize> {
+        match self {
+            Self::Exact => Some(capacity),
+            Self::PowerOfTwo => capacity.checked_next_power_of_two(),
+            Self::MultipleOf(quantum) => match capacity % quantum {
+                0 => Some(capacity),
+                rest => capacity.checked_add(quantum - rest),
+            },
+        }
+    }
 }
 
 /// The default configuration for bucket vectors.
@@ -25,66 +185,690 @@ impl BucketVecConfig for DefaultConfig {
     const GROWTH_RATE: f64 = 2.0;
 }
 
-/// Returns the total capacity of all buckets up to (and including) the
-/// bucket indexed by `index`.
-pub fn total_capacity<C>(index: usize) -> usize
+/// A configuration for bucket vectors where all buckets have a capacity of `N`.
+///
+/// # Index Math
+///
+/// Looking up an element costs a single division.
+#[derive(Debug)]
+pub enum EqualSizeConfig<const N: usize> {}
+
+impl<const N: usize> BucketVecConfig for EqualSizeConfig<N> {
+    /// The first bucket has a capacity of `N`.
+    const STARTING_CAPACITY: usize = N;
+    /// All buckets have the same capacity as the first bucket.
+    const GROWTH_RATE: f64 = 1.0;
+}
+
+/// A configuration for bucket vectors whose bucket capacities grow like the
+/// Fibonacci sequence.
+///
+/// # Index Math
+///
+/// Since the growth rate is fractional [`BucketVec`](crate::BucketVec) looks
+/// up elements with a binary search over its buckets.
+/// Other bucket vector types look up elements in a table of bucket
+/// capacities computed at compile time.
+#[derive(Debug)]
+pub enum FibonacciConfig {}
+
+impl BucketVecConfig for FibonacciConfig {
+    /// The first bucket has a capacity of 4.
+    const STARTING_CAPACITY: usize = 4;
+    /// The next bucket is always approx 62% larger.
+    const GROWTH_RATE: f64 = 1.618_033_988_749_895;
+}
+
+/// A configuration for bucket vectors whose bucket capacities double until
+/// they reach a capacity of `N`.
+///
+/// # Index Math
+///
+/// [`BucketVec`](crate::BucketVec) looks up elements with a binary search
+/// over its buckets.
+/// Other bucket vector types first locate the capped bucket in a number of
+/// steps logarithmic in `N` and then use a single division.
+#[derive(Debug)]
+pub enum CappedDoublingConfig<const N: usize> {}
+
+impl<const N: usize> BucketVecConfig for CappedDoublingConfig<N> {
+    /// The first bucket has a capacity of 4.
+    const STARTING_CAPACITY: usize = 4;
+    /// The next bucket always doubles in capacity.
+    const GROWTH_RATE: f64 = 2.0;
+    /// No bucket is larger than `N`.
+    const MAX_BUCKET_CAPACITY: Option<usize> = Some(N);
+}
+
+/// A configuration for bucket vectors that starts with a single element
+/// bucket and doubles.
+///
+/// Wastes the least memory for bucket vectors that often stay very small.
+///
+/// # Index Math
+///
+/// Looking up an element costs only a few bit shifts.
+#[derive(Debug)]
+pub enum TinyStartConfig {}
+
+impl BucketVecConfig for TinyStartConfig {
+    /// The first bucket has a capacity of 1.
+    const STARTING_CAPACITY: usize = 1;
+    /// The next bucket always doubles in capacity.
+    const GROWTH_RATE: f64 = 2.0;
+}
+
+/// A configuration for bucket vectors defined entirely through const generics.
+///
+/// The first bucket has a capacity of `START` and the growth rate is the
+/// rational number `NUM / DEN`.
+///
+/// # Note
+///
+/// `START` must be larger than or equal to `1` and `NUM` must be larger
+/// than or equal to `DEN` which must not be `0`.
+///
+/// # Example
+///
+/// ```
+/// # use bucket_vec::{BucketVec, ConstConfig};
+/// // First bucket has capacity 8, every next bucket is 50% larger.
+/// let mut vec = BucketVec::<i32, ConstConfig<8, 3, 2>>::new();
+/// vec.push(1);
+/// assert_eq!(vec.get(0), Some(&1));
+/// ```
+#[derive(Debug)]
+pub enum ConstConfig<const START: usize, const NUM: usize, const DEN: usize> {}
+
+impl<const START: usize, const NUM: usize, const DEN: usize> BucketVecConfig
+    for ConstConfig<START, NUM, DEN>
+{
+    const STARTING_CAPACITY: usize = START;
+    const GROWTH_RATE: f64 = NUM as f64 / DEN as f64;
+}
+
+/// Validates configurations at compile time.
+trait ValidConfig {
+    /// Fails to evaluate if the configuration is invalid.
+    const VALID: ();
+}
+
+impl<C> ValidConfig for C
 where
-    C: BucketVecConfig,
+    C: BucketVecConfig + ?Sized,
+{
+    const VALID: () = {
+        assert!(
+            C::STARTING_CAPACITY >= 1,
+            "`STARTING_CAPACITY` must be larger than or equal to 1"
+        );
+        assert!(
+            C::GROWTH_RATE >= 1.0,
+            "`GROWTH_RATE` must be larger than or equal to 1"
+        );
+        assert!(C::GROWTH_RATE.is_finite(), "`GROWTH_RATE` must be finite");
+        assert!(
+            !matches!(C::MAX_BUCKET_CAPACITY, Some(0)),
+            "`MAX_BUCKET_CAPACITY` must be larger than or equal to 1"
+        );
+        assert!(
+            !matches!(C::CAPACITY_ROUNDING, CapacityRounding::MultipleOf(0)),
+            "the quantum of `CAPACITY_ROUNDING` must be larger than or equal to 1"
+        );
+    };
+}
+
+/// The number of total capacities tabulated for fractional growth rates.
+///
+/// Suffices for all growth rates of at least about `1.19` to overflow
+/// `usize` within the table.
+const TABLE_LEN: usize = 256;
+
+/// The total capacities of a geometric configuration with a fractional
+/// growth rate or rounded capacities computed at compile time.
+struct CapacityTable {
+    /// The total capacity of all buckets before every bucket index.
+    ///
+    /// Total capacities that overflow `usize` are stored as `usize::MAX`.
+    totals: [usize; TABLE_LEN],
+    /// The index, offset and capacity of the first bucket from which on all
+    /// buckets are equally sized if any.
+    ///
+    /// Total capacities beyond this bucket are not tabulated.
+    plateau: Option<(usize, usize, usize)>,
+    /// Whether the table covers all buckets because the total capacity
+    /// overflows `usize` or bucket capacities plateau within the table.
+    ///
+    /// The table is only used if this holds.
+    complete: bool,
+}
+
+impl CapacityTable {
+    /// Tabulates the total capacities of the configuration.
+    ///
+    /// Evaluates the same formula as [`checked_total_capacity_of`].
+    /// The table is left incomplete unless the configuration is geometric with
+    /// a fractional growth rate or rounded capacities.
+    const fn new<C>() -> Self
+    where
+        C: BucketVecConfig + ?Sized,
+    {
+        let start_capacity = C::STARTING_CAPACITY;
+        let growth_rate = C::GROWTH_RATE;
+        let mut totals = [usize::MAX; TABLE_LEN];
+        if C::GEOMETRIC && !matches!(C::CAPACITY_ROUNDING, CapacityRounding::Exact) {
+            return Self::new_rounded::<C>();
+        }
+        if !C::GEOMETRIC || growth_rate as usize as f64 == growth_rate {
+            return Self {
+                totals,
+                plateau: None,
+                complete: false,
+            };
+        }
+        let mut index = 0;
+        while index < TABLE_LEN {
+            let total = start_capacity as f64 * (math::powi(growth_rate, index as u32) - 1.0)
+                / (growth_rate - 1.0);
+            if total >= usize::MAX as f64 {
+                return Self {
+                    totals,
+                    plateau: None,
+                    complete: true,
+                };
+            }
+            totals[index] = total as usize;
+            index += 1;
+        }
+        Self {
+            totals,
+            plateau: None,
+            complete: false,
+        }
+    }
+
+    /// Tabulates the total capacities of the configuration with rounded
+    /// bucket capacities.
+    ///
+    /// Bucket capacities are computed like the default
+    /// [`BucketVecConfig::bucket_capacity`] does.
+    const fn new_rounded<C>() -> Self
+    where
+        C: BucketVecConfig + ?Sized,
+    {
+        let start_capacity = C::STARTING_CAPACITY;
+        let growth_rate = C::GROWTH_RATE;
+        let unit = matches!(integral_growth_rate_of(growth_rate), Some(1));
+        let mut totals = [usize::MAX; TABLE_LEN];
+        let mut total = 0usize;
+        let mut index = 0;
+        while index < TABLE_LEN {
+            totals[index] = total;
+            // The exact capacity is `None` if the exact total capacity
+            // overflows `usize` in which case the cap is reached.
+            let exact = match (
+                checked_total_capacity_of(start_capacity, growth_rate, index),
+                checked_total_capacity_of(start_capacity, growth_rate, index + 1),
+            ) {
+                (Some(offset), Some(next_offset)) => Some(next_offset - offset),
+                _ => None,
+            };
+            let (capacity, capped) = match (exact, C::MAX_BUCKET_CAPACITY) {
+                (Some(capacity), Some(max_capacity)) if capacity >= max_capacity => {
+                    (max_capacity, true)
+                }
+                (None, Some(max_capacity)) => (max_capacity, true),
+                (Some(capacity), _) => (capacity, false),
+                (None, None) => break,
+            };
+            let capacity = match C::CAPACITY_ROUNDING.round(capacity) {
+                Some(capacity) => capacity,
+                None => break,
+            };
+            if capped || unit {
+                return Self {
+                    totals,
+                    plateau: Some((index, total, capacity)),
+                    complete: true,
+                };
+            }
+            total = match total.checked_add(capacity) {
+                Some(total) => total,
+                None => break,
+            };
+            index += 1;
+        }
+        // Either the total capacity overflowed or the table is exhausted.
+        Self {
+            totals,
+            plateau: None,
+            complete: index < TABLE_LEN,
+        }
+    }
+
+    /// Returns the total capacity of all buckets before the bucket index.
+    ///
+    /// Returns `None` if the total capacity overflows `usize`.
+    fn total(&self, index: usize) -> Option<usize> {
+        if let Some((plateau_index, offset, capacity)) = self.plateau {
+            if index > plateau_index {
+                return offset.checked_add((index - plateau_index).checked_mul(capacity)?);
+            }
+        }
+        // Total capacities beyond the table overflow `usize`.
+        self.totals
+            .get(index)
+            .copied()
+            .filter(|&total| total != usize::MAX)
+    }
+
+    /// Returns the largest bucket index whose total capacity is less than or
+    /// equal to `index`.
+    fn bucket_index(&self, index: usize) -> usize {
+        if let Some((plateau_index, offset, capacity)) = self.plateau {
+            if index >= offset {
+                return plateau_index + (index - offset) / capacity;
+            }
+        }
+        // Binary search with a fixed number of steps that compile to
+        // conditional moves instead of branches.
+        let mut base = 0;
+        let mut size = TABLE_LEN;
+        while size > 1 {
+            let half = size / 2;
+            let middle = base + half;
+            let total = self.totals[middle];
+            let fits = total <= index && total != usize::MAX;
+            base = if fits { middle } else { base };
+            size -= half;
+        }
+        base
+    }
+}
+
+/// Provides the compile time capacity table of a configuration.
+trait Tabulated {
+    /// The capacity table of the configuration.
+    const TABLE: &'static CapacityTable;
+}
+
+impl<C> Tabulated for C
+where
+    C: BucketVecConfig + ?Sized,
+{
+    const TABLE: &'static CapacityTable = &CapacityTable::new::<C>();
+}
+
+/// Returns the index, offset and capacity of the first bucket whose capacity
+/// is capped by `MAX_BUCKET_CAPACITY` if any.
+///
+/// All buckets following the returned bucket have the same capacity.
+fn capped_bucket<C>() -> Option<(usize, usize, usize)>
+where
+    C: BucketVecConfig + ?Sized,
 {
+    let max_capacity = <C as BucketVecConfig>::MAX_BUCKET_CAPACITY?;
     let start_capacity = <C as BucketVecConfig>::STARTING_CAPACITY;
     let growth_rate = <C as BucketVecConfig>::GROWTH_RATE;
-    if <f64 as FloatExt>::fract(growth_rate).abs() < core::f64::EPSILON {
-        let growth_rate = growth_rate as usize;
-        start_capacity * (growth_rate.pow(index as u32) - 1) / (growth_rate - 1)
+    if integral_growth_rate_of(growth_rate) == Some(1) && start_capacity < max_capacity {
+        // Equally sized buckets never reach the cap.
+        return None;
+    }
+    let mut index = 0;
+    let mut offset = 0;
+    loop {
+        match checked_total_capacity_of(start_capacity, growth_rate, index + 1) {
+            Some(next_offset) if next_offset - offset < max_capacity => {
+                index += 1;
+                offset = next_offset;
+            }
+            _ => return Some((index, offset, max_capacity)),
+        }
+    }
+}
+
+/// Returns `true` if the configuration rounds the capacities of its
+/// geometric schedule.
+fn is_rounded<C>() -> bool
+where
+    C: BucketVecConfig + ?Sized,
+{
+    <C as BucketVecConfig>::GEOMETRIC
+        && <C as BucketVecConfig>::CAPACITY_ROUNDING != CapacityRounding::Exact
+}
+
+/// Returns the growth rate of the configuration if it is an integer.
+///
+/// Returns `None` if the configuration does not follow a geometric schedule
+/// or rounds its bucket capacities.
+pub fn integral_growth_rate<C>() -> Option<usize>
+where
+    C: BucketVecConfig,
+{
+    if !<C as BucketVecConfig>::GEOMETRIC || is_rounded::<C>() {
+        return None;
+    }
+    integral_growth_rate_of(<C as BucketVecConfig>::GROWTH_RATE)
+}
+
+/// Returns the growth rate if it is an integer.
+pub const fn integral_growth_rate_of(growth_rate: f64) -> Option<usize> {
+    let integral = growth_rate as usize;
+    if integral as f64 == growth_rate {
+        Some(integral)
     } else {
-        <f64 as FloatExt>::floor(
-            start_capacity as f64 * (<f64 as FloatExt>::powi(growth_rate, index as i32) - 1.0)
-                / (growth_rate - 1.0),
-        ) as usize
+        None
+    }
+}
+
+/// Returns the total capacity of all buckets up to (and including) the
+/// bucket indexed by `index`.
+///
+/// Returns `None` if the total capacity overflows `usize`.
+pub fn checked_total_capacity<C>(index: usize) -> Option<usize>
+where
+    C: BucketVecConfig,
+{
+    let _: () = <C as ValidConfig>::VALID;
+    let table = <C as Tabulated>::TABLE;
+    if is_rounded::<C>() && table.complete {
+        return table.total(index);
+    }
+    if !<C as BucketVecConfig>::GEOMETRIC || is_rounded::<C>() {
+        return (0..index).try_fold(0usize, |total, bucket_index| {
+            total.checked_add(<C as BucketVecConfig>::bucket_capacity(bucket_index))
+        });
     }
+    if let Some((capped_index, offset, capacity)) = capped_bucket::<C>() {
+        if index > capped_index {
+            return offset.checked_add((index - capped_index).checked_mul(capacity)?);
+        }
+    }
+    if table.complete {
+        return table.total(index);
+    }
+    checked_total_capacity_of(
+        <C as BucketVecConfig>::STARTING_CAPACITY,
+        <C as BucketVecConfig>::GROWTH_RATE,
+        index,
+    )
+}
+
+/// Returns the total capacity of all buckets up to (and including) the
+/// bucket indexed by `index` for the given starting capacity and growth rate.
+///
+/// Returns `None` if the total capacity overflows `usize`.
+pub const fn checked_total_capacity_of(
+    start_capacity: usize,
+    growth_rate: f64,
+    index: usize,
+) -> Option<usize> {
+    if index > u32::MAX as usize {
+        return None;
+    }
+    match integral_growth_rate_of(growth_rate) {
+        Some(1) => start_capacity.checked_mul(index),
+        Some(growth_rate) => {
+            let growth_rate = growth_rate as u128;
+            let power = match growth_rate.checked_pow(index as u32) {
+                Some(power) => power,
+                None => return None,
+            };
+            match (start_capacity as u128).checked_mul((power - 1) / (growth_rate - 1)) {
+                Some(total) if total <= usize::MAX as u128 => Some(total as usize),
+                _ => None,
+            }
+        }
+        None => {
+            let total = start_capacity as f64 * (math::powi(growth_rate, index as u32) - 1.0)
+                / (growth_rate - 1.0);
+            // `usize::MAX as f64` rounds up to the next power of two.
+            // Truncation rounds down since the total is never negative.
+            if total < usize::MAX as f64 {
+                Some(total as usize)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Returns the total capacity of all buckets up to (and including) the
+/// bucket indexed by `index`.
+///
+/// # Panics
+///
+/// If the total capacity overflows `usize`.
+pub fn total_capacity<C>(index: usize) -> usize
+where
+    C: BucketVecConfig,
+{
+    checked_total_capacity::<C>(index).expect("capacity overflow")
 }
 
 /// Returns the capacity of the indexed bucket.
+///
+/// # Panics
+///
+/// If the total capacity up to the indexed bucket overflows `usize`.
 pub fn bucket_capacity<C>(index: usize) -> usize
 where
     C: BucketVecConfig,
 {
-    let start_capacity = <C as BucketVecConfig>::STARTING_CAPACITY;
-    let growth_rate = <C as BucketVecConfig>::GROWTH_RATE;
-    if (growth_rate - 1.0).abs() < core::f64::EPSILON {
+    let _: () = <C as ValidConfig>::VALID;
+    if <C as BucketVecConfig>::GEOMETRIC && integral_growth_rate::<C>().is_none() {
+        // Fractional growth rate:
+        // Derive the capacity from the same total capacities that
+        // `bucket_entry_indices` searches so that looked up entries always
+        // agree with the allocated buckets regardless of rounding.
+        return total_capacity::<C>(index + 1) - total_capacity::<C>(index);
+    }
+    <C as BucketVecConfig>::bucket_capacity(index)
+}
+
+/// Returns the capacity of the indexed bucket.
+///
+/// Returns `None` if the total capacity up to the indexed bucket overflows `usize`.
+///
+/// # Note
+///
+/// Custom schedules are queried as is and may still panic.
+///
+/// # Panics
+///
+/// If the configuration is geometric but overrides its schedule.
+pub fn checked_bucket_capacity<C>(index: usize) -> Option<usize>
+where
+    C: BucketVecConfig,
+{
+    let _: () = <C as ValidConfig>::VALID;
+    if !<C as BucketVecConfig>::GEOMETRIC {
+        return Some(<C as BucketVecConfig>::bucket_capacity(index));
+    }
+    let next_total = checked_total_capacity::<C>(index.checked_add(1)?)?;
+    let capacity = next_total - checked_total_capacity::<C>(index)?;
+    check_geometric::<C>(index);
+    Some(capacity)
+}
+
+/// Returns the capacity of the indexed bucket of the geometric schedule.
+///
+/// This is the default implementation of [`BucketVecConfig::bucket_capacity`].
+///
+/// # Panics
+///
+/// If the total capacity up to the indexed bucket overflows `usize`.
+fn geometric_bucket_capacity<C>(index: usize) -> usize
+where
+    C: BucketVecConfig + ?Sized,
+{
+    let capacity = match capped_bucket::<C>() {
+        Some((capped_index, _, capacity)) if index >= capped_index => capacity,
+        _ => bucket_capacity_of(C::STARTING_CAPACITY, C::GROWTH_RATE, index),
+    };
+    C::CAPACITY_ROUNDING
+        .round(capacity)
+        .expect("capacity overflow")
+}
+
+/// Asserts that geometric configurations do not override their schedule.
+///
+/// Only the first [`TABLE_LEN`] buckets are checked which is where custom
+/// schedules most likely deviate.
+///
+/// # Panics
+///
+/// If the configuration is geometric but `bucket_capacity` returns another
+/// capacity for the indexed bucket than the geometric schedule.
+fn check_geometric<C>(index: usize)
+where
+    C: BucketVecConfig,
+{
+    if <C as BucketVecConfig>::GEOMETRIC && index < TABLE_LEN {
+        assert_eq!(
+            <C as BucketVecConfig>::bucket_capacity(index),
+            geometric_bucket_capacity::<C>(index),
+            "`bucket_capacity` is overridden but `GEOMETRIC` is not set to `false`"
+        );
+    }
+}
+
+/// Returns the capacity of the indexed bucket that is about to be allocated.
+///
+/// Notifies the configuration about the allocation.
+///
+/// # Panics
+///
+/// - If the total capacity up to the indexed bucket overflows `usize`.
+/// - If the configuration is geometric but overrides its schedule.
+pub fn allocate_bucket<C>(index: usize) -> usize
+where
+    C: BucketVecConfig,
+{
+    let capacity = bucket_capacity::<C>(index);
+    check_geometric::<C>(index);
+    <C as BucketVecConfig>::on_bucket_allocated(index, capacity);
+    capacity
+}
+
+/// Returns the capacity of the indexed bucket for the given starting
+/// capacity and growth rate.
+///
+/// # Panics
+///
+/// If the total capacity up to the indexed bucket overflows `usize`.
+pub fn bucket_capacity_of(start_capacity: usize, growth_rate: f64, index: usize) -> usize {
+    if math::is_unit_growth_rate(growth_rate) {
         start_capacity
     } else {
-        let next_total_capacity = total_capacity::<C>(index + 1);
-        let total_capacity = total_capacity::<C>(index);
-        next_total_capacity - total_capacity
+        let total_capacity = |index| {
+            checked_total_capacity_of(start_capacity, growth_rate, index)
+                .expect("capacity overflow")
+        };
+        total_capacity(index + 1) - total_capacity(index)
     }
 }
 
+/// Returns the largest capacity of any bucket or `None` if bucket capacities
+/// grow without bounds.
+pub fn max_bucket_capacity<C>() -> Option<usize>
+where
+    C: BucketVecConfig,
+{
+    <C as BucketVecConfig>::max_bucket_capacity()
+}
+
 /// Returns the bucket index and its internal entry index for the given
 /// bucket vector index into an element.
 pub fn bucket_entry_indices<C>(index: usize) -> (usize, usize)
 where
     C: BucketVecConfig,
 {
+    let _: () = <C as ValidConfig>::VALID;
+    let table = <C as Tabulated>::TABLE;
+    if is_rounded::<C>() && table.complete {
+        // Rounded schedule:
+        // The bucket index is looked up in the capacity table computed at
+        // compile time since rounding defeats the closed formulas.
+        let x = table.bucket_index(index);
+        let offset = table.total(x).expect("encountered missing total capacity");
+        return (x, index - offset);
+    }
+    if !<C as BucketVecConfig>::GEOMETRIC || is_rounded::<C>() {
+        // Custom or slowly growing rounded schedule:
+        // Walk the buckets until the one containing the index is found.
+        let mut x = 0;
+        let mut y = index;
+        loop {
+            let capacity = <C as BucketVecConfig>::bucket_capacity(x);
+            if y < capacity {
+                return (x, y);
+            }
+            y -= capacity;
+            x += 1;
+        }
+    }
+    if let Some((capped_index, offset, capacity)) = capped_bucket::<C>() {
+        if index >= offset {
+            // Capped case:
+            // All buckets starting from the capped bucket are equally sized.
+            let x = capped_index + (index - offset) / capacity;
+            let y = (index - offset) % capacity;
+            return (x, y);
+        }
+    }
     // Calculate bucket index and entry index within the bucket.
     let start_capacity = <C as BucketVecConfig>::STARTING_CAPACITY;
     let growth_rate = <C as BucketVecConfig>::GROWTH_RATE;
-    if (growth_rate - 1.0).abs() < core::f64::EPSILON {
+    if math::is_unit_growth_rate(growth_rate) {
         // growth_rate == 1.0:
         // Simple case: All buckets are equally sized.
         let x = index / start_capacity;
         let y = index % start_capacity;
         (x, y)
+    } else if integral_growth_rate::<C>() == Some(2) && start_capacity.is_power_of_two() {
+        // growth_rate == 2.0 and start_capacity is a power of two:
+        // Bucket capacities are powers of two and indices can be computed
+        // using shifts only.
+        let shift = start_capacity.trailing_zeros();
+        let x = ((index >> shift) as u128 + 1).ilog2() as usize;
+        let y = index - (((start_capacity as u128) << x) - start_capacity as u128) as usize;
+        (x, y)
+    } else if let Some(growth_rate) = integral_growth_rate::<C>() {
+        // growth_rate is an integer > 1:
+        // The bucket index is the largest `x` with `total_capacity(x) <= index`
+        // which can be computed exactly using integer arithmetic only.
+        let growth_rate = growth_rate as u128;
+        let bound = (index / start_capacity) as u128 * (growth_rate - 1) + 1;
+        let x = bound.ilog(growth_rate) as usize;
+        let y = index - total_capacity::<C>(x);
+        (x, y)
+    } else if table.complete {
+        // growth rate is fractional and all total capacities are tabulated:
+        // The bucket index is looked up in the capacity table computed at
+        // compile time without any float math at run time.
+        let x = table.bucket_index(index);
+        (x, index - table.totals[x])
     } else {
-        // growth rate != 1.0:
+        // growth rate is fractional:
         // Non-trivial case: Buckets are unequally sized.
-        let f_inv = 1.0 + (index + 1) as f64 * (growth_rate - 1.0) / start_capacity as f64;
-        let off_x = if (growth_rate - 2.0).abs() < core::f64::EPSILON {
-            <f64 as FloatExt>::log2(f_inv)
-        } else {
-            <f64 as FloatExt>::log(f_inv, growth_rate)
-        };
-        let x = <f64 as FloatExt>::ceil(off_x) as usize - 1;
+        // The bucket index is the largest `x` with `total_capacity(x) <= index`
+        // which is searched for exponentially and then by bisection.
+        let fits = |x| checked_total_capacity::<C>(x).is_some_and(|total| total <= index);
+        let mut upper = 1;
+        while fits(upper) {
+            upper *= 2;
+        }
+        let mut lower = upper / 2;
+        while upper - lower > 1 {
+            let middle = lower + (upper - lower) / 2;
+            if fits(middle) {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        let x = lower;
         let y = index - total_capacity::<C>(x);
         (x, y)
     }
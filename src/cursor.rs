@@ -0,0 +1,115 @@
+//! Cursor that speeds up nearly sequential lookups into a bucket vector.
+
+use super::{index_out_of_bounds, BucketVec, BucketVecConfig, DefaultConfig};
+
+/// Resolves indices into a bucket vector remembering the last resolved position.
+///
+/// Looking up an index within or adjacent to the bucket of the previously
+/// resolved one merely offsets into that bucket instead of computing its
+/// bucket from scratch. Random jumps fall back to the regular index
+/// computation.
+#[derive(Debug)]
+pub struct GetCursor<'a, T, C = DefaultConfig> {
+    /// The bucket vector that is being accessed.
+    vec: &'a BucketVec<T, C>,
+    /// The last resolved index together with its bucket and entry indices.
+    last: Option<(usize, usize, usize)>,
+}
+
+impl<'a, T, C> Clone for GetCursor<'a, T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            vec: self.vec,
+            last: self.last,
+        }
+    }
+}
+
+impl<'a, T, C> GetCursor<'a, T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Creates a new cursor into the given bucket vector.
+    pub(crate) fn new(vec: &'a BucketVec<T, C>) -> Self {
+        Self { vec, last: None }
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&mut self, index: usize) -> Option<&'a T> {
+        let (x, y) = self.bucket_entry_indices(index)?;
+        self.last = Some((index, x, y));
+        self.vec.buckets[x].get(y)
+    }
+
+    /// Returns the bucket and entry indices of the element at the given index.
+    ///
+    /// Offsets from the last resolved position if the index is within or
+    /// adjacent to its bucket.
+    fn bucket_entry_indices(&self, index: usize) -> Option<(usize, usize)> {
+        if index >= self.vec.len() {
+            return None;
+        }
+        let buckets = &self.vec.buckets;
+        if let Some((last, x, y)) = self.last {
+            // The index of the first element of the last resolved bucket.
+            let start = last - y;
+            let len = buckets[x].len();
+            if (start..start + len).contains(&index) {
+                return Some((x, index - start));
+            }
+            if index == start + len {
+                // All buckets but the last one are filled to capacity.
+                return Some((x + 1, 0));
+            }
+            if index.wrapping_add(1) == start {
+                return Some((x - 1, buckets[x - 1].len() - 1));
+            }
+        }
+        self.vec.bucket_entry_indices(index)
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a cursor that speeds up nearly sequential lookups.
+    pub fn get_cursor(&self) -> GetCursor<'_, T, C> {
+        GetCursor::new(self)
+    }
+
+    /// Returns an iterator over shared references to the elements at the
+    /// given indices in order.
+    ///
+    /// # Note
+    ///
+    /// Consecutive indices within the same bucket are resolved without
+    /// computing their bucket from scratch.
+    ///
+    /// # Panics
+    ///
+    /// If any of the indices is out of bounds once it is reached.
+    pub fn select<'a, I>(&'a self, indices: I) -> impl Iterator<Item = &'a T> + 'a
+    where
+        I: IntoIterator<Item = usize>,
+        I::IntoIter: 'a,
+    {
+        let mut cursor = self.get_cursor();
+        indices.into_iter().map(move |index| {
+            cursor
+                .get(index)
+                .unwrap_or_else(|| index_out_of_bounds(index, self.len()))
+        })
+    }
+
+    /// Returns an iterator over shared references to the elements at the
+    /// given indices in order yielding `None` for out of bounds indices.
+    pub fn try_select<'a, I>(&'a self, indices: I) -> impl Iterator<Item = Option<&'a T>> + 'a
+    where
+        I: IntoIterator<Item = usize>,
+        I::IntoIter: 'a,
+    {
+        let mut cursor = self.get_cursor();
+        indices.into_iter().map(move |index| cursor.get(index))
+    }
+}
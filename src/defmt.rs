@@ -0,0 +1,20 @@
+//! Formatting of bucket vectors for `defmt` based logging.
+
+use super::BucketVec;
+
+impl<T, C> defmt::Format for BucketVec<T, C>
+where
+    T: defmt::Format,
+{
+    /// Formats the elements like a slice.
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "[");
+        for (n, element) in self.iter().enumerate() {
+            if n != 0 {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{}", element);
+        }
+        defmt::write!(f, "]");
+    }
+}
@@ -0,0 +1,311 @@
+//! Double-ended bucket queue that never moves its contained elements.
+
+use super::{config, BucketVecConfig, DefaultConfig, VecDeque};
+use core::marker::PhantomData;
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::vec_deque, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::vec_deque;
+
+/// A fixed capacity bucket of a bucket queue that can grow at both ends.
+#[derive(Debug, Clone)]
+struct DequeBucket<T> {
+    /// The entries of this bucket.
+    ///
+    /// Only the entries within `start..end` are occupied.
+    entries: Vec<Option<T>>,
+    /// The index of the first occupied entry.
+    start: usize,
+    /// The index one past the last occupied entry.
+    end: usize,
+}
+
+impl<T> DequeBucket<T> {
+    /// Creates a new empty bucket with a fixed capacity.
+    ///
+    /// The bucket is filled starting at `position` in both directions.
+    fn new(capacity: usize, position: usize) -> Self {
+        Self {
+            entries: (0..capacity).map(|_| None).collect(),
+            start: position,
+            end: position,
+        }
+    }
+
+    /// Returns the number of occupied entries.
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns the occupied entries.
+    fn occupied(&self) -> &[Option<T>] {
+        &self.entries[self.start..self.end]
+    }
+
+    /// Pushes the value in front of the occupied entries if there is space.
+    fn push_front(&mut self, new_value: T) -> Result<(), T> {
+        if self.start == 0 {
+            return Err(new_value);
+        }
+        self.start -= 1;
+        self.entries[self.start] = Some(new_value);
+        Ok(())
+    }
+
+    /// Pushes the value behind the occupied entries if there is space.
+    fn push_back(&mut self, new_value: T) -> Result<(), T> {
+        if self.end == self.entries.len() {
+            return Err(new_value);
+        }
+        self.entries[self.end] = Some(new_value);
+        self.end += 1;
+        Ok(())
+    }
+
+    /// Takes the first occupied entry if any.
+    fn pop_front(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        self.entries[self.start - 1].take()
+    }
+
+    /// Takes the last occupied entry if any.
+    fn pop_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        self.entries[self.end].take()
+    }
+}
+
+/// A double-ended queue that never moves its contained elements.
+///
+/// Grows by allocating new fixed-capacity buckets at either end and frees
+/// buckets as soon as they have been drained from either end.
+///
+/// # Note
+///
+/// Indexing walks the buckets and thus takes time proportional to the
+/// number of buckets.
+#[derive(Debug, Clone)]
+pub struct BucketVecDeque<T, C = DefaultConfig> {
+    /// The buckets in order from front to back.
+    buckets: VecDeque<DequeBucket<T>>,
+    /// The number of elements stored in the queue.
+    len: usize,
+    /// The configuration determining the capacity of new buckets.
+    config: PhantomData<fn() -> C>,
+}
+
+impl<T, C> Default for BucketVecDeque<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> BucketVecDeque<T, C> {
+    /// Creates a new empty bucket queue.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            buckets: VecDeque::new(),
+            len: 0,
+            config: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements stored in the bucket queue.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bucket queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over shared references to the elements from front to back.
+    pub fn iter(&self) -> DequeIter<'_, T> {
+        DequeIter {
+            entries: self
+                .buckets
+                .iter()
+                .flat_map(DequeBucket::occupied as fn(&DequeBucket<T>) -> &[Option<T>]),
+            len: self.len,
+        }
+    }
+
+    /// Returns a shared reference to the first element if any.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a shared reference to the last element if any.
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    /// Returns the bucket and entry indices of the element at the given index.
+    fn bucket_entry_indices(&self, mut index: usize) -> Option<(usize, usize)> {
+        for (bucket_index, bucket) in self.buckets.iter().enumerate() {
+            if index < bucket.len() {
+                return Some((bucket_index, bucket.start + index));
+            }
+            index -= bucket.len();
+        }
+        None
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (x, y) = self.bucket_entry_indices(index)?;
+        self.buckets[x].entries[y].as_ref()
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let (x, y) = self.bucket_entry_indices(index)?;
+        self.buckets[x].entries[y].as_mut()
+    }
+
+    /// Removes the first element and returns it if any.
+    ///
+    /// Frees the first bucket once it has been drained.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let popped = self.buckets.front_mut()?.pop_front();
+        if self.buckets.front().map(DequeBucket::len) == Some(0) {
+            self.buckets.pop_front();
+        }
+        if popped.is_some() {
+            self.len -= 1;
+        }
+        popped
+    }
+
+    /// Removes the last element and returns it if any.
+    ///
+    /// Frees the last bucket once it has been drained.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let popped = self.buckets.back_mut()?.pop_back();
+        if self.buckets.back().map(DequeBucket::len) == Some(0) {
+            self.buckets.pop_back();
+        }
+        if popped.is_some() {
+            self.len -= 1;
+        }
+        popped
+    }
+}
+
+impl<T, C> BucketVecDeque<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns the capacity for the next allocated bucket.
+    ///
+    /// Must only be called right before allocating the bucket.
+    fn next_capacity(&self) -> usize {
+        config::allocate_bucket::<C>(self.buckets.len())
+    }
+
+    /// Pushes a new element to the front of the bucket queue.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket queue.
+    pub fn push_front(&mut self, new_value: T) {
+        let new_value = match self.buckets.front_mut() {
+            Some(bucket) => match bucket.push_front(new_value) {
+                Ok(()) => {
+                    self.len += 1;
+                    return;
+                }
+                Err(new_value) => new_value,
+            },
+            None => new_value,
+        };
+        let capacity = self.next_capacity();
+        let mut bucket = DequeBucket::new(capacity, capacity);
+        if bucket.push_front(new_value).is_err() {
+            panic!("encountered new bucket without capacity")
+        }
+        self.buckets.push_front(bucket);
+        self.len += 1;
+    }
+
+    /// Pushes a new element to the back of the bucket queue.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket queue.
+    pub fn push_back(&mut self, new_value: T) {
+        let new_value = match self.buckets.back_mut() {
+            Some(bucket) => match bucket.push_back(new_value) {
+                Ok(()) => {
+                    self.len += 1;
+                    return;
+                }
+                Err(new_value) => new_value,
+            },
+            None => new_value,
+        };
+        let mut bucket = DequeBucket::new(self.next_capacity(), 0);
+        if bucket.push_back(new_value).is_err() {
+            panic!("encountered new bucket without capacity")
+        }
+        self.buckets.push_back(bucket);
+        self.len += 1;
+    }
+}
+
+/// The occupied entries of all buckets of a bucket queue.
+type DequeEntries<'a, T> = core::iter::FlatMap<
+    vec_deque::Iter<'a, DequeBucket<T>>,
+    &'a [Option<T>],
+    fn(&DequeBucket<T>) -> &[Option<T>],
+>;
+
+/// An iterator yielding shared references to the elements of a bucket queue.
+#[derive(Debug, Clone)]
+pub struct DequeIter<'a, T> {
+    /// The occupied entries of all buckets.
+    entries: DequeEntries<'a, T>,
+    /// Number of elements that are to be yielded by the iterator.
+    len: usize,
+}
+
+impl<'a, T> Iterator for DequeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.entries.next()?;
+        self.len -= 1;
+        next.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for DequeIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let next = self.entries.next_back()?;
+        self.len -= 1;
+        next.as_ref()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for DequeIter<'a, T> {}
+
+impl<'a, T> core::iter::FusedIterator for DequeIter<'a, T> {}
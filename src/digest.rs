@@ -0,0 +1,127 @@
+//! Bucket vector that maintains a running digest of its elements.
+
+use super::{BucketVec, BucketVecConfig, DefaultConfig, Iter};
+use core::hash::{Hash, Hasher};
+
+/// A bucket vector that feeds every pushed element into a hasher.
+///
+/// The digest of all elements is therefore available at any time without
+/// rehashing them. Elements can only be pushed and never mutated so that
+/// the digest stays consistent with them.
+#[derive(Debug, Clone)]
+pub struct DigestBucketVec<T, H, C = DefaultConfig> {
+    /// The digested elements.
+    values: BucketVec<T, C>,
+    /// The hasher fed with all elements in order.
+    hasher: H,
+}
+
+impl<T, H, C> Default for DigestBucketVec<T, H, C>
+where
+    H: Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(H::default())
+    }
+}
+
+impl<T, H, C> DigestBucketVec<T, H, C> {
+    /// Creates a new empty digest bucket vector feeding the given hasher.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn with_hasher(hasher: H) -> Self {
+        Self {
+            values: BucketVec::new(),
+            hasher,
+        }
+    }
+
+    /// Returns the number of elements stored in the digest bucket vector.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the digest bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over shared references to the elements.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.values.iter()
+    }
+
+    /// Returns the underlying bucket vector.
+    pub fn as_bucket_vec(&self) -> &BucketVec<T, C> {
+        &self.values
+    }
+
+    /// Returns the hasher that has been fed with all elements in order.
+    ///
+    /// This allows to extract digests that are wider than `u64`.
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Returns the underlying bucket vector and the hasher.
+    pub fn into_parts(self) -> (BucketVec<T, C>, H) {
+        (self.values, self.hasher)
+    }
+}
+
+impl<T, H, C> DigestBucketVec<T, H, C>
+where
+    H: Hasher,
+{
+    /// Returns the digest of all elements in order.
+    pub fn digest(&self) -> u64 {
+        self.hasher.finish()
+    }
+}
+
+impl<T, H, C> DigestBucketVec<T, H, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+}
+
+impl<T, H, C> DigestBucketVec<T, H, C>
+where
+    T: Hash,
+    H: Hasher,
+    C: BucketVecConfig,
+{
+    /// Pushes the value, feeds it into the hasher and returns its index.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the digest bucket vector.
+    pub fn push(&mut self, new_value: T) -> usize {
+        let index = self.values.len();
+        self.values.push_get(new_value).hash(&mut self.hasher);
+        index
+    }
+}
+
+impl<T, H, C> Extend<T> for DigestBucketVec<T, H, C>
+where
+    T: Hash,
+    H: Hasher,
+    C: BucketVecConfig,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
@@ -0,0 +1,487 @@
+//! Bucket vector whose configuration is chosen at runtime.
+
+use super::{
+    config, index_out_of_bounds,
+    storage::{BucketSchedule, BucketStorage, Parts, PartsMut},
+    Access, Bucket, BucketVecConfig, CapacityRounding, ChunkPairs, DefaultConfig, IntoIter, Iter,
+    IterMut, Position,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::VecDeque, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+
+/// A bucket vector whose starting capacity and growth rate are stored as
+/// fields instead of being determined by a configuration type.
+///
+/// This allows to choose the growth parameters at runtime, e.g. from a
+/// configuration file, at the cost of slightly slower element lookup.
+///
+/// # Supported API
+///
+/// Supports the core API of [`BucketVec`](crate::BucketVec): element access
+/// and indexing, pushing, popping, truncating, reserving, iteration,
+/// `extend_from_slice` and the `Clone`, `PartialEq`, `Eq`, `PartialOrd`,
+/// `Ord`, `Hash`, `Debug`, `FromIterator` and `Extend` implementations.
+/// Operations that rely on the bucket layout being known at compile time,
+/// such as contiguous slice pushes, cursors, pointer tables or the
+/// serialization integrations, are only provided by `BucketVec`.
+///
+/// # Example
+///
+/// ```
+/// # use bucket_vec::DynBucketVec;
+/// let mut vec = DynBucketVec::new(8, 1.5);
+/// vec.push(1);
+/// vec.push(2);
+/// assert_eq!(vec.get(1), Some(&2));
+/// ```
+pub struct DynBucketVec<T> {
+    /// The capacity of the first bucket.
+    starting_capacity: usize,
+    /// The rate with which the buckets are extended in their capacity.
+    growth_rate: f64,
+    /// The number of elements stored in the bucket vector.
+    len: usize,
+    /// The entry vector.
+    buckets: Vec<Bucket<T>>,
+    /// The index of the first element of every bucket in the entry vector.
+    offsets: Vec<usize>,
+    /// Empty buckets allocated ahead of time in the order they are pushed.
+    reserved: VecDeque<Bucket<T>>,
+}
+
+impl<T> Clone for DynBucketVec<T>
+where
+    T: Clone,
+{
+    /// Clones the bucket vector preserving the capacities of its buckets.
+    fn clone(&self) -> Self {
+        Self {
+            starting_capacity: self.starting_capacity,
+            growth_rate: self.growth_rate,
+            len: self.len,
+            buckets: self.buckets.clone(),
+            offsets: self.offsets.clone(),
+            reserved: VecDeque::new(),
+        }
+    }
+}
+
+impl<T, U> PartialEq<DynBucketVec<U>> for DynBucketVec<T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &DynBucketVec<U>) -> bool {
+        self.len() == other.len()
+            && ChunkPairs::new(
+                self.buckets.iter().map(Bucket::as_slice),
+                other.buckets.iter().map(Bucket::as_slice),
+            )
+            .all(|(lhs, rhs)| lhs == rhs)
+    }
+}
+
+impl<T> Eq for DynBucketVec<T> where T: Eq {}
+
+impl<T> core::cmp::PartialOrd for DynBucketVec<T>
+where
+    T: core::cmp::PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        let chunks = ChunkPairs::new(
+            self.buckets.iter().map(Bucket::as_slice),
+            other.buckets.iter().map(Bucket::as_slice),
+        );
+        for (lhs, rhs) in chunks {
+            match lhs.partial_cmp(rhs) {
+                Some(core::cmp::Ordering::Equal) => (),
+                non_eq => return non_eq,
+            }
+        }
+        self.len().partial_cmp(&other.len())
+    }
+}
+
+impl<T> core::cmp::Ord for DynBucketVec<T>
+where
+    T: core::cmp::Ord,
+{
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let chunks = ChunkPairs::new(
+            self.buckets.iter().map(Bucket::as_slice),
+            other.buckets.iter().map(Bucket::as_slice),
+        );
+        for (lhs, rhs) in chunks {
+            match lhs.cmp(rhs) {
+                core::cmp::Ordering::Equal => (),
+                non_eq => return non_eq,
+            }
+        }
+        self.len().cmp(&other.len())
+    }
+}
+
+impl<T> core::hash::Hash for DynBucketVec<T>
+where
+    T: core::hash::Hash,
+{
+    /// Hashes the bucket vector the same way as an equivalent `BucketVec`.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for bucket in &self.buckets {
+            T::hash_slice(bucket.as_slice(), state);
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for DynBucketVec<T>
+where
+    T: core::fmt::Debug,
+{
+    /// Formats the elements like a slice.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Default for DynBucketVec<T> {
+    fn default() -> Self {
+        Self::from_config::<DefaultConfig>()
+    }
+}
+
+impl<T> DynBucketVec<T> {
+    /// Creates a new empty bucket vector with the given starting capacity
+    /// and growth rate.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    ///
+    /// # Panics
+    ///
+    /// If `starting_capacity` is `0` or `growth_rate` is not a finite number
+    /// of at least `1`.
+    pub fn new(starting_capacity: usize, growth_rate: f64) -> Self {
+        assert!(
+            starting_capacity >= 1,
+            "starting capacity must be at least 1"
+        );
+        assert!(
+            growth_rate.is_finite() && growth_rate >= 1.0,
+            "growth rate must be finite and at least 1"
+        );
+        Self {
+            starting_capacity,
+            growth_rate,
+            len: 0,
+            buckets: Vec::new(),
+            offsets: Vec::new(),
+            reserved: VecDeque::new(),
+        }
+    }
+
+    /// Creates a new empty bucket vector with the starting capacity and
+    /// growth rate of the given configuration.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    ///
+    /// # Panics
+    ///
+    /// If the configuration does not follow a geometric schedule, caps or
+    /// rounds its bucket capacities.
+    pub fn from_config<C>() -> Self
+    where
+        C: BucketVecConfig,
+    {
+        assert!(
+            <C as BucketVecConfig>::GEOMETRIC
+                && <C as BucketVecConfig>::MAX_BUCKET_CAPACITY.is_none()
+                && <C as BucketVecConfig>::CAPACITY_ROUNDING == CapacityRounding::Exact,
+            "custom, capped or rounded bucket capacity schedules are not supported"
+        );
+        Self::new(
+            <C as BucketVecConfig>::STARTING_CAPACITY,
+            <C as BucketVecConfig>::GROWTH_RATE,
+        )
+    }
+
+    /// Returns the capacity of the first bucket.
+    pub fn starting_capacity(&self) -> usize {
+        self.starting_capacity
+    }
+
+    /// Returns the rate with which the buckets are extended in their capacity.
+    pub fn growth_rate(&self) -> f64 {
+        self.growth_rate
+    }
+
+    /// Returns the number of elements stored in the bucket vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements the bucket vector can hold without
+    /// allocating another bucket.
+    ///
+    /// # Note
+    ///
+    /// Always returns `usize::MAX` for zero-sized types since they never
+    /// require any allocations.
+    pub fn capacity(&self) -> usize {
+        BucketStorage::capacity(self)
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// # Note
+    ///
+    /// The reserved buckets are allocated eagerly but only become part of
+    /// the bucket vector once pushed elements require them.
+    pub fn reserve(&mut self, additional: usize) {
+        BucketSchedule::reserve(self, additional)
+    }
+
+    /// Returns an iterator that yields shared references to the elements of the bucket vector.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::from_buckets(&self.buckets, &self.offsets, self.len)
+    }
+
+    /// Returns an iterator that yields exclusive reference to the elements of the bucket vector.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::from_buckets(&mut self.buckets, &self.offsets, self.len)
+    }
+
+    /// Returns the bucket index and its internal entry index for the given
+    /// bucket vector index into an element.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    fn bucket_entry_indices(&self, index: usize) -> Option<(usize, usize)> {
+        if index >= self.len() {
+            return None;
+        }
+        let x = self.offsets.partition_point(|&offset| offset <= index) - 1;
+        Some((x, index - self.offsets[x]))
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (x, y) = self.bucket_entry_indices(index)?;
+        self.buckets[x].get(y)
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let (x, y) = self.bucket_entry_indices(index)?;
+        self.buckets[x].get_mut(y)
+    }
+
+    /// Returns a shared reference to the first element of the bucket vector.
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns an exclusive reference to the first element of the bucket vector.
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns a shared reference to the last element of the bucket vector.
+    pub fn last(&self) -> Option<&T> {
+        self.buckets.last()?.as_slice().last()
+    }
+
+    /// Returns an exclusive reference to the last element of the bucket vector.
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.buckets.last_mut()?.as_mut_slice().last_mut()
+    }
+
+    /// Pushes a new element onto the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push(&mut self, new_value: T) {
+        self.ensure_spare_bucket();
+        self.buckets
+            .last_mut()
+            .expect("encountered missing bucket with spare capacity")
+            .push(new_value);
+        self.len += 1;
+    }
+
+    /// Pushes a new element onto the bucket vector and returns access to it.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push_get(&mut self, new_value: T) -> Access<'_, T> {
+        let index = self.len();
+        self.push(new_value);
+        let bucket_index = self.buckets.len() - 1;
+        let entry_index = index - self.offsets[bucket_index];
+        Access::new(
+            Position::new(index, bucket_index, entry_index),
+            &mut self.buckets[bucket_index][entry_index],
+        )
+    }
+
+    /// Removes the last element from the bucket vector and returns it if any.
+    ///
+    /// # Note
+    ///
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    pub fn pop(&mut self) -> Option<T> {
+        BucketStorage::pop(self)
+    }
+
+    /// Shortens the bucket vector to the given length dropping all elements beyond it.
+    ///
+    /// Does nothing if `len` is not less than the current length.
+    ///
+    /// # Note
+    ///
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn truncate(&mut self, len: usize) {
+        BucketStorage::truncate(self, len)
+    }
+
+    /// Removes all elements from the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// All buckets are kept as reserved buckets for later pushes.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Clones and pushes all of the given values onto the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// The values are copied in bulk into the spare capacity of the buckets.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn extend_from_slice(&mut self, values: &[T])
+    where
+        T: Clone,
+    {
+        let mut cloned = 0;
+        while cloned < values.len() {
+            cloned += self.fill_bucket_from_slice(&values[cloned..]);
+        }
+    }
+}
+
+impl<T> BucketStorage<T> for DynBucketVec<T> {
+    fn parts(&self) -> Parts<'_, T> {
+        Parts {
+            len: self.len,
+            buckets: &self.buckets,
+            reserved: &self.reserved,
+        }
+    }
+
+    fn parts_mut(&mut self) -> PartsMut<'_, T> {
+        PartsMut {
+            len: &mut self.len,
+            buckets: &mut self.buckets,
+            offsets: &mut self.offsets,
+            reserved: &mut self.reserved,
+        }
+    }
+
+    fn sync(&mut self) {}
+}
+
+impl<T> BucketSchedule<T> for DynBucketVec<T> {
+    fn allocate_bucket(&self, index: usize) -> usize {
+        config::bucket_capacity_of(self.starting_capacity, self.growth_rate, index)
+    }
+}
+
+impl<T> core::ops::Index<usize> for DynBucketVec<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.bucket_entry_indices(index) {
+            Some((x, y)) => &self.buckets[x][y],
+            None => index_out_of_bounds(index, self.len()),
+        }
+    }
+}
+
+impl<T> core::ops::IndexMut<usize> for DynBucketVec<T> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match self.bucket_entry_indices(index) {
+            Some((x, y)) => &mut self.buckets[x][y],
+            None => index_out_of_bounds(index, self.len()),
+        }
+    }
+}
+
+impl<T> IntoIterator for DynBucketVec<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::from_buckets(self.buckets, self.offsets, self.len)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DynBucketVec<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DynBucketVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> core::iter::FromIterator<T> for DynBucketVec<T> {
+    /// Collects the elements into a bucket vector using the growth parameters of `DefaultConfig`.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::default();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<T> core::iter::Extend<T> for DynBucketVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.extend_from_iter(iter.into_iter())
+    }
+}
+
+impl<'a, T> core::iter::Extend<&'a T> for DynBucketVec<T>
+where
+    T: Copy + 'a,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        <Self as core::iter::Extend<T>>::extend(self, iter.into_iter().copied())
+    }
+}
@@ -0,0 +1,223 @@
+//! Bucket vector whose operations report failures instead of panicking.
+
+use super::{config, Bucket, BucketVec, BucketVecConfig, DefaultConfig, IndexError, Iter, IterMut};
+use core::fmt;
+
+/// Errors returned by the allocating operations of [`FallibleBucketVec`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ReserveError {
+    /// The required capacity exceeds `usize::MAX`.
+    CapacityOverflow,
+    /// The allocator failed to allocate a bucket or the bookkeeping of the
+    /// buckets.
+    AllocFailed,
+}
+
+impl fmt::Display for ReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CapacityOverflow => write!(f, "capacity overflow"),
+            Self::AllocFailed => write!(f, "memory allocation failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReserveError {}
+
+/// A bucket vector whose operations never panic.
+///
+/// Every operation that allocates or indexes returns a `Result` instead.
+/// This is meant for builds that must not panic, e.g. in safety-critical
+/// environments.
+///
+/// # Note
+///
+/// Custom bucket capacity schedules as well as the `debug-stability-checks`
+/// crate feature may still panic.
+#[derive(Debug, Clone)]
+pub struct FallibleBucketVec<T, C = DefaultConfig> {
+    /// The underlying bucket vector.
+    values: BucketVec<T, C>,
+}
+
+impl<T, C> Default for FallibleBucketVec<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> From<BucketVec<T, C>> for FallibleBucketVec<T, C> {
+    fn from(values: BucketVec<T, C>) -> Self {
+        Self { values }
+    }
+}
+
+impl<T, C> FallibleBucketVec<T, C> {
+    /// Creates a new empty fallible bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self::from(BucketVec::new())
+    }
+
+    /// Returns the number of elements stored in the fallible bucket vector.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the fallible bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over shared references to the elements.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.values.iter()
+    }
+
+    /// Returns an iterator over exclusive references to the elements.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.values.iter_mut()
+    }
+
+    /// Returns the underlying bucket vector.
+    pub fn as_bucket_vec(&self) -> &BucketVec<T, C> {
+        &self.values
+    }
+
+    /// Returns the underlying bucket vector.
+    pub fn into_bucket_vec(self) -> BucketVec<T, C> {
+        self.values
+    }
+
+    /// Returns the number of elements the buckets of the fallible bucket
+    /// vector can hold without allocating.
+    ///
+    /// # Note
+    ///
+    /// Saturates at `usize::MAX`.
+    pub fn capacity(&self) -> usize {
+        self.values
+            .buckets
+            .iter()
+            .chain(self.values.reserved.iter())
+            .fold(0, |capacity: usize, bucket| {
+                capacity.saturating_add(bucket.capacity())
+            })
+    }
+}
+
+impl<T, C> FallibleBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the element at the given index.
+    ///
+    /// # Errors
+    ///
+    /// If the index is out of bounds.
+    pub fn try_get(&self, index: usize) -> Result<&T, IndexError> {
+        self.values.try_get(index)
+    }
+
+    /// Returns an exclusive reference to the element at the given index.
+    ///
+    /// # Errors
+    ///
+    /// If the index is out of bounds.
+    pub fn try_get_mut(&mut self, index: usize) -> Result<&mut T, IndexError> {
+        self.values.try_get_mut(index)
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// # Note
+    ///
+    /// The reserved buckets only become part of the bucket vector once pushed
+    /// elements require them.
+    ///
+    /// # Errors
+    ///
+    /// If the required capacity overflows or allocating a bucket fails.
+    /// Buckets allocated before the failure remain reserved.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        let required = self
+            .len()
+            .checked_add(additional)
+            .ok_or(ReserveError::CapacityOverflow)?;
+        let mut capacity = self.capacity();
+        let values = &mut self.values;
+        while capacity < required {
+            let index = values.buckets.len() + values.reserved.len();
+            let new_capacity = config::checked_bucket_capacity::<C>(index)
+                .ok_or(ReserveError::CapacityOverflow)?;
+            values
+                .reserved
+                .try_reserve(1)
+                .map_err(|_| ReserveError::AllocFailed)?;
+            let bucket = Bucket::try_new(new_capacity).ok_or(ReserveError::AllocFailed)?;
+            <C as BucketVecConfig>::on_bucket_allocated(index, new_capacity);
+            values.reserved.push_back(bucket);
+            capacity = capacity.saturating_add(new_capacity);
+        }
+        // Attaching the reserved buckets later on must not allocate.
+        let len_buckets = values.reserved.len();
+        values
+            .buckets
+            .try_reserve(len_buckets)
+            .map_err(|_| ReserveError::AllocFailed)?;
+        values
+            .offsets
+            .try_reserve(len_buckets)
+            .map_err(|_| ReserveError::AllocFailed)?;
+        values.check_stability();
+        Ok(())
+    }
+
+    /// Pushes the value and returns its index.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the fallible bucket vector.
+    ///
+    /// # Errors
+    ///
+    /// If a new bucket is required but cannot be allocated.
+    /// The value is returned back together with the error in this case.
+    pub fn try_push(&mut self, new_value: T) -> Result<usize, (T, ReserveError)> {
+        if self.values.spare == 0 {
+            if let Err(error) = self.try_reserve(1) {
+                return Err((new_value, error));
+            }
+        }
+        let index = self.len();
+        self.values.push(new_value);
+        Ok(index)
+    }
+
+    /// Pushes all elements of the iterator.
+    ///
+    /// # Note
+    ///
+    /// Buckets are allocated one by one as the elements arrive instead of
+    /// trusting the size hint of the iterator.
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first element for which a new bucket is required but
+    /// cannot be allocated and returns it back together with the error.
+    /// Elements pushed before remain in the fallible bucket vector.
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), (T, ReserveError)>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.try_push(value)?;
+        }
+        Ok(())
+    }
+}
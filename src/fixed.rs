@@ -0,0 +1,248 @@
+//! Bucket vector with inline storage that never allocates.
+
+use core::fmt;
+
+/// A bucket vector with a fixed capacity of `N` elements that stores its
+/// elements inline.
+///
+/// Never allocates heap memory and never moves its contained elements while
+/// pushing so that it can be used on targets without an allocator.
+/// It is the only bucket vector that is available without the `alloc` crate feature.
+///
+/// # Note
+///
+/// All elements are stored inline in a single array so every element moves
+/// whenever the static bucket vector itself moves. Keep it in place for
+/// element addresses to stay valid, e.g. behind a pinned reference or in a
+/// `static` guarded by a lock since `new` is a `const fn`:
+///
+/// ```
+/// # use bucket_vec::StaticBucketVec;
+/// # use std::sync::Mutex;
+/// static VEC: Mutex<StaticBucketVec<u32, 8>> = Mutex::new(StaticBucketVec::new());
+/// assert_eq!(VEC.lock().unwrap().push(42), Ok(()));
+/// assert_eq!(VEC.lock().unwrap().get(0), Some(&42));
+/// ```
+///
+/// The entries that are not yet occupied are vacant so that the element type
+/// is not required to have a default value.
+#[derive(Clone)]
+pub struct StaticBucketVec<T, const N: usize> {
+    /// The number of elements stored in the bucket vector.
+    len: usize,
+    /// The inline storage of all elements.
+    ///
+    /// Only the first `len` entries are occupied.
+    entries: [Option<T>; N],
+}
+
+impl<T, const N: usize> fmt::Debug for StaticBucketVec<T, N>
+where
+    T: fmt::Debug,
+{
+    /// Formats the elements like a slice.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const N: usize> Default for StaticBucketVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> StaticBucketVec<T, N> {
+    /// A vacant entry used to initialize the inline storage in a `const` context.
+    const VACANT: Option<T> = None;
+
+    /// Creates a new empty static bucket vector.
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            entries: [Self::VACANT; N],
+        }
+    }
+
+    /// Returns the number of elements stored in the bucket vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the bucket vector can hold.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the bucket vector is filled to capacity.
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.entries[..self.len].get(index).and_then(Option::as_ref)
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.entries[..self.len]
+            .get_mut(index)
+            .and_then(Option::as_mut)
+    }
+
+    /// Pushes a new element onto the bucket vector.
+    ///
+    /// Returns the element back if the bucket vector is filled to capacity.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements within the bucket vector.
+    pub fn push(&mut self, new_value: T) -> Result<(), T> {
+        match self.entries.get_mut(self.len) {
+            Some(entry) => {
+                *entry = Some(new_value);
+                self.len += 1;
+                Ok(())
+            }
+            None => Err(new_value),
+        }
+    }
+
+    /// Removes the last element from the bucket vector and returns it.
+    ///
+    /// Returns `None` if the bucket vector is empty.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements within the bucket vector.
+    pub fn pop(&mut self) -> Option<T> {
+        let index = self.len.checked_sub(1)?;
+        self.len = index;
+        Some(
+            self.entries[index]
+                .take()
+                .expect("encountered vacant entry within the length"),
+        )
+    }
+
+    /// Removes all elements from the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// The capacity of the bucket vector is unaffected since all elements are
+    /// stored inline.
+    pub fn clear(&mut self) {
+        for entry in &mut self.entries[..self.len] {
+            *entry = None;
+        }
+        self.len = 0;
+    }
+
+    /// Returns an iterator that yields shared references to the elements of the bucket vector.
+    pub fn iter(&self) -> StaticIter<'_, T> {
+        StaticIter {
+            entries: self.entries[..self.len].iter(),
+        }
+    }
+
+    /// Returns an iterator that yields exclusive references to the elements of the bucket vector.
+    pub fn iter_mut(&mut self) -> StaticIterMut<'_, T> {
+        StaticIterMut {
+            entries: self.entries[..self.len].iter_mut(),
+        }
+    }
+}
+
+/// An iterator yielding shared references to the elements of a static bucket vector.
+#[derive(Debug, Clone)]
+pub struct StaticIter<'a, T> {
+    /// The occupied entries.
+    entries: core::slice::Iter<'a, Option<T>>,
+}
+
+impl<'a, T> Iterator for StaticIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(occupied)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.entries.nth(n).map(occupied)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for StaticIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back().map(occupied)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.entries.nth_back(n).map(occupied)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for StaticIter<'a, T> {}
+
+impl<'a, T> core::iter::FusedIterator for StaticIter<'a, T> {}
+
+/// An iterator yielding exclusive references to the elements of a static bucket vector.
+#[derive(Debug)]
+pub struct StaticIterMut<'a, T> {
+    /// The occupied entries.
+    entries: core::slice::IterMut<'a, Option<T>>,
+}
+
+impl<'a, T> Iterator for StaticIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(occupied_mut)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.entries.nth(n).map(occupied_mut)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for StaticIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back().map(occupied_mut)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.entries.nth_back(n).map(occupied_mut)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for StaticIterMut<'a, T> {}
+
+impl<'a, T> core::iter::FusedIterator for StaticIterMut<'a, T> {}
+
+/// Returns the element of an entry within the length of a static bucket vector.
+fn occupied<T>(entry: &Option<T>) -> &T {
+    entry
+        .as_ref()
+        .expect("encountered vacant entry within the length")
+}
+
+/// Returns the exclusive element of an entry within the length of a static bucket vector.
+fn occupied_mut<T>(entry: &mut Option<T>) -> &mut T {
+    entry
+        .as_mut()
+        .expect("encountered vacant entry within the length")
+}
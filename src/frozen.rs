@@ -0,0 +1,240 @@
+//! Append-only bucket vector that can be pushed to through shared references.
+
+use super::{config, BucketVecConfig, DefaultConfig};
+use core::{
+    cell::{Cell, OnceCell},
+    marker::PhantomData,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+/// The lazily initialized entries of a single bucket.
+type Entries<T> = Box<[OnceCell<T>]>;
+
+/// A node of the linked list of bucket directories.
+///
+/// The `k`th directory holds `2^k` buckets so that locating a bucket takes
+/// time logarithmic in the number of buckets.
+#[derive(Debug)]
+struct Directory<T> {
+    /// The lazily allocated buckets of this directory.
+    buckets: Box<[OnceCell<Entries<T>>]>,
+    /// The next directory holding twice as many buckets.
+    next: OnceCell<Box<Directory<T>>>,
+}
+
+impl<T> Directory<T> {
+    /// Creates a new directory with the given number of buckets.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate the buckets themselves.
+    fn new(len_buckets: usize) -> Self {
+        Self {
+            buckets: (0..len_buckets).map(|_| OnceCell::new()).collect(),
+            next: OnceCell::new(),
+        }
+    }
+}
+
+/// Returns the directory index and the bucket index within it for the bucket
+/// of the given index.
+pub(crate) fn directory_indices(bucket_index: usize) -> (usize, usize) {
+    let n = bucket_index + 1;
+    let directory_index = (usize::BITS - 1 - n.leading_zeros()) as usize;
+    (directory_index, n - (1 << directory_index))
+}
+
+/// An append-only bucket vector that can be pushed to through shared references.
+///
+/// Since pushing never moves elements, references to elements returned by
+/// [`FrozenBucketVec::push`] and [`FrozenBucketVec::get`] remain valid while
+/// more elements are pushed.
+///
+/// # Note
+///
+/// Locating an element takes time logarithmic in the number of buckets.
+#[derive(Debug)]
+pub struct FrozenBucketVec<T, C = DefaultConfig> {
+    /// The number of elements stored in the bucket vector.
+    len: Cell<usize>,
+    /// The first bucket directory.
+    directories: OnceCell<Box<Directory<T>>>,
+    /// The config phantom data.
+    config: PhantomData<fn() -> C>,
+}
+
+impl<T, C> Default for FrozenBucketVec<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> FrozenBucketVec<T, C> {
+    /// Creates a new empty frozen bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            len: Cell::new(0),
+            directories: OnceCell::new(),
+            config: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements stored in the bucket vector.
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Returns `true` if the bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the elements pushed so far.
+    pub fn iter(&self) -> FrozenIter<'_, T, C> {
+        FrozenIter {
+            vec: self,
+            index: 0,
+            len: self.len(),
+        }
+    }
+}
+
+impl<T, C> FrozenBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns the entries of the bucket with the given index if allocated.
+    fn bucket(&self, bucket_index: usize) -> Option<&Entries<T>> {
+        let (directory_index, index) = directory_indices(bucket_index);
+        let mut directory = self.directories.get()?;
+        for _ in 0..directory_index {
+            directory = directory.next.get()?;
+        }
+        directory.buckets[index].get()
+    }
+
+    /// Returns the entries of the bucket with the given index.
+    ///
+    /// Allocates the bucket and its directories if necessary.
+    fn bucket_or_alloc(&self, bucket_index: usize) -> &Entries<T> {
+        let (directory_index, index) = directory_indices(bucket_index);
+        let mut directory = self.directories.get_or_init(|| Box::new(Directory::new(1)));
+        for _ in 0..directory_index {
+            let len_buckets = directory.buckets.len() * 2;
+            directory = directory
+                .next
+                .get_or_init(|| Box::new(Directory::new(len_buckets)));
+        }
+        directory.buckets[index].get_or_init(|| {
+            (0..config::allocate_bucket::<C>(bucket_index))
+                .map(|_| OnceCell::new())
+                .collect()
+        })
+    }
+
+    /// Pushes a new element onto the bucket vector and returns a reference to it.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push(&self, new_value: T) -> &T {
+        let index = self.len();
+        let (x, y) = config::bucket_entry_indices::<C>(index);
+        let entry = &self.bucket_or_alloc(x)[y];
+        if entry.set(new_value).is_err() {
+            panic!("encountered already occupied entry")
+        }
+        self.len.set(index + 1);
+        entry.get().expect("encountered missing pushed element")
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        let (x, y) = config::bucket_entry_indices::<C>(index);
+        self.bucket(x)?[y].get()
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+        let (x, y) = config::bucket_entry_indices::<C>(index);
+        let (directory_index, index) = directory_indices(x);
+        let mut directory = self.directories.get_mut()?;
+        for _ in 0..directory_index {
+            directory = directory.next.get_mut()?;
+        }
+        directory.buckets[index].get_mut()?[y].get_mut()
+    }
+}
+
+/// An iterator over the elements of a frozen bucket vector.
+///
+/// Only yields the elements that have been pushed before its creation.
+#[derive(Debug)]
+pub struct FrozenIter<'a, T, C> {
+    /// The iterated bucket vector.
+    vec: &'a FrozenBucketVec<T, C>,
+    /// The index of the next element to yield.
+    index: usize,
+    /// The index one past the last element to yield.
+    len: usize,
+}
+
+impl<'a, T, C> Clone for FrozenIter<'a, T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            vec: self.vec,
+            index: self.index,
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, T, C> Iterator for FrozenIter<'a, T, C>
+where
+    C: BucketVecConfig,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        self.index += 1;
+        self.vec.get(self.index - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T, C> DoubleEndedIterator for FrozenIter<'a, T, C>
+where
+    C: BucketVecConfig,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        self.len -= 1;
+        self.vec.get(self.len)
+    }
+}
+
+impl<'a, T, C> ExactSizeIterator for FrozenIter<'a, T, C> where C: BucketVecConfig {}
+
+impl<'a, T, C> core::iter::FusedIterator for FrozenIter<'a, T, C> where C: BucketVecConfig {}
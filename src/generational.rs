@@ -0,0 +1,198 @@
+//! Bucket vector with generational handles that detect stale indices.
+
+use super::{storage::BucketStorage, BucketVec, BucketVecConfig, DefaultConfig};
+use core::{cmp::Ordering, fmt, hash, marker::PhantomData};
+
+/// A handle to an element of a [`GenBucketVec`] that carries the generation
+/// of the element it refers to.
+///
+/// Accessing an element through a handle whose element has been removed
+/// returns `None` even if another element has been pushed at the same index.
+pub struct GenKey<T> {
+    /// The index of the referred to element.
+    index: usize,
+    /// The generation of the referred to element.
+    generation: u64,
+    /// Marker for the type of the referred to element.
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> GenKey<T> {
+    /// Returns the index of the referred to element.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the generation of the referred to element.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl<T> fmt::Debug for GenKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GenKey")
+            .field("index", &self.index)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+impl<T> Copy for GenKey<T> {}
+
+impl<T> Clone for GenKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> PartialEq for GenKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for GenKey<T> {}
+
+impl<T> PartialOrd for GenKey<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for GenKey<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.index, self.generation).cmp(&(other.index, other.generation))
+    }
+}
+
+impl<T> hash::Hash for GenKey<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+/// An element of a generational bucket vector.
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    /// The generation at which the element has been pushed.
+    generation: u64,
+    /// The element.
+    value: T,
+}
+
+/// A bucket vector that hands out generational handles to its elements.
+///
+/// Every removal of elements starts a new generation so that handles to
+/// removed elements never alias elements that are pushed afterwards.
+#[derive(Debug)]
+pub struct GenBucketVec<T, C = DefaultConfig> {
+    /// The elements together with their generations.
+    slots: BucketVec<Slot<T>, C>,
+    /// The generation assigned to newly pushed elements.
+    generation: u64,
+}
+
+impl<T, C> Default for GenBucketVec<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> GenBucketVec<T, C> {
+    /// Creates a new empty generational bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            slots: BucketVec::new(),
+            generation: 0,
+        }
+    }
+
+    /// Returns the number of elements stored in the bucket vector.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if the bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+}
+
+impl<T, C> GenBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Pushes a new element onto the bucket vector and returns its handle.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push(&mut self, new_value: T) -> GenKey<T> {
+        let index = self.slots.len();
+        self.slots.push(Slot {
+            generation: self.generation,
+            value: new_value,
+        });
+        GenKey {
+            index,
+            generation: self.generation,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.slots.get(index).map(|slot| &slot.value)
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.slots.get_mut(index).map(|slot| &mut slot.value)
+    }
+
+    /// Returns a shared reference to the element referred to by the handle.
+    ///
+    /// Returns `None` if the referred to element has been removed.
+    pub fn get_gen(&self, key: GenKey<T>) -> Option<&T> {
+        self.slots
+            .get(key.index)
+            .filter(|slot| slot.generation == key.generation)
+            .map(|slot| &slot.value)
+    }
+
+    /// Returns an exclusive reference to the element referred to by the handle.
+    ///
+    /// Returns `None` if the referred to element has been removed.
+    pub fn get_gen_mut(&mut self, key: GenKey<T>) -> Option<&mut T> {
+        self.slots
+            .get_mut(key.index)
+            .filter(|slot| slot.generation == key.generation)
+            .map(|slot| &mut slot.value)
+    }
+
+    /// Removes the last element from the bucket vector and returns it if any.
+    ///
+    /// Invalidates all handles to the removed element.
+    pub fn pop(&mut self) -> Option<T> {
+        let popped = self.slots.pop()?;
+        self.generation += 1;
+        Some(popped.value)
+    }
+
+    /// Shortens the bucket vector to the given length dropping all elements beyond it.
+    ///
+    /// Invalidates all handles to the removed elements.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.slots.len() {
+            self.slots.truncate(len);
+            self.generation += 1;
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Priority queue that never moves its elements.
+
+use super::{BucketVecConfig, DefaultConfig, SlotBucketVec};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A priority queue that stores its elements in a slot bucket vector.
+///
+/// Like `BinaryHeap` this is a max-heap; wrap elements in
+/// [`Reverse`](core::cmp::Reverse) to obtain a min-heap. In contrast to
+/// `BinaryHeap` the elements themselves are never moved since the heap
+/// ordering is maintained over their indices only. References to the
+/// greatest element therefore stay valid across later pushes.
+///
+/// # Note
+///
+/// Popping an element frees its slot which is reused by later pushes.
+#[derive(Debug, Clone)]
+pub struct BucketHeap<T, C = DefaultConfig> {
+    /// The queued elements.
+    elements: SlotBucketVec<T, C>,
+    /// The indices of the queued elements in heap order.
+    heap: Vec<usize>,
+}
+
+impl<T, C> Default for BucketHeap<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> BucketHeap<T, C> {
+    /// Creates a new empty bucket heap.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            elements: SlotBucketVec::new(),
+            heap: Vec::new(),
+        }
+    }
+
+    /// Returns the number of queued elements.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns `true` if no elements are queued.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the index of the greatest queued element if any.
+    pub fn peek_index(&self) -> Option<usize> {
+        self.heap.first().copied()
+    }
+}
+
+impl<T, C> BucketHeap<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the queued element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.elements.get(index)
+    }
+
+    /// Returns a shared reference to the greatest queued element if any.
+    pub fn peek(&self) -> Option<&T> {
+        self.peek_index().and_then(|index| self.elements.get(index))
+    }
+
+    /// Returns the element at the given position of the heap.
+    fn element_at(&self, position: usize) -> &T {
+        self.elements
+            .get(self.heap[position])
+            .expect("encountered out of bounds index in heap")
+    }
+}
+
+impl<T, C> BucketHeap<T, C>
+where
+    T: Ord,
+    C: BucketVecConfig,
+{
+    /// Pushes the element into the heap and returns its index.
+    ///
+    /// # Note
+    ///
+    /// Reuses the slot of a popped element if any.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket heap.
+    pub fn push(&mut self, new_value: T) -> usize {
+        let index = self.elements.insert(new_value);
+        self.heap.push(index);
+        self.sift_up(self.heap.len() - 1);
+        index
+    }
+
+    /// Removes the greatest element from the heap and returns it if any.
+    ///
+    /// # Note
+    ///
+    /// The slot of the element is reused by later pushes.
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.heap.len().checked_sub(1)?;
+        self.heap.swap(0, last);
+        let popped = self.heap.pop()?;
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        self.elements.remove(popped)
+    }
+
+    /// Moves the index at the given position up until the heap order is restored.
+    fn sift_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if self.element_at(position) <= self.element_at(parent) {
+                break;
+            }
+            self.heap.swap(position, parent);
+            position = parent;
+        }
+    }
+
+    /// Moves the index at the given position down until the heap order is restored.
+    fn sift_down(&mut self, mut position: usize) {
+        let len = self.heap.len();
+        loop {
+            let mut greatest = position;
+            for child in [2 * position + 1, 2 * position + 2] {
+                if child < len && self.element_at(child) > self.element_at(greatest) {
+                    greatest = child;
+                }
+            }
+            if greatest == position {
+                break;
+            }
+            self.heap.swap(position, greatest);
+            position = greatest;
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Fallible element lookup reporting why an index is out of bounds.
+
+use super::{BucketVec, BucketVecConfig};
+use core::fmt;
+
+/// Errors returned by [`BucketVec::try_get`] and [`BucketVec::try_get_mut`]
+/// for out of bounds indices.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IndexError {
+    /// The index lies beyond all buckets allocated for elements.
+    MissingBucket {
+        /// The out of bounds index.
+        index: usize,
+        /// The length of the bucket vector.
+        len: usize,
+    },
+    /// The index lies within the last bucket but its entry is not occupied.
+    MissingEntry {
+        /// The out of bounds index.
+        index: usize,
+        /// The length of the bucket vector.
+        len: usize,
+        /// The index of the bucket the index lies within.
+        bucket_index: usize,
+        /// The index of the unoccupied entry within its bucket.
+        entry_index: usize,
+    },
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingBucket { index, len } => write!(
+                f,
+                "index {} is out of bounds for bucket vector length {} since no bucket is allocated for it",
+                index, len
+            ),
+            Self::MissingEntry {
+                index,
+                len,
+                bucket_index,
+                entry_index,
+            } => write!(
+                f,
+                "index {} is out of bounds for bucket vector length {} since entry {} of bucket {} is not occupied",
+                index, len, entry_index, bucket_index
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IndexError {}
+
+impl<T, C> BucketVec<T, C> {
+    /// Returns the error describing why the out of bounds index missed.
+    fn index_error(&self, index: usize) -> IndexError {
+        let len = self.len();
+        let last = self.buckets.last().zip(self.offsets.last());
+        match last {
+            Some((bucket, &offset)) if index - offset < bucket.capacity() => {
+                IndexError::MissingEntry {
+                    index,
+                    len,
+                    bucket_index: self.buckets.len() - 1,
+                    entry_index: index - offset,
+                }
+            }
+            _ => IndexError::MissingBucket { index, len },
+        }
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the element at the given index.
+    ///
+    /// # Errors
+    ///
+    /// If the index is out of bounds.
+    pub fn try_get(&self, index: usize) -> Result<&T, IndexError> {
+        match self.get(index) {
+            Some(value) => Ok(value),
+            None => Err(self.index_error(index)),
+        }
+    }
+
+    /// Returns an exclusive reference to the element at the given index.
+    ///
+    /// # Errors
+    ///
+    /// If the index is out of bounds.
+    pub fn try_get_mut(&mut self, index: usize) -> Result<&mut T, IndexError> {
+        if index >= self.len() {
+            return Err(self.index_error(index));
+        }
+        Ok(self
+            .get_mut(index)
+            .expect("encountered missing element within bounds"))
+    }
+}
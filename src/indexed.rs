@@ -0,0 +1,206 @@
+//! Hash index over the elements of a bucket vector by an extracted key.
+
+use super::{BucketVec, BucketVecConfig, DefaultConfig, Iter};
+use core::{borrow::Borrow, hash::Hash};
+use std::collections::HashMap;
+
+/// A hash index mapping keys extracted from elements to their indices in a
+/// bucket vector.
+///
+/// # Note
+///
+/// Every key refers to at most one element. Later elements win over earlier
+/// elements with the same key.
+#[derive(Debug, Clone)]
+pub struct HashIndex<K, F> {
+    /// Extracts the key of an element.
+    key_of: F,
+    /// The index of the element of every key.
+    indices: HashMap<K, usize>,
+}
+
+impl<K, F> HashIndex<K, F> {
+    /// Creates a new empty hash index using the given key extractor.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new(key_of: F) -> Self {
+        Self {
+            key_of,
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of indexed keys.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if no keys are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+impl<K, F> HashIndex<K, F>
+where
+    K: Hash + Eq,
+{
+    /// Creates a hash index over all elements of the bucket vector using the
+    /// given key extractor.
+    pub fn build<T, C>(vec: &BucketVec<T, C>, key_of: F) -> Self
+    where
+        F: Fn(&T) -> K,
+    {
+        let mut index = Self::new(key_of);
+        for (position, value) in vec.iter().enumerate() {
+            index.insert(value, position);
+        }
+        index
+    }
+
+    /// Indexes the value stored at the given index and returns the index of
+    /// the previously indexed element with the same key if any.
+    pub fn insert<T>(&mut self, value: &T, index: usize) -> Option<usize>
+    where
+        F: Fn(&T) -> K,
+    {
+        self.indices.insert((self.key_of)(value), index)
+    }
+
+    /// Returns the index of the element with the given key if any.
+    pub fn index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.indices.get(key).copied()
+    }
+
+    /// Returns a shared reference to the element of the bucket vector with
+    /// the given key if any.
+    pub fn get_by_key<'a, Q, T, C>(&self, vec: &'a BucketVec<T, C>, key: &Q) -> Option<&'a T>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        C: BucketVecConfig,
+    {
+        self.index_of(key).and_then(|index| vec.get(index))
+    }
+}
+
+/// A bucket vector that keeps a [`HashIndex`] over its elements up to date.
+///
+/// Elements can only be pushed and never mutated so that their keys stay
+/// consistent with the index.
+#[derive(Debug, Clone)]
+pub struct IndexedBucketVec<T, K, F, C = DefaultConfig> {
+    /// The indexed elements.
+    values: BucketVec<T, C>,
+    /// The index over the keys of the elements.
+    index: HashIndex<K, F>,
+}
+
+impl<T, K, F, C> IndexedBucketVec<T, K, F, C> {
+    /// Creates a new empty indexed bucket vector using the given key extractor.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new(key_of: F) -> Self {
+        Self {
+            values: BucketVec::new(),
+            index: HashIndex::new(key_of),
+        }
+    }
+
+    /// Returns the number of elements stored in the indexed bucket vector.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the indexed bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over shared references to the elements.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.values.iter()
+    }
+
+    /// Returns the underlying bucket vector.
+    pub fn as_bucket_vec(&self) -> &BucketVec<T, C> {
+        &self.values
+    }
+
+    /// Returns the hash index over the elements.
+    pub fn hash_index(&self) -> &HashIndex<K, F> {
+        &self.index
+    }
+}
+
+impl<T, K, F, C> IndexedBucketVec<T, K, F, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+}
+
+impl<T, K, F, C> IndexedBucketVec<T, K, F, C>
+where
+    K: Hash + Eq,
+    F: Fn(&T) -> K,
+    C: BucketVecConfig,
+{
+    /// Pushes the value, indexes its key and returns its index.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the indexed bucket vector.
+    pub fn push(&mut self, new_value: T) -> usize {
+        let index = self.values.len();
+        let value = self.values.push_get(new_value);
+        self.index.insert(&*value, index);
+        index
+    }
+
+    /// Returns the index of the element with the given key if any.
+    pub fn index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.index_of(key)
+    }
+
+    /// Returns a shared reference to the element with the given key if any.
+    pub fn get_by_key<Q>(&self, key: &Q) -> Option<&T>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.get_by_key(&self.values, key)
+    }
+}
+
+impl<T, K, F, C> Extend<T> for IndexedBucketVec<T, K, F, C>
+where
+    K: Hash + Eq,
+    F: Fn(&T) -> K,
+    C: BucketVecConfig,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
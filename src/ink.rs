@@ -0,0 +1,22 @@
+//! Support for using bucket vectors as ink! contract storage fields.
+//!
+//! Bucket vectors are `Storable` and `Packed` through their SCALE codec
+//! implementation which is available via the `scale-3` crate feature even
+//! for `no_std` contract builds. Only the storage layout has to be described
+//! here which ink! requires for metadata generation under `std`.
+
+use super::BucketVec;
+use ink_metadata::layout::{Layout, LayoutKey, LeafLayout};
+use ink_primitives::Key;
+use ink_storage_traits::{Packed, StorageLayout};
+
+/// Bucket vectors occupy a single storage cell just like vectors.
+impl<T, C> StorageLayout for BucketVec<T, C>
+where
+    T: scale_info::TypeInfo + 'static + Packed,
+    C: 'static,
+{
+    fn layout(key: &Key) -> Layout {
+        Layout::Leaf(LeafLayout::from_key::<Self>(LayoutKey::from(key)))
+    }
+}
@@ -0,0 +1,141 @@
+//! Bucket vector that stores every distinct value only once.
+
+use super::{BucketVec, BucketVecConfig, DefaultConfig, Iter};
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+};
+use std::collections::{hash_map::RandomState, HashMap};
+
+/// A bucket vector that stores every distinct value only once.
+///
+/// Pushing a value that is equal to an already stored value returns the
+/// index of the stored value instead. Since values are never moved their
+/// references serve well as interned handles.
+#[derive(Debug, Clone)]
+pub struct InternBucketVec<T, C = DefaultConfig, S = RandomState> {
+    /// The distinct values in order of their first push.
+    values: BucketVec<T, C>,
+    /// The index of the most recently pushed value of every hash.
+    heads: HashMap<u64, usize>,
+    /// The index of the previously pushed value with the same hash for every value.
+    collisions: Vec<Option<usize>>,
+    /// The hasher used to hash all values.
+    hasher: S,
+}
+
+impl<T, C, S> Default for InternBucketVec<T, C, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T, C> InternBucketVec<T, C> {
+    /// Creates a new empty interning bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<T, C, S> InternBucketVec<T, C, S> {
+    /// Creates a new empty interning bucket vector using the given hasher.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            values: BucketVec::new(),
+            heads: HashMap::new(),
+            collisions: Vec::new(),
+            hasher,
+        }
+    }
+
+    /// Returns the number of distinct values stored in the bucket vector.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over the distinct values in order of their first push.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.values.iter()
+    }
+}
+
+impl<T, C, S> InternBucketVec<T, C, S>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the value at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+}
+
+impl<T, C, S> InternBucketVec<T, C, S>
+where
+    T: Hash + Eq,
+    C: BucketVecConfig,
+    S: BuildHasher,
+{
+    /// Returns the index of the stored value equal to the given value if any.
+    pub fn index_of<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(value);
+        let mut candidate = self.heads.get(&hash).copied();
+        while let Some(index) = candidate {
+            let stored = self
+                .values
+                .get(index)
+                .expect("encountered missing value of indexed hash");
+            if stored.borrow() == value {
+                return Some(index);
+            }
+            candidate = self.collisions[index];
+        }
+        None
+    }
+
+    /// Pushes the value unless an equal value is already stored and returns
+    /// the index of the stored value.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push_unique(&mut self, new_value: T) -> usize {
+        if let Some(index) = self.index_of(&new_value) {
+            return index;
+        }
+        let hash = self.hasher.hash_one(&new_value);
+        let index = self.values.len();
+        self.values.push(new_value);
+        self.collisions.push(self.heads.insert(hash, index));
+        index
+    }
+}
+
+impl<'a, T, C, S> IntoIterator for &'a InternBucketVec<T, C, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,245 @@
+use super::{BucketVec, BucketVecConfig, DefaultConfig};
+use core::convert::TryFrom;
+use std::io;
+
+/// The length of the stack buffer used by [`BucketVec::extend_from_reader`].
+const READ_BUFFER_LEN: usize = 8 * 1024;
+
+/// Appends the written bytes to the bucket vector.
+///
+/// # Note
+///
+/// Bytes are copied in bulk into the spare capacity of the buckets.
+/// Previously written bytes are never moved.
+impl<C> io::Write for BucketVec<u8, C>
+where
+    C: BucketVecConfig,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<C> BucketVec<u8, C>
+where
+    C: BucketVecConfig,
+{
+    /// Reads all bytes from `reader` until EOF into the buckets and returns
+    /// the number of read bytes.
+    ///
+    /// # Note
+    ///
+    /// Bytes are read through a bounded stack buffer that is zeroed only
+    /// once since readers require initialized buffers. They are then copied
+    /// in bulk into the spare capacity of the buckets.
+    /// New buckets are allocated as required.
+    /// Previously stored bytes are never moved.
+    ///
+    /// # Errors
+    ///
+    /// Stops at and returns the first error of `reader` that is not of kind
+    /// [`io::ErrorKind::Interrupted`].
+    /// Bytes read before the error remain in the bucket vector.
+    pub fn extend_from_reader<R>(&mut self, mut reader: R) -> io::Result<usize>
+    where
+        R: io::Read,
+    {
+        let mut buffer = [0_u8; READ_BUFFER_LEN];
+        let mut total = 0;
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => return Ok(total),
+                Ok(read) => {
+                    let read = core::cmp::min(read, buffer.len());
+                    self.extend_from_slice(&buffer[..read]);
+                    total += read;
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+/// A cursor over a byte bucket vector that reads, writes and seeks like
+/// [`io::Cursor`] over a `Vec<u8>`.
+///
+/// Writing overwrites existing bytes in place and appends bytes past the end.
+/// Seeking past the end and writing pads the gap with zeros.
+///
+/// # Note
+///
+/// Unlike a `Vec<u8>` the bucket vector never moves previously written
+/// bytes so that pointers into them remain valid while writing.
+#[derive(Debug, Clone)]
+pub struct BucketCursor<C = DefaultConfig> {
+    /// The underlying byte bucket vector.
+    inner: BucketVec<u8, C>,
+    /// The position of the next read or write.
+    position: u64,
+}
+
+impl<C> Default for BucketCursor<C> {
+    fn default() -> Self {
+        Self::new(BucketVec::new())
+    }
+}
+
+/// Returns the error of seeks and writes to invalid positions.
+fn invalid_position() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "invalid seek to a negative or overflowing position",
+    )
+}
+
+impl<C> BucketCursor<C> {
+    /// Creates a new cursor over the byte bucket vector positioned at its start.
+    pub fn new(inner: BucketVec<u8, C>) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    /// Returns the underlying byte bucket vector.
+    pub fn into_inner(self) -> BucketVec<u8, C> {
+        self.inner
+    }
+
+    /// Returns a shared reference to the underlying byte bucket vector.
+    pub fn get_ref(&self) -> &BucketVec<u8, C> {
+        &self.inner
+    }
+
+    /// Returns an exclusive reference to the underlying byte bucket vector.
+    pub fn get_mut(&mut self) -> &mut BucketVec<u8, C> {
+        &mut self.inner
+    }
+
+    /// Returns the position of the next read or write.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Sets the position of the next read or write.
+    ///
+    /// # Note
+    ///
+    /// The position may lie past the end of the byte bucket vector.
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position;
+    }
+}
+
+impl<C> BucketCursor<C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns the bytes from the position until the end of its bucket.
+    ///
+    /// Returns an empty slice if the position lies at or past the end.
+    fn remaining_in_bucket(&self) -> &[u8] {
+        let indices = usize::try_from(self.position)
+            .ok()
+            .and_then(|position| self.inner.bucket_entry_indices(position));
+        match indices {
+            Some((x, y)) => &self.inner.buckets[x].as_slice()[y..],
+            None => &[],
+        }
+    }
+}
+
+impl<C> io::Read for BucketCursor<C>
+where
+    C: BucketVecConfig,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+        while read < buf.len() {
+            let bytes = self.remaining_in_bucket();
+            if bytes.is_empty() {
+                break;
+            }
+            let len = core::cmp::min(bytes.len(), buf.len() - read);
+            buf[read..read + len].copy_from_slice(&bytes[..len]);
+            read += len;
+            self.position += len as u64;
+        }
+        Ok(read)
+    }
+}
+
+/// Yields the bytes of one bucket at a time without copying.
+impl<C> io::BufRead for BucketCursor<C>
+where
+    C: BucketVecConfig,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.remaining_in_bucket())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position += amt as u64;
+    }
+}
+
+impl<C> io::Write for BucketCursor<C>
+where
+    C: BucketVecConfig,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let position = usize::try_from(self.position).map_err(|_| invalid_position())?;
+        let end = position
+            .checked_add(buf.len())
+            .ok_or_else(invalid_position)?;
+        if position > self.inner.len() {
+            let gap = position - self.inner.len();
+            self.inner.push_repeated(gap, 0);
+        }
+        let mut written = 0;
+        while written < buf.len() {
+            match self.inner.bucket_entry_indices(position + written) {
+                Some((x, y)) => {
+                    let bytes = &mut self.inner.buckets[x].as_mut_slice()[y..];
+                    let len = core::cmp::min(bytes.len(), buf.len() - written);
+                    bytes[..len].copy_from_slice(&buf[written..written + len]);
+                    written += len;
+                }
+                None => {
+                    self.inner.extend_from_slice(&buf[written..]);
+                    written = buf.len();
+                }
+            }
+        }
+        self.position = end as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<C> io::Seek for BucketCursor<C>
+where
+    C: BucketVecConfig,
+{
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            io::SeekFrom::Start(position) => {
+                self.position = position;
+                return Ok(position);
+            }
+            io::SeekFrom::End(offset) => (self.inner.len() as u64, offset),
+            io::SeekFrom::Current(offset) => (self.position, offset),
+        };
+        let position = base
+            .checked_add_signed(offset)
+            .ok_or_else(invalid_position)?;
+        self.position = position;
+        Ok(position)
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Bucket, BucketVec};
+use super::{Bucket, BucketVec, BucketVecConfig, ChunkPairs};
 
 #[cfg(feature = "std")]
 use std::vec;
@@ -6,11 +6,157 @@ use std::vec;
 #[cfg(not(feature = "std"))]
 use alloc::vec;
 
+/// An iterator over buckets that knows the global index of the first element
+/// of every remaining bucket.
+///
+/// This allows to jump over many buckets at once to the bucket storing an
+/// element instead of walking all buckets in between.
+///
+/// # Note
+///
+/// All remaining buckets except for the last one must be full.
+#[derive(Debug, Clone, Default)]
+struct Buckets<I, O> {
+    /// The remaining buckets.
+    iter: I,
+    /// The global index of the first element of every bucket.
+    offsets: O,
+    /// The index of the first remaining bucket within `offsets`.
+    front: usize,
+    /// The global index one past the last element of the remaining buckets.
+    end: usize,
+}
+
+impl<I, O> Buckets<I, O>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+    O: AsRef<[usize]>,
+{
+    /// Creates a new iterator over the buckets with the given offsets whose
+    /// elements end before `end`.
+    fn new(iter: I, offsets: O, end: usize) -> Self {
+        debug_assert_eq!(iter.len(), offsets.as_ref().len());
+        Self {
+            iter,
+            offsets,
+            front: 0,
+            end,
+        }
+    }
+
+    /// Returns the offsets of the remaining buckets.
+    fn offsets(&self) -> &[usize] {
+        &self.offsets.as_ref()[self.front..self.front + self.iter.len()]
+    }
+
+    /// Skips the first `n` elements of the remaining buckets.
+    ///
+    /// Returns the bucket storing the next element and its index within it.
+    /// Buckets in front of it are consumed without visiting their elements.
+    ///
+    /// # Errors
+    ///
+    /// Consumes all buckets and returns the number of elements left to skip
+    /// if the remaining buckets store no more than `n` elements.
+    fn skip_front(&mut self, n: usize) -> Result<(I::Item, usize), usize> {
+        let offsets = self.offsets();
+        let start = match offsets.first() {
+            Some(&start) => start,
+            None => return Err(n),
+        };
+        if n >= self.end - start {
+            self.nth(self.iter.len());
+            return Err(n - (self.end - start));
+        }
+        let index = start + n;
+        let skipped = offsets.partition_point(|&offset| offset <= index) - 1;
+        let entry = index - offsets[skipped];
+        let bucket = self.nth(skipped).expect("encountered missing bucket");
+        Ok((bucket, entry))
+    }
+
+    /// Skips the last `n` elements of the remaining buckets.
+    ///
+    /// Returns the bucket storing the next element from the back and its
+    /// index within it counted from the back of the bucket.
+    /// Buckets behind it are consumed without visiting their elements.
+    ///
+    /// # Errors
+    ///
+    /// Consumes all buckets and returns the number of elements left to skip
+    /// if the remaining buckets store no more than `n` elements.
+    fn skip_back(&mut self, n: usize) -> Result<(I::Item, usize), usize> {
+        let offsets = self.offsets();
+        let start = match offsets.first() {
+            Some(&start) => start,
+            None => return Err(n),
+        };
+        if n >= self.end - start {
+            self.nth_back(self.iter.len());
+            return Err(n - (self.end - start));
+        }
+        let index = self.end - 1 - n;
+        let position = offsets.partition_point(|&offset| offset <= index) - 1;
+        let bucket_end = offsets.get(position + 1).copied().unwrap_or(self.end);
+        let entry = bucket_end - 1 - index;
+        let skipped = offsets.len() - 1 - position;
+        let bucket = self.nth_back(skipped).expect("encountered missing bucket");
+        Ok((bucket, entry))
+    }
+}
+
+impl<I, O> Iterator for Buckets<I, O>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+    O: AsRef<[usize]>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nth(0)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let len = self.iter.len();
+        let bucket = self.iter.nth(n);
+        self.front += len - self.iter.len();
+        bucket
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, O> DoubleEndedIterator for Buckets<I, O>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+    O: AsRef<[usize]>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.nth_back(0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let bucket = self.iter.nth_back(n)?;
+        // The popped bucket starts where the remaining buckets end.
+        self.end = self.offsets.as_ref()[self.front + self.iter.len()];
+        Some(bucket)
+    }
+}
+
+impl<I, O> ExactSizeIterator for Buckets<I, O>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+    O: AsRef<[usize]>,
+{
+}
+
 /// An iterator yielding shared references to the elements of a bucket vector.
 #[derive(Debug, Clone)]
 pub struct Iter<'a, T> {
     /// Buckets iterator.
-    buckets: core::slice::Iter<'a, Bucket<T>>,
+    buckets: Buckets<core::slice::Iter<'a, Bucket<T>>, &'a [usize]>,
     /// Front iterator for `next`.
     front_iter: Option<core::slice::Iter<'a, T>>,
     /// Back iterator for `next_back`.
@@ -22,11 +168,115 @@ pub struct Iter<'a, T> {
 impl<'a, T> Iter<'a, T> {
     /// Creates a new iterator over the bucket vector.
     pub(crate) fn new<C>(vec: &'a BucketVec<T, C>) -> Self {
+        Self::from_buckets(&vec.buckets, &vec.offsets, vec.len())
+    }
+
+    /// Creates a new iterator over the given buckets holding `len` elements.
+    ///
+    /// The offsets store the index of the first element of every bucket.
+    pub(crate) fn from_buckets(buckets: &'a [Bucket<T>], offsets: &'a [usize], len: usize) -> Self {
         Self {
-            buckets: vec.buckets.iter(),
+            buckets: Buckets::new(buckets.iter(), offsets, len),
             front_iter: None,
             back_iter: None,
-            len: vec.len(),
+            len,
+        }
+    }
+
+    /// Creates a new iterator over the `len` elements of the given buckets
+    /// starting at `start` and ending before `end`.
+    ///
+    /// Both positions are given as bucket index and entry index.
+    fn from_range(
+        buckets: &'a [Bucket<T>],
+        offsets: &'a [usize],
+        start: (usize, usize),
+        end: (usize, usize),
+        len: usize,
+    ) -> Self {
+        let (start_bucket, start_entry) = start;
+        let (end_bucket, end_entry) = end;
+        if start_bucket == end_bucket {
+            let entries = &buckets[start_bucket].as_slice()[start_entry..end_entry];
+            return Self {
+                buckets: Default::default(),
+                front_iter: Some(entries.iter()),
+                back_iter: None,
+                len,
+            };
+        }
+        let back_iter = if end_entry == 0 {
+            None
+        } else {
+            Some(buckets[end_bucket].as_slice()[..end_entry].iter())
+        };
+        let middle = start_bucket + 1..end_bucket;
+        let end_middle = match middle.clone().last() {
+            Some(last) => offsets[last] + buckets[last].len(),
+            None => 0,
+        };
+        Self {
+            buckets: Buckets::new(buckets[middle.clone()].iter(), &offsets[middle], end_middle),
+            front_iter: Some(buckets[start_bucket].as_slice()[start_entry..].iter()),
+            back_iter,
+            len,
+        }
+    }
+
+    /// Returns the remaining elements as a sequence of contiguous slices.
+    ///
+    /// This allows to switch to bulk slice operations without losing the
+    /// position of the iterator.
+    pub fn remaining_buckets(&self) -> impl DoubleEndedIterator<Item = &'a [T]> + 'a {
+        let front = self.front_iter.clone().map(|entries| entries.as_slice());
+        let back = self.back_iter.clone().map(|entries| entries.as_slice());
+        front
+            .into_iter()
+            .chain(self.buckets.clone().map(Bucket::as_slice))
+            .chain(back)
+            .filter(|entries| !entries.is_empty())
+    }
+
+    /// Applies `search` to the contiguous entries of every bucket in order
+    /// until it returns `Some`.
+    ///
+    /// Operating on whole slices allows the compiler to vectorize searches.
+    fn search<R, F>(&mut self, mut search: F) -> Option<R>
+    where
+        F: FnMut(&mut core::slice::Iter<'a, T>) -> Option<R>,
+    {
+        if let Some(ref mut front_iter) = self.front_iter {
+            let len_front = front_iter.len();
+            let found = search(front_iter);
+            self.len -= len_front - front_iter.len();
+            if found.is_some() {
+                return found;
+            }
+        }
+        for bucket in &mut self.buckets {
+            let mut front_iter = bucket.iter();
+            let found = search(&mut front_iter);
+            self.len -= bucket.len() - front_iter.len();
+            self.front_iter = Some(front_iter);
+            if found.is_some() {
+                return found;
+            }
+        }
+        let back_iter = self.back_iter.as_mut()?;
+        let len_back = back_iter.len();
+        let found = search(back_iter);
+        self.len -= len_back - back_iter.len();
+        found
+    }
+}
+
+impl<'a, T> Default for Iter<'a, T> {
+    fn default() -> Self {
+        Self {
+            buckets: Default::default(),
+            front_iter: None,
+            back_iter: None,
+            len: 0,
         }
     }
 }
@@ -44,17 +294,106 @@ impl<'a, T> Iterator for Iter<'a, T> {
             }
             match self.buckets.next() {
                 None => {
-                    self.len -= 1;
-                    return self.back_iter.as_mut()?.next();
+                    let back = self.back_iter.as_mut()?.next();
+                    if back.is_some() {
+                        self.len -= 1;
+                    }
+                    return back;
                 }
                 Some(bucket) => self.front_iter = Some(bucket.iter()),
             }
         }
     }
 
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let remaining = self.len.saturating_sub(n).saturating_sub(1);
+        let mut n = n;
+        if let Some(ref mut front_iter) = self.front_iter {
+            let len_front = front_iter.len();
+            if n < len_front {
+                self.len = remaining;
+                return front_iter.nth(n);
+            }
+            n -= len_front;
+            self.front_iter = None;
+        }
+        // Jump to the bucket of the element without visiting the buckets in between.
+        match self.buckets.skip_front(n) {
+            Ok((bucket, entry)) => {
+                let mut front_iter = bucket.iter();
+                let front = front_iter.nth(entry);
+                self.front_iter = Some(front_iter);
+                self.len = remaining;
+                front
+            }
+            Err(n) => {
+                self.len = remaining;
+                self.back_iter.as_mut()?.nth(n)
+            }
+        }
+    }
+
+    fn find<P>(&mut self, mut predicate: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.search(|entries| entries.find(&mut predicate))
+    }
+
+    fn position<P>(&mut self, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        let len = self.len;
+        self.search(|entries| entries.position(&mut predicate))
+            .map(|_| len - self.len - 1)
+    }
+
+    fn any<P>(&mut self, mut predicate: P) -> bool
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        self.search(|entries| Some(()).filter(|_| entries.any(&mut predicate)))
+            .is_some()
+    }
+
+    fn all<P>(&mut self, mut predicate: P) -> bool
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        self.search(|entries| Some(()).filter(|_| !entries.all(&mut predicate)))
+            .is_none()
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.len(), Some(self.len()))
     }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        // Fold over the contiguous entries of every bucket in isolation.
+        let mut acc = init;
+        if let Some(front_iter) = self.front_iter {
+            acc = front_iter.fold(acc, &mut f);
+        }
+        for bucket in self.buckets {
+            acc = bucket.iter().fold(acc, &mut f);
+        }
+        if let Some(back_iter) = self.back_iter {
+            acc = back_iter.fold(acc, &mut f);
+        }
+        acc
+    }
 }
 
 impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
@@ -68,13 +407,62 @@ impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
             }
             match self.buckets.next_back() {
                 None => {
-                    self.len -= 1;
-                    return self.front_iter.as_mut()?.next_back();
+                    let front = self.front_iter.as_mut()?.next_back();
+                    if front.is_some() {
+                        self.len -= 1;
+                    }
+                    return front;
                 }
                 Some(bucket) => self.back_iter = Some(bucket.iter()),
             }
         }
     }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let remaining = self.len.saturating_sub(n).saturating_sub(1);
+        let mut n = n;
+        if let Some(ref mut back_iter) = self.back_iter {
+            let len_back = back_iter.len();
+            if n < len_back {
+                self.len = remaining;
+                return back_iter.nth_back(n);
+            }
+            n -= len_back;
+            self.back_iter = None;
+        }
+        // Jump to the bucket of the element without visiting the buckets in between.
+        match self.buckets.skip_back(n) {
+            Ok((bucket, entry)) => {
+                let mut back_iter = bucket.iter();
+                let back = back_iter.nth_back(entry);
+                self.back_iter = Some(back_iter);
+                self.len = remaining;
+                back
+            }
+            Err(n) => {
+                self.len = remaining;
+                self.front_iter.as_mut()?.nth_back(n)
+            }
+        }
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        // Fold over the contiguous entries of every bucket in isolation.
+        let mut acc = init;
+        if let Some(back_iter) = self.back_iter {
+            acc = back_iter.rfold(acc, &mut f);
+        }
+        for bucket in self.buckets.rev() {
+            acc = bucket.iter().rfold(acc, &mut f);
+        }
+        if let Some(front_iter) = self.front_iter {
+            acc = front_iter.rfold(acc, &mut f);
+        }
+        acc
+    }
 }
 
 impl<'a, T> ExactSizeIterator for Iter<'a, T> {
@@ -83,11 +471,13 @@ impl<'a, T> ExactSizeIterator for Iter<'a, T> {
     }
 }
 
+impl<'a, T> core::iter::FusedIterator for Iter<'a, T> {}
+
 /// An iterator yielding exclusive references to the elements of a bucket vector.
 #[derive(Debug)]
 pub struct IterMut<'a, T> {
     /// Buckets iterator used by forward iteration.
-    buckets: core::slice::IterMut<'a, Bucket<T>>,
+    buckets: Buckets<core::slice::IterMut<'a, Bucket<T>>, &'a [usize]>,
     /// Front iterator for `next`.
     front_iter: Option<core::slice::IterMut<'a, T>>,
     /// Back iterator for `next_back`.
@@ -100,13 +490,67 @@ impl<'a, T> IterMut<'a, T> {
     /// Creates a new iterator over the bucket vector.
     pub(crate) fn new<C>(vec: &'a mut BucketVec<T, C>) -> Self {
         let len = vec.len();
+        Self::from_buckets(&mut vec.buckets, &vec.offsets, len)
+    }
+
+    /// Creates a new iterator over the given buckets holding `len` elements.
+    ///
+    /// The offsets store the index of the first element of every bucket.
+    pub(crate) fn from_buckets(
+        buckets: &'a mut [Bucket<T>],
+        offsets: &'a [usize],
+        len: usize,
+    ) -> Self {
         Self {
-            buckets: vec.buckets.iter_mut(),
+            buckets: Buckets::new(buckets.iter_mut(), offsets, len),
             front_iter: None,
             back_iter: None,
             len,
         }
     }
+
+    /// Returns the remaining elements as a sequence of contiguous slices.
+    ///
+    /// This allows to switch to bulk slice operations without losing the
+    /// position of the iterator.
+    pub fn remaining_buckets(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        let front = self.front_iter.as_ref().map(|entries| entries.as_slice());
+        let back = self.back_iter.as_ref().map(|entries| entries.as_slice());
+        front
+            .into_iter()
+            .chain(self.buckets.iter.as_slice().iter().map(Bucket::as_slice))
+            .chain(back)
+            .filter(|entries| !entries.is_empty())
+    }
+
+    /// Converts the iterator into the remaining elements as a sequence of
+    /// contiguous exclusive slices.
+    pub fn into_remaining_buckets(self) -> impl DoubleEndedIterator<Item = &'a mut [T]> {
+        let front = self.front_iter.map(core::slice::IterMut::into_slice);
+        let back = self.back_iter.map(core::slice::IterMut::into_slice);
+        front
+            .into_iter()
+            .chain(
+                self.buckets
+                    .iter
+                    .into_slice()
+                    .iter_mut()
+                    .map(Bucket::as_mut_slice),
+            )
+            .chain(back)
+            .filter(|entries| !entries.is_empty())
+    }
+}
+
+impl<'a, T> Default for IterMut<'a, T> {
+    fn default() -> Self {
+        Self {
+            buckets: Default::default(),
+            front_iter: None,
+            back_iter: None,
+            len: 0,
+        }
+    }
 }
 
 impl<'a, T> Iterator for IterMut<'a, T> {
@@ -122,17 +566,74 @@ impl<'a, T> Iterator for IterMut<'a, T> {
             }
             match self.buckets.next() {
                 None => {
-                    self.len -= 1;
-                    return self.back_iter.as_mut()?.next();
+                    let back = self.back_iter.as_mut()?.next();
+                    if back.is_some() {
+                        self.len -= 1;
+                    }
+                    return back;
                 }
                 Some(bucket) => self.front_iter = Some(bucket.iter_mut()),
             }
         }
     }
 
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let remaining = self.len.saturating_sub(n).saturating_sub(1);
+        let mut n = n;
+        if let Some(ref mut front_iter) = self.front_iter {
+            let len_front = front_iter.len();
+            if n < len_front {
+                self.len = remaining;
+                return front_iter.nth(n);
+            }
+            n -= len_front;
+            self.front_iter = None;
+        }
+        // Jump to the bucket of the element without visiting the buckets in between.
+        match self.buckets.skip_front(n) {
+            Ok((bucket, entry)) => {
+                let mut front_iter = bucket.iter_mut();
+                let front = front_iter.nth(entry);
+                self.front_iter = Some(front_iter);
+                self.len = remaining;
+                front
+            }
+            Err(n) => {
+                self.len = remaining;
+                self.back_iter.as_mut()?.nth(n)
+            }
+        }
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.len(), Some(self.len()))
     }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        // Fold over the contiguous entries of every bucket in isolation.
+        let mut acc = init;
+        if let Some(front_iter) = self.front_iter {
+            acc = front_iter.fold(acc, &mut f);
+        }
+        for bucket in self.buckets {
+            acc = bucket.iter_mut().fold(acc, &mut f);
+        }
+        if let Some(back_iter) = self.back_iter {
+            acc = back_iter.fold(acc, &mut f);
+        }
+        acc
+    }
 }
 
 impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
@@ -146,13 +647,62 @@ impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
             }
             match self.buckets.next_back() {
                 None => {
-                    self.len -= 1;
-                    return self.front_iter.as_mut()?.next_back();
+                    let front = self.front_iter.as_mut()?.next_back();
+                    if front.is_some() {
+                        self.len -= 1;
+                    }
+                    return front;
                 }
                 Some(bucket) => self.back_iter = Some(bucket.iter_mut()),
             }
         }
     }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let remaining = self.len.saturating_sub(n).saturating_sub(1);
+        let mut n = n;
+        if let Some(ref mut back_iter) = self.back_iter {
+            let len_back = back_iter.len();
+            if n < len_back {
+                self.len = remaining;
+                return back_iter.nth_back(n);
+            }
+            n -= len_back;
+            self.back_iter = None;
+        }
+        // Jump to the bucket of the element without visiting the buckets in between.
+        match self.buckets.skip_back(n) {
+            Ok((bucket, entry)) => {
+                let mut back_iter = bucket.iter_mut();
+                let back = back_iter.nth_back(entry);
+                self.back_iter = Some(back_iter);
+                self.len = remaining;
+                back
+            }
+            Err(n) => {
+                self.len = remaining;
+                self.front_iter.as_mut()?.nth_back(n)
+            }
+        }
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        // Fold over the contiguous entries of every bucket in isolation.
+        let mut acc = init;
+        if let Some(back_iter) = self.back_iter {
+            acc = back_iter.rfold(acc, &mut f);
+        }
+        for bucket in self.buckets.rev() {
+            acc = bucket.iter_mut().rfold(acc, &mut f);
+        }
+        if let Some(front_iter) = self.front_iter {
+            acc = front_iter.rfold(acc, &mut f);
+        }
+        acc
+    }
 }
 
 impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
@@ -161,11 +711,19 @@ impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
     }
 }
 
+impl<'a, T> core::iter::FusedIterator for IterMut<'a, T> {}
+
 /// An iterator yielding the elements of a bucket vector by value.
-#[derive(Debug)]
+///
+/// # Note
+///
+/// Every bucket is freed as soon as all of its elements have been yielded.
+/// Streaming a bucket vector into another container therefore never holds
+/// on to the memory of consumed buckets.
+#[derive(Debug, Clone)]
 pub struct IntoIter<T> {
     /// Buckets iterator used by forward iteration.
-    buckets: vec::IntoIter<Bucket<T>>,
+    buckets: Buckets<vec::IntoIter<Bucket<T>>, vec::Vec<usize>>,
     /// Front iterator for `next`.
     front_iter: Option<vec::IntoIter<T>>,
     /// Back iterator for `next_back`.
@@ -176,10 +734,22 @@ pub struct IntoIter<T> {
 
 impl<T> IntoIter<T> {
     /// Creates a new iterator over the bucket vector.
-    pub(crate) fn new<C>(vec: BucketVec<T, C>) -> Self {
-        let len = vec.len();
+    pub(crate) fn new<C>(mut vec: BucketVec<T, C>) -> Self {
+        let offsets = core::mem::take(&mut vec.offsets);
+        let (buckets, len) = vec.into_entry_vector();
+        Self::from_buckets(buckets, offsets, len)
+    }
+
+    /// Creates a new iterator over the given buckets holding `len` elements.
+    ///
+    /// The offsets store the index of the first element of every bucket.
+    pub(crate) fn from_buckets(
+        buckets: vec::Vec<Bucket<T>>,
+        offsets: vec::Vec<usize>,
+        len: usize,
+    ) -> Self {
         Self {
-            buckets: vec.buckets.into_iter(),
+            buckets: Buckets::new(buckets.into_iter(), offsets, len),
             front_iter: None,
             back_iter: None,
             len,
@@ -187,6 +757,17 @@ impl<T> IntoIter<T> {
     }
 }
 
+impl<T> Default for IntoIter<T> {
+    fn default() -> Self {
+        Self {
+            buckets: Default::default(),
+            front_iter: None,
+            back_iter: None,
+            len: 0,
+        }
+    }
+}
+
 impl<T> Iterator for IntoIter<T> {
     type Item = T;
 
@@ -197,20 +778,80 @@ impl<T> Iterator for IntoIter<T> {
                     self.len -= 1;
                     return front;
                 }
+                // Free the exhausted bucket right away.
+                self.front_iter = None;
             }
             match self.buckets.next() {
                 None => {
-                    self.len -= 1;
-                    return self.back_iter.as_mut()?.next();
+                    let back = self.back_iter.as_mut()?.next();
+                    match back {
+                        Some(_) => self.len -= 1,
+                        None => self.back_iter = None,
+                    }
+                    return back;
                 }
                 Some(bucket) => self.front_iter = Some(bucket.into_iter()),
             }
         }
     }
 
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let remaining = self.len.saturating_sub(n).saturating_sub(1);
+        let mut n = n;
+        if let Some(ref mut front_iter) = self.front_iter {
+            let len_front = front_iter.len();
+            if n < len_front {
+                self.len = remaining;
+                return front_iter.nth(n);
+            }
+            n -= len_front;
+            self.front_iter = None;
+        }
+        // Jump to the bucket of the element without visiting the buckets in between.
+        match self.buckets.skip_front(n) {
+            Ok((bucket, entry)) => {
+                let mut front_iter = bucket.into_iter();
+                let front = front_iter.nth(entry);
+                self.front_iter = Some(front_iter);
+                self.len = remaining;
+                front
+            }
+            Err(n) => {
+                self.len = remaining;
+                self.back_iter.as_mut()?.nth(n)
+            }
+        }
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.len(), Some(self.len()))
     }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        // Fold over the contiguous entries of every bucket in isolation.
+        let mut acc = init;
+        if let Some(front_iter) = self.front_iter {
+            acc = front_iter.fold(acc, &mut f);
+        }
+        for bucket in self.buckets {
+            acc = bucket.into_iter().fold(acc, &mut f);
+        }
+        if let Some(back_iter) = self.back_iter {
+            acc = back_iter.fold(acc, &mut f);
+        }
+        acc
+    }
 }
 
 impl<T> DoubleEndedIterator for IntoIter<T> {
@@ -221,16 +862,68 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
                     self.len -= 1;
                     return back;
                 }
+                // Free the exhausted bucket right away.
+                self.back_iter = None;
             }
             match self.buckets.next_back() {
                 None => {
-                    self.len -= 1;
-                    return self.front_iter.as_mut()?.next_back();
+                    let front = self.front_iter.as_mut()?.next_back();
+                    match front {
+                        Some(_) => self.len -= 1,
+                        None => self.front_iter = None,
+                    }
+                    return front;
                 }
                 Some(bucket) => self.back_iter = Some(bucket.into_iter()),
             }
         }
     }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let remaining = self.len.saturating_sub(n).saturating_sub(1);
+        let mut n = n;
+        if let Some(ref mut back_iter) = self.back_iter {
+            let len_back = back_iter.len();
+            if n < len_back {
+                self.len = remaining;
+                return back_iter.nth_back(n);
+            }
+            n -= len_back;
+            self.back_iter = None;
+        }
+        // Jump to the bucket of the element without visiting the buckets in between.
+        match self.buckets.skip_back(n) {
+            Ok((bucket, entry)) => {
+                let mut back_iter = bucket.into_iter();
+                let back = back_iter.nth_back(entry);
+                self.back_iter = Some(back_iter);
+                self.len = remaining;
+                back
+            }
+            Err(n) => {
+                self.len = remaining;
+                self.front_iter.as_mut()?.nth_back(n)
+            }
+        }
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        // Fold over the contiguous entries of every bucket in isolation.
+        let mut acc = init;
+        if let Some(back_iter) = self.back_iter {
+            acc = back_iter.rfold(acc, &mut f);
+        }
+        for bucket in self.buckets.rev() {
+            acc = bucket.into_iter().rfold(acc, &mut f);
+        }
+        if let Some(front_iter) = self.front_iter {
+            acc = front_iter.rfold(acc, &mut f);
+        }
+        acc
+    }
 }
 
 impl<T> ExactSizeIterator for IntoIter<T> {
@@ -238,3 +931,778 @@ impl<T> ExactSizeIterator for IntoIter<T> {
         self.len
     }
 }
+
+impl<T> core::iter::FusedIterator for IntoIter<T> {}
+
+/// The position of an element within a bucket vector.
+///
+/// Besides the global index of the element this also carries the index of
+/// the bucket that stores the element and the element's index within it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// The global index of the element.
+    index: usize,
+    /// The index of the bucket storing the element.
+    bucket_index: usize,
+    /// The index of the element within its bucket.
+    entry_index: usize,
+}
+
+impl Position {
+    /// Creates a new position from the given global and bucket indices.
+    pub(crate) fn new(index: usize, bucket_index: usize, entry_index: usize) -> Self {
+        Self {
+            index,
+            bucket_index,
+            entry_index,
+        }
+    }
+
+    /// Returns the global index of the element.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the index of the bucket storing the element.
+    pub fn bucket_index(&self) -> usize {
+        self.bucket_index
+    }
+
+    /// Returns the index of the element within its bucket.
+    pub fn entry_index(&self) -> usize {
+        self.entry_index
+    }
+}
+
+/// An iterator over the entries of a single bucket that keeps track of their positions.
+#[derive(Debug, Clone)]
+struct PositionedEntries<I> {
+    /// The index of the iterated bucket.
+    bucket_index: usize,
+    /// The global index of the first element of the iterated bucket.
+    offset: usize,
+    /// The enumerated entries of the iterated bucket.
+    entries: core::iter::Enumerate<I>,
+}
+
+impl<I> PositionedEntries<I>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+{
+    /// Creates a new positioned iterator over the entries of a bucket.
+    fn new(bucket_index: usize, offset: usize, entries: I) -> Self {
+        Self {
+            bucket_index,
+            offset,
+            entries: entries.enumerate(),
+        }
+    }
+
+    /// Attaches the position to the given enumerated entry.
+    fn with_position(&self, (entry_index, entry): (usize, I::Item)) -> (Position, I::Item) {
+        let index = self.offset + entry_index;
+        (Position::new(index, self.bucket_index, entry_index), entry)
+    }
+
+    fn next(&mut self) -> Option<(Position, I::Item)> {
+        let entry = self.entries.next()?;
+        Some(self.with_position(entry))
+    }
+
+    fn next_back(&mut self) -> Option<(Position, I::Item)> {
+        let entry = self.entries.next_back()?;
+        Some(self.with_position(entry))
+    }
+}
+
+/// An iterator yielding shared references to the elements of a bucket vector
+/// together with their positions.
+#[derive(Debug, Clone)]
+pub struct IterIndexed<'a, T> {
+    /// Enumerated buckets iterator.
+    buckets: core::iter::Enumerate<core::slice::Iter<'a, Bucket<T>>>,
+    /// Front iterator for `next`.
+    front_iter: Option<PositionedEntries<core::slice::Iter<'a, T>>>,
+    /// Back iterator for `next_back`.
+    back_iter: Option<PositionedEntries<core::slice::Iter<'a, T>>>,
+    /// The global index of the first element of the next bucket from the front.
+    front_offset: usize,
+    /// The global index one past the last element of the next bucket from the back.
+    back_offset: usize,
+    /// Number of elements that are to be yielded by the iterator.
+    len: usize,
+}
+
+impl<'a, T> IterIndexed<'a, T> {
+    /// Creates a new indexed iterator over the bucket vector.
+    pub(crate) fn new<C>(vec: &'a BucketVec<T, C>) -> Self {
+        Self {
+            buckets: vec.buckets.iter().enumerate(),
+            front_iter: None,
+            back_iter: None,
+            front_offset: 0,
+            back_offset: vec.len(),
+            len: vec.len(),
+        }
+    }
+}
+
+impl<'a, T> Default for IterIndexed<'a, T> {
+    fn default() -> Self {
+        Self {
+            buckets: Default::default(),
+            front_iter: None,
+            back_iter: None,
+            front_offset: 0,
+            back_offset: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterIndexed<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ref mut front_iter) = self.front_iter {
+                if let front @ Some(_) = front_iter.next() {
+                    self.len -= 1;
+                    return front;
+                }
+            }
+            match self.buckets.next() {
+                None => {
+                    let back = self.back_iter.as_mut()?.next();
+                    if back.is_some() {
+                        self.len -= 1;
+                    }
+                    return back;
+                }
+                Some((bucket_index, bucket)) => {
+                    let offset = self.front_offset;
+                    self.front_offset += bucket.len();
+                    self.front_iter =
+                        Some(PositionedEntries::new(bucket_index, offset, bucket.iter()));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterIndexed<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ref mut back_iter) = self.back_iter {
+                if let back @ Some(_) = back_iter.next_back() {
+                    self.len -= 1;
+                    return back;
+                }
+            }
+            match self.buckets.next_back() {
+                None => {
+                    let front = self.front_iter.as_mut()?.next_back();
+                    if front.is_some() {
+                        self.len -= 1;
+                    }
+                    return front;
+                }
+                Some((bucket_index, bucket)) => {
+                    self.back_offset -= bucket.len();
+                    let offset = self.back_offset;
+                    self.back_iter =
+                        Some(PositionedEntries::new(bucket_index, offset, bucket.iter()));
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterIndexed<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> core::iter::FusedIterator for IterIndexed<'a, T> {}
+
+/// An iterator yielding exclusive references to the elements of a bucket vector
+/// together with their positions.
+#[derive(Debug)]
+pub struct IterIndexedMut<'a, T> {
+    /// Enumerated buckets iterator.
+    buckets: core::iter::Enumerate<core::slice::IterMut<'a, Bucket<T>>>,
+    /// Front iterator for `next`.
+    front_iter: Option<PositionedEntries<core::slice::IterMut<'a, T>>>,
+    /// Back iterator for `next_back`.
+    back_iter: Option<PositionedEntries<core::slice::IterMut<'a, T>>>,
+    /// The global index of the first element of the next bucket from the front.
+    front_offset: usize,
+    /// The global index one past the last element of the next bucket from the back.
+    back_offset: usize,
+    /// Number of elements that are to be yielded by the iterator.
+    len: usize,
+}
+
+impl<'a, T> IterIndexedMut<'a, T> {
+    /// Creates a new indexed iterator over the bucket vector.
+    pub(crate) fn new<C>(vec: &'a mut BucketVec<T, C>) -> Self {
+        let len = vec.len();
+        Self {
+            buckets: vec.buckets.iter_mut().enumerate(),
+            front_iter: None,
+            back_iter: None,
+            front_offset: 0,
+            back_offset: len,
+            len,
+        }
+    }
+}
+
+impl<'a, T> Default for IterIndexedMut<'a, T> {
+    fn default() -> Self {
+        Self {
+            buckets: Default::default(),
+            front_iter: None,
+            back_iter: None,
+            front_offset: 0,
+            back_offset: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterIndexedMut<'a, T> {
+    type Item = (Position, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ref mut front_iter) = self.front_iter {
+                if let front @ Some(_) = front_iter.next() {
+                    self.len -= 1;
+                    return front;
+                }
+            }
+            match self.buckets.next() {
+                None => {
+                    let back = self.back_iter.as_mut()?.next();
+                    if back.is_some() {
+                        self.len -= 1;
+                    }
+                    return back;
+                }
+                Some((bucket_index, bucket)) => {
+                    let offset = self.front_offset;
+                    self.front_offset += bucket.len();
+                    self.front_iter = Some(PositionedEntries::new(
+                        bucket_index,
+                        offset,
+                        bucket.iter_mut(),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterIndexedMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ref mut back_iter) = self.back_iter {
+                if let back @ Some(_) = back_iter.next_back() {
+                    self.len -= 1;
+                    return back;
+                }
+            }
+            match self.buckets.next_back() {
+                None => {
+                    let front = self.front_iter.as_mut()?.next_back();
+                    if front.is_some() {
+                        self.len -= 1;
+                    }
+                    return front;
+                }
+                Some((bucket_index, bucket)) => {
+                    self.back_offset -= bucket.len();
+                    let offset = self.back_offset;
+                    self.back_iter = Some(PositionedEntries::new(
+                        bucket_index,
+                        offset,
+                        bucket.iter_mut(),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterIndexedMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> core::iter::FusedIterator for IterIndexedMut<'a, T> {}
+
+/// An iterator over runs of consecutive elements of a bucket vector for
+/// which a predicate holds pairwise.
+///
+/// Every run is yielded as an iterator over its elements since runs may
+/// span multiple buckets.
+pub struct ChunkBy<'a, T, F> {
+    /// The buckets of the bucket vector.
+    buckets: &'a [Bucket<T>],
+    /// The index of the first element of every bucket.
+    offsets: &'a [usize],
+    /// The bucket index and entry index of the first element of the next run.
+    position: (usize, usize),
+    /// The number of elements that are yet to be yielded within runs.
+    remaining: usize,
+    /// Returns `true` if both consecutive elements belong to the same run.
+    pred: F,
+}
+
+impl<'a, T, F> ChunkBy<'a, T, F> {
+    /// Creates a new iterator over the runs of the bucket vector.
+    pub(crate) fn new<C>(vec: &'a BucketVec<T, C>, pred: F) -> Self {
+        Self {
+            buckets: &vec.buckets,
+            offsets: &vec.offsets,
+            position: (0, 0),
+            remaining: vec.len(),
+            pred,
+        }
+    }
+
+    /// Returns the position following the given position.
+    fn advance(&self, (bucket, entry): (usize, usize)) -> (usize, usize) {
+        if entry + 1 < self.buckets[bucket].len() {
+            (bucket, entry + 1)
+        } else {
+            (bucket + 1, 0)
+        }
+    }
+}
+
+impl<'a, T, F> core::fmt::Debug for ChunkBy<'a, T, F>
+where
+    T: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("ChunkBy")
+            .field("buckets", &self.buckets)
+            .field("position", &self.position)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+impl<'a, T, F> Iterator for ChunkBy<'a, T, F>
+where
+    F: FnMut(&'a T, &'a T) -> bool,
+{
+    type Item = Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let start = self.position;
+        let (mut bucket, mut entry) = start;
+        let mut len = 1;
+        while len < self.remaining {
+            let previous = &self.buckets[bucket][entry];
+            let (next_bucket, next_entry) = self.advance((bucket, entry));
+            if !(self.pred)(previous, &self.buckets[next_bucket][next_entry]) {
+                break;
+            }
+            bucket = next_bucket;
+            entry = next_entry;
+            len += 1;
+        }
+        let end = self.advance((bucket, entry));
+        self.position = end;
+        self.remaining -= len;
+        Some(Iter::from_range(
+            self.buckets,
+            self.offsets,
+            start,
+            end,
+            len,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (core::cmp::min(self.remaining, 1), Some(self.remaining))
+    }
+}
+
+impl<'a, T, F> core::iter::FusedIterator for ChunkBy<'a, T, F> where F: FnMut(&'a T, &'a T) -> bool {}
+
+/// An iterator over chunks of `chunk_size` consecutive elements of a bucket
+/// vector.
+///
+/// Chunks may span multiple buckets which is why every chunk is yielded as
+/// an iterator over its elements. The last chunk may be shorter.
+#[derive(Debug, Clone)]
+pub struct Chunks<'a, T> {
+    /// The buckets of the bucket vector.
+    buckets: &'a [Bucket<T>],
+    /// The index of the first element of every bucket.
+    offsets: &'a [usize],
+    /// The bucket index and entry index of the first element of the next chunk.
+    position: (usize, usize),
+    /// The number of elements that are yet to be yielded within chunks.
+    remaining: usize,
+    /// The maximum number of elements per chunk.
+    chunk_size: usize,
+}
+
+impl<'a, T> Chunks<'a, T> {
+    /// Creates a new iterator over the chunks of the bucket vector.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_size` is zero.
+    pub(crate) fn new<C>(vec: &'a BucketVec<T, C>, chunk_size: usize) -> Self {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        Self {
+            buckets: &vec.buckets,
+            offsets: &vec.offsets,
+            position: (0, 0),
+            remaining: vec.len(),
+            chunk_size,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let len = core::cmp::min(self.chunk_size, self.remaining);
+        let start = self.position;
+        let (mut bucket, mut entry) = start;
+        let mut missing = len;
+        while missing > 0 {
+            let available = self.buckets[bucket].len() - entry;
+            if missing < available {
+                entry += missing;
+                break;
+            }
+            missing -= available;
+            bucket += 1;
+            entry = 0;
+        }
+        self.position = (bucket, entry);
+        self.remaining -= len;
+        Some(Iter::from_range(
+            self.buckets,
+            self.offsets,
+            start,
+            self.position,
+            len,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining.div_ceil(self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Chunks<'a, T> {}
+
+impl<'a, T> core::iter::FusedIterator for Chunks<'a, T> {}
+
+/// An iterator over overlapping windows of `N` consecutive elements of a
+/// bucket vector.
+///
+/// Windows may span multiple buckets which is why every window is yielded
+/// as an array of references.
+#[derive(Debug, Clone)]
+pub struct ArrayWindows<'a, T, const N: usize> {
+    /// The elements following the current window.
+    rest: Iter<'a, T>,
+    /// The most recently yielded window if any.
+    window: Option<[&'a T; N]>,
+}
+
+impl<'a, T, const N: usize> ArrayWindows<'a, T, N> {
+    /// Creates a new iterator over the windows of the bucket vector.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero.
+    pub(crate) fn new<C>(vec: &'a BucketVec<T, C>) -> Self {
+        assert!(N != 0, "window size must be non-zero");
+        Self {
+            rest: vec.iter(),
+            window: None,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for ArrayWindows<'a, T, N> {
+    type Item = [&'a T; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.window {
+            Some(window) => {
+                let next = self.rest.next()?;
+                window.rotate_left(1);
+                window[N - 1] = next;
+                Some(*window)
+            }
+            None => {
+                if self.rest.len() < N {
+                    return None;
+                }
+                let rest = &mut self.rest;
+                let window = core::array::from_fn(|_| {
+                    rest.next().expect("encountered missing element for window")
+                });
+                self.window = Some(window);
+                Some(window)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.window {
+            Some(_) => self.rest.len(),
+            None => (self.rest.len() + 1).saturating_sub(N),
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for ArrayWindows<'a, T, N> {}
+
+impl<'a, T, const N: usize> core::iter::FusedIterator for ArrayWindows<'a, T, N> {}
+
+/// An iterator yielding shared references to every `step`th element of a
+/// bucket vector starting at a given index.
+///
+/// Advances through the index math of the bucket vector instead of visiting
+/// the skipped elements.
+#[derive(Debug)]
+pub struct IterStepBy<'a, T, C> {
+    /// The iterated bucket vector.
+    vec: &'a BucketVec<T, C>,
+    /// The bucket index and entry index of the next element to yield.
+    position: (usize, usize),
+    /// The distance between two yielded elements.
+    step: usize,
+    /// The number of elements that are yet to be yielded.
+    remaining: usize,
+}
+
+impl<'a, T, C> Clone for IterStepBy<'a, T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            vec: self.vec,
+            position: self.position,
+            step: self.step,
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, T, C> IterStepBy<'a, T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Creates a new iterator over every `step`th element starting at `start`.
+    ///
+    /// # Panics
+    ///
+    /// If `step` is zero.
+    pub(crate) fn new(vec: &'a BucketVec<T, C>, start: usize, step: usize) -> Self {
+        assert!(step != 0, "step must be non-zero");
+        let (position, remaining) = match vec.bucket_entry_indices(start) {
+            Some(position) => (position, (vec.len() - start - 1) / step + 1),
+            None => ((0, 0), 0),
+        };
+        Self {
+            vec,
+            position,
+            step,
+            remaining,
+        }
+    }
+
+    /// Returns the position of the element `distance` elements after the
+    /// element at the given position.
+    fn advance(&self, (x, y): (usize, usize), distance: usize) -> (usize, usize) {
+        if distance < self.vec.buckets[x].len() - y {
+            // Fast path: The element lies within the same bucket.
+            return (x, y + distance);
+        }
+        self.vec
+            .bucket_entry_indices(self.vec.offsets[x] + y + distance)
+            .expect("encountered out of bounds step")
+    }
+
+    /// Returns a shared reference to the element at the given position.
+    fn get(&self, (x, y): (usize, usize)) -> &'a T {
+        &self.vec.buckets[x][y]
+    }
+}
+
+impl<'a, T, C> Iterator for IterStepBy<'a, T, C>
+where
+    C: BucketVecConfig,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let position = self.position;
+        self.remaining -= 1;
+        if self.remaining != 0 {
+            self.position = self.advance(position, self.step);
+        }
+        Some(self.get(position))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+        // Cannot overflow since the skipped elements are in bounds.
+        self.position = self.advance(self.position, n * self.step);
+        self.remaining -= n;
+        self.next()
+    }
+}
+
+impl<'a, T, C> DoubleEndedIterator for IterStepBy<'a, T, C>
+where
+    C: BucketVecConfig,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let back = self.advance(self.position, self.remaining * self.step);
+        Some(self.get(back))
+    }
+}
+
+impl<'a, T, C> ExactSizeIterator for IterStepBy<'a, T, C> where C: BucketVecConfig {}
+
+impl<'a, T, C> core::iter::FusedIterator for IterStepBy<'a, T, C> where C: BucketVecConfig {}
+
+/// The slices of the elements of every bucket of a bucket vector.
+type BucketSlices<'a, T> =
+    core::iter::Map<core::slice::Iter<'a, Bucket<T>>, fn(&'a Bucket<T>) -> &'a [T]>;
+
+/// The exclusive slices of the elements of every bucket of a bucket vector.
+type BucketSlicesMut<'a, T> =
+    core::iter::Map<core::slice::IterMut<'a, Bucket<T>>, fn(&'a mut Bucket<T>) -> &'a mut [T]>;
+
+/// An iterator yielding pairs of equally long slices of the elements of two
+/// bucket vectors with the same configuration.
+///
+/// Since both bucket vectors share their bucket layout every pair consists
+/// of the elements of buckets with the same index.
+/// Stops once either bucket vector is exhausted.
+#[derive(Debug, Clone)]
+pub struct ZipBuckets<'a, T, U> {
+    /// The pairs of slices of the buckets of both bucket vectors.
+    chunks: ChunkPairs<BucketSlices<'a, T>, BucketSlices<'a, U>>,
+}
+
+impl<'a, T, U> ZipBuckets<'a, T, U> {
+    /// Creates a new iterator over the paired buckets of both bucket vectors.
+    pub(crate) fn new<C>(lhs: &'a BucketVec<T, C>, rhs: &'a BucketVec<U, C>) -> Self {
+        // Buckets of zero-sized elements are laid out differently which is
+        // why the slices are split whenever their lengths differ.
+        Self {
+            chunks: ChunkPairs::new(
+                lhs.buckets.iter().map(Bucket::as_slice as _),
+                rhs.buckets.iter().map(Bucket::as_slice as _),
+            ),
+        }
+    }
+}
+
+impl<'a, T, U> Iterator for ZipBuckets<'a, T, U> {
+    type Item = (&'a [T], &'a [U]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next()
+    }
+}
+
+impl<'a, T, U> core::iter::FusedIterator for ZipBuckets<'a, T, U> {}
+
+/// An iterator yielding pairs of equally long exclusive slices of the
+/// elements of two bucket vectors with the same configuration.
+///
+/// Since both bucket vectors share their bucket layout every pair consists
+/// of the elements of buckets with the same index.
+/// Stops once either bucket vector is exhausted.
+#[derive(Debug)]
+pub struct ZipBucketsMut<'a, T, U> {
+    /// The pairs of exclusive slices of the buckets of both bucket vectors.
+    chunks: ChunkPairs<BucketSlicesMut<'a, T>, BucketSlicesMut<'a, U>>,
+}
+
+impl<'a, T, U> ZipBucketsMut<'a, T, U> {
+    /// Creates a new iterator over the paired buckets of both bucket vectors.
+    pub(crate) fn new<C>(lhs: &'a mut BucketVec<T, C>, rhs: &'a mut BucketVec<U, C>) -> Self {
+        Self {
+            chunks: ChunkPairs::new(
+                lhs.buckets.iter_mut().map(Bucket::as_mut_slice as _),
+                rhs.buckets.iter_mut().map(Bucket::as_mut_slice as _),
+            ),
+        }
+    }
+}
+
+impl<'a, T, U> Iterator for ZipBucketsMut<'a, T, U> {
+    type Item = (&'a mut [T], &'a mut [U]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next()
+    }
+}
+
+impl<'a, T, U> core::iter::FusedIterator for ZipBucketsMut<'a, T, U> {}
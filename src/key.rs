@@ -0,0 +1,198 @@
+//! Typed keys referring to the elements of a bucket vector.
+
+use super::{BucketVec, BucketVecConfig};
+use core::{cmp::Ordering, convert::TryFrom, fmt, hash, marker::PhantomData};
+
+mod private {
+    /// Seals the implementation of `KeyIndex`.
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type that stores the index of a [`Key`].
+///
+/// Implemented for `usize`, `u32` and `u16`. Narrower index types shrink
+/// keys and the tables that store them at the cost of limiting the indices
+/// they are able to refer to.
+pub trait KeyIndex: Copy + Ord + hash::Hash + fmt::Debug + private::Sealed {
+    /// Converts the index into the key index type if it fits.
+    fn from_index(index: usize) -> Option<Self>;
+
+    /// Converts the key index back into an index.
+    fn into_index(self) -> usize;
+}
+
+macro_rules! impl_key_index_for {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl private::Sealed for $ty {}
+
+            impl KeyIndex for $ty {
+                #[inline]
+                fn from_index(index: usize) -> Option<Self> {
+                    <$ty>::try_from(index).ok()
+                }
+
+                #[inline]
+                fn into_index(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+impl_key_index_for!(usize, u32, u16);
+
+/// A typed index referring to an element of type `T` within a bucket vector.
+///
+/// Returned by [`BucketVec::push_key`] and accepted by [`BucketVec::get_key`]
+/// and [`BucketVec::get_key_mut`] so that indices into bucket vectors of
+/// different element types cannot be mixed up.
+///
+/// The index is stored as `I` which allows for compact keys, e.g.
+/// `Key<T, u32>` as returned by [`BucketVec::try_push_key`].
+pub struct Key<T, I = usize> {
+    /// The index of the referred to element.
+    index: I,
+    /// Marker for the type of the referred to element.
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Key<T> {
+    /// Creates a new key from the given index.
+    pub fn new(index: usize) -> Self {
+        Self {
+            index,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, I> Key<T, I>
+where
+    I: KeyIndex,
+{
+    /// Creates a new key from the given index if it fits into the key index type.
+    pub fn try_new(index: usize) -> Option<Self> {
+        I::from_index(index).map(|index| Self {
+            index,
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns the index of the referred to element.
+    pub fn index(&self) -> usize {
+        self.index.into_index()
+    }
+}
+
+impl<T, I> fmt::Debug for Key<T, I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Key").field(&self.index).finish()
+    }
+}
+
+impl<T, I> Copy for Key<T, I> where I: Copy {}
+
+impl<T, I> Clone for Key<T, I>
+where
+    I: Copy,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, I> PartialEq for Key<T, I>
+where
+    I: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T, I> Eq for Key<T, I> where I: Eq {}
+
+impl<T, I> PartialOrd for Key<T, I>
+where
+    I: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, I> Ord for Key<T, I>
+where
+    I: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<T, I> hash::Hash for Key<T, I>
+where
+    I: hash::Hash,
+{
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state)
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Pushes a new element onto the bucket vector and returns its key.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push_key(&mut self, new_value: T) -> Key<T> {
+        let key = Key::new(self.len());
+        self.push(new_value);
+        key
+    }
+
+    /// Pushes a new element onto the bucket vector and returns its compact key.
+    ///
+    /// Returns the element back if its index does not fit into the key index type.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn try_push_key<I>(&mut self, new_value: T) -> Result<Key<T, I>, T>
+    where
+        I: KeyIndex,
+    {
+        match Key::try_new(self.len()) {
+            Some(key) => {
+                self.push(new_value);
+                Ok(key)
+            }
+            None => Err(new_value),
+        }
+    }
+
+    /// Returns a shared reference to the element referred to by the key if any.
+    pub fn get_key<I>(&self, key: Key<T, I>) -> Option<&T>
+    where
+        I: KeyIndex,
+    {
+        self.get(key.index())
+    }
+
+    /// Returns an exclusive reference to the element referred to by the key if any.
+    pub fn get_key_mut<I>(&mut self, key: Key<T, I>) -> Option<&mut T>
+    where
+        I: KeyIndex,
+    {
+        self.get_mut(key.index())
+    }
+}
@@ -0,0 +1,63 @@
+//! The index math of bucket vectors for use by external data structures.
+
+use super::{config, BucketVecConfig, DefaultConfig};
+use core::marker::PhantomData;
+
+/// Computes the bucket layout of bucket vectors with the configuration `C`.
+///
+/// Allows data structures built on top of bucket vectors to locate elements
+/// the same way bucket vectors do.
+///
+/// # Note
+///
+/// Bucket vectors of zero-sized elements store all elements within their
+/// first bucket and therefore do not follow this layout.
+#[derive(Debug)]
+pub struct Layout<C = DefaultConfig> {
+    /// Marker for the configuration of the layout.
+    marker: PhantomData<fn() -> C>,
+}
+
+impl<C> Layout<C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns the capacity of the indexed bucket.
+    ///
+    /// # Panics
+    ///
+    /// If the total capacity up to the indexed bucket overflows `usize`.
+    pub fn bucket_capacity(index: usize) -> usize {
+        config::bucket_capacity::<C>(index)
+    }
+
+    /// Returns the total capacity of all buckets preceding the bucket indexed
+    /// by `index` which is also the index of its first element.
+    ///
+    /// # Panics
+    ///
+    /// If the total capacity overflows `usize`.
+    pub fn total_capacity(index: usize) -> usize {
+        config::total_capacity::<C>(index)
+    }
+
+    /// Returns the total capacity of all buckets preceding the bucket indexed
+    /// by `index` which is also the index of its first element.
+    ///
+    /// Returns `None` if the total capacity overflows `usize`.
+    pub fn checked_total_capacity(index: usize) -> Option<usize> {
+        config::checked_total_capacity::<C>(index)
+    }
+
+    /// Returns the largest capacity of any bucket or `None` if bucket capacities
+    /// grow without bounds.
+    pub fn max_bucket_capacity() -> Option<usize> {
+        config::max_bucket_capacity::<C>()
+    }
+
+    /// Returns the bucket index and its internal entry index for the given
+    /// bucket vector index into an element.
+    pub fn bucket_entry_indices(index: usize) -> (usize, usize) {
+        config::bucket_entry_indices::<C>(index)
+    }
+}
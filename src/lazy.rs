@@ -0,0 +1,99 @@
+//! Bucket vector of lazily initialized slots.
+
+use super::{BucketVecConfig, DefaultConfig, FrozenBucketVec};
+use core::cell::OnceCell;
+
+/// A bucket vector of slots that are initialized upon their first access.
+///
+/// Accessing a slot grows the lazy bucket vector with empty slots up to it
+/// through a shared reference. Since slots never move, references to
+/// initialized values remain valid while more slots are accessed. This
+/// suits memoization tables indexed by id.
+///
+/// # Note
+///
+/// Locating a slot takes time logarithmic in the number of buckets.
+#[derive(Debug)]
+pub struct LazyBucketVec<T, C = DefaultConfig> {
+    /// The slots, initialized or not.
+    slots: FrozenBucketVec<OnceCell<T>, C>,
+}
+
+impl<T, C> Default for LazyBucketVec<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> LazyBucketVec<T, C> {
+    /// Creates a new empty lazy bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            slots: FrozenBucketVec::new(),
+        }
+    }
+
+    /// Returns the number of slots of the lazy bucket vector, initialized
+    /// or not.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if the lazy bucket vector has no slots.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+}
+
+impl<T, C> LazyBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the value of the slot at the given
+    /// index if it has been initialized.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.slots.get(index)?.get()
+    }
+
+    /// Returns an exclusive reference to the value of the slot at the given
+    /// index if it has been initialized.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.slots.get_mut(index)?.get_mut()
+    }
+
+    /// Returns a shared reference to the value of the slot at the given
+    /// index and initializes it with `f` first if necessary.
+    ///
+    /// Grows the lazy bucket vector with empty slots up to the index.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the lazy bucket vector.
+    ///
+    /// # Panics
+    ///
+    /// If `f` reentrantly initializes the same slot.
+    pub fn get_or_init_at<F>(&self, index: usize, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        while self.slots.len() <= index {
+            self.slots.push(OnceCell::new());
+        }
+        self.slots
+            .get(index)
+            .expect("encountered missing slot within bounds")
+            .get_or_init(f)
+    }
+
+    /// Returns an iterator over the values of all slots or `None` for slots
+    /// that have not been initialized.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Option<&T>> {
+        self.slots.iter().map(OnceCell::get)
+    }
+}
@@ -27,8 +27,18 @@
 //! actively decides that they want or need pinned references into another data
 //! structure.
 //!
-//! For the same reasons as stated above the `BucketVec` does not allow to remove
-//! or swap elements.
+//! Operations that only add elements, such as pushing, extending or
+//! reserving, never move any element. Removing elements from the end, e.g.
+//! via `pop_while` or `truncate_while`, only affects the removed elements.
+//!
+//! Some operations deliberately move elements and thus invalidate previously
+//! obtained references, pointers or indices of the moved elements:
+//!
+//! - `swap_remove_last` moves the last element into the slot of the removed one.
+//! - `insert_shift` and `remove_shift` move the elements at and after the given index.
+//! - `retain_invalidating` and `dedup` pack the retained elements to the front.
+//! - `reverse` and the `sort_*` methods swap values between slots that themselves
+//!   stay in place, so pointers stay valid but afterwards point to different values.
 //!
 //! ## Example
 //!
@@ -61,29 +71,210 @@
 //! Where `15 x _` denotes 15 consecutive vacant entries.
 
 #![cfg_attr(not(feature = "std"), no_std)]
+#![forbid(unsafe_code)]
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 
-#[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+// Allows the derive and attribute macros to refer to `::bucket_vec` from within this crate.
+#[cfg(feature = "derive")]
+extern crate self as bucket_vec;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
 
+#[cfg(feature = "alloc")]
+mod any;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "borsh")]
+mod borsh;
+#[cfg(feature = "alloc")]
+mod bounded;
+#[cfg(feature = "alloc")]
 mod bucket;
+#[cfg(feature = "bumpalo")]
+mod bump;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+#[cfg(feature = "alloc")]
+mod bytes;
+#[cfg(feature = "alloc")]
+mod capped;
+#[cfg(feature = "alloc")]
+mod cells;
+#[cfg(feature = "alloc")]
+mod checkpoint;
+#[cfg(feature = "std")]
+mod concurrent;
+#[cfg(feature = "alloc")]
 mod config;
+#[cfg(feature = "alloc")]
+mod cursor;
+#[cfg(feature = "defmt")]
+mod defmt;
+#[cfg(feature = "alloc")]
+mod deque;
+#[cfg(feature = "alloc")]
+mod digest;
+#[cfg(feature = "alloc")]
+mod dynamic;
+#[cfg(feature = "alloc")]
+mod fallible;
+mod fixed;
+#[cfg(feature = "alloc")]
+mod frozen;
+#[cfg(feature = "alloc")]
+mod generational;
+#[cfg(feature = "alloc")]
+mod heap;
+#[cfg(feature = "alloc")]
+mod index;
+#[cfg(feature = "std")]
+mod indexed;
+#[cfg(feature = "ink")]
+mod ink;
+#[cfg(feature = "std")]
+mod intern;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "alloc")]
 mod iter;
+#[cfg(feature = "alloc")]
+mod key;
+#[cfg(feature = "alloc")]
+mod layout;
+#[cfg(feature = "alloc")]
+mod lazy;
+#[cfg(feature = "persistence")]
+mod log;
+#[cfg(feature = "std")]
+mod map;
+#[cfg(feature = "alloc")]
 mod math;
+#[cfg(feature = "alloc")]
+mod ordered;
+#[cfg(feature = "alloc")]
+mod persistent;
+#[cfg(feature = "alloc")]
+mod pool;
+#[cfg(feature = "proptest")]
+mod proptest;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "alloc")]
+mod raw;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "bevy_reflect")]
+mod reflect;
+#[cfg(feature = "scale-3")]
 mod scale;
+#[cfg(feature = "scale-1")]
+mod scale1;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "alloc")]
+mod slot;
+#[cfg(feature = "debug-stability-checks")]
+mod stability;
+#[cfg(feature = "alloc")]
+mod stable;
+#[cfg(feature = "alloc")]
+mod stats;
+#[cfg(feature = "alloc")]
+mod storage;
+#[cfg(feature = "alloc")]
+mod string;
+#[cfg(feature = "alloc")]
+mod tracked;
+#[cfg(any(feature = "serde", feature = "scale-3", feature = "bincode"))]
+mod with_layout;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests;
 
-use self::bucket::Bucket;
-use self::math::FloatExt;
+pub use self::fixed::{StaticBucketVec, StaticIter, StaticIterMut};
+
+#[cfg(feature = "alloc")]
 pub use self::{
-    config::{BucketVecConfig, DefaultConfig},
-    iter::{IntoIter, Iter, IterMut},
+    any::{AnyKey, BucketAny},
+    bounded::BoundedBucketVec,
+    capped::CappedBucketVec,
+    cells::Cells,
+    checkpoint::Checkpoint,
+    config::{
+        BucketVecConfig, CapacityRounding, CappedDoublingConfig, ConstConfig, DefaultConfig,
+        EqualSizeConfig, FibonacciConfig, TinyStartConfig,
+    },
+    cursor::GetCursor,
+    deque::{BucketVecDeque, DequeIter},
+    digest::DigestBucketVec,
+    dynamic::DynBucketVec,
+    fallible::{FallibleBucketVec, ReserveError},
+    frozen::{FrozenBucketVec, FrozenIter},
+    generational::{GenBucketVec, GenKey},
+    heap::BucketHeap,
+    index::IndexError,
+    iter::{
+        ArrayWindows, ChunkBy, Chunks, IntoIter, Iter, IterIndexed, IterIndexedMut, IterMut,
+        IterStepBy, Position, ZipBuckets, ZipBucketsMut,
+    },
+    key::{Key, KeyIndex},
+    layout::Layout,
+    lazy::LazyBucketVec,
+    ordered::{OrderedIndex, OrderedRange},
+    persistent::PersistentBucketVec,
+    pool::BucketPool,
+    raw::LayoutError,
+    shared::SharedBucketVec,
+    slot::{SlotBucketVec, SlotIter, SlotIterMut},
+    stable::StableVec,
+    stats::{BucketStats, MemoryStats},
+    string::{BucketString, StrSpan},
+    tracked::TrackedBucketVec,
+};
+#[cfg(feature = "alloc")]
+use self::{
+    bucket::Bucket,
+    storage::{BucketSchedule, BucketStorage, Parts, PartsMut},
 };
-use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use core::{
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    ptr::NonNull,
+};
+
+#[cfg(feature = "bumpalo")]
+pub use self::bump::{BumpBucketVec, BumpIter};
+#[cfg(feature = "std")]
+pub use self::concurrent::{ConcurrentBucketVec, ConcurrentIter};
+#[cfg(feature = "std")]
+pub use self::indexed::{HashIndex, IndexedBucketVec};
+#[cfg(feature = "std")]
+pub use self::intern::InternBucketVec;
+#[cfg(feature = "std")]
+pub use self::io::BucketCursor;
+#[cfg(feature = "persistence")]
+pub use self::log::{LoggedBucketVec, TornTail};
+#[cfg(feature = "std")]
+pub use self::map::{BucketMap, BucketMapIter};
+#[cfg(feature = "proptest")]
+pub use self::proptest::bucket_vec_strategy;
+#[cfg(feature = "rayon")]
+pub use self::rayon::{IntoParIter, ParIter, ParIterMut};
+#[cfg(any(feature = "serde", feature = "scale-3", feature = "bincode"))]
+pub use self::with_layout::{LayoutMismatch, WithLayout};
+#[cfg(feature = "derive")]
+pub use bucket_vec_derive::{bucket_config, BucketSoA};
 
 /// A vector-like data structure that never moves its contained elements.
 ///
@@ -126,6 +317,12 @@ use core::marker::PhantomData;
 /// This case is trivial and all buckets are equally sized to have a
 /// capacity of `N`.
 ///
+/// ### Rounding
+///
+/// Configurations may round `capacity(i)` up to allocator-friendly size
+/// classes via `CAPACITY_ROUNDING`. The capacity until bucket `i` is then the
+/// sum of the rounded capacities before it.
+///
 /// ## Accessing Elements by Index
 ///
 /// Accessing the `i`th element of a `BucketVec` can be expressed by the
@@ -148,6 +345,19 @@ use core::marker::PhantomData;
 /// entry_index(i) = i - floor(capacity_until(bucket_index(i)))
 /// ```
 ///
+/// For fractional `a` the `bucket_index` is instead searched for as the
+/// largest `b` with `floor(capacity_until(b)) <= i` so that no logarithms
+/// are required. For growth rates of at least about `1.19` all values of
+/// `floor(capacity_until(b))` are tabulated at compile time so that the
+/// search itself involves no float math at run time.
+///
+/// Note that only integral growth rates are handled entirely without float
+/// math. Fractional growth rates are still evaluated using basic `f64`
+/// arithmetic, i.e. without logarithms or `libm`, both for the capacity
+/// tables and for buckets beyond them.
+///
+/// Rounded capacities are tabulated the same way regardless of `a`.
+///
 /// ### For `a == 1`:
 ///
 /// This case is very easy and we can simply calculate the `bucket_index` and
@@ -157,16 +367,30 @@ use core::marker::PhantomData;
 /// bucket_index(i) = i / N
 /// entry_index(i) = i % N
 /// ```
-#[derive(Debug)]
+#[cfg(feature = "alloc")]
 pub struct BucketVec<T, C = DefaultConfig> {
     /// The number of elements stored in the bucket vector.
     len: usize,
     /// The entry vector.
     buckets: Vec<Bucket<T>>,
+    /// The index of the first element of every bucket in the entry vector.
+    ///
+    /// Used to look up elements exactly for fractional growth rates.
+    offsets: Vec<usize>,
+    /// The number of elements the last bucket of the entry vector can still hold.
+    spare: usize,
+    /// Empty buckets that have been allocated ahead of time.
+    ///
+    /// These are moved over to the entry vector once they are required.
+    reserved: VecDeque<Bucket<T>>,
+    /// The recorded addresses of all elements.
+    #[cfg(feature = "debug-stability-checks")]
+    stability: stability::StabilityChecker,
     /// The config phantom data.
     config: PhantomData<fn() -> C>,
 }
 
+#[cfg(feature = "alloc")]
 impl<T, C> IntoIterator for BucketVec<T, C> {
     type Item = T;
     type IntoIter = IntoIter<T>;
@@ -176,6 +400,7 @@ impl<T, C> IntoIterator for BucketVec<T, C> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T, C> IntoIterator for &'a BucketVec<T, C> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
@@ -185,6 +410,7 @@ impl<'a, T, C> IntoIterator for &'a BucketVec<T, C> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T, C> IntoIterator for &'a mut BucketVec<T, C> {
     type Item = &'a mut T;
     type IntoIter = IterMut<'a, T>;
@@ -194,36 +420,248 @@ impl<'a, T, C> IntoIterator for &'a mut BucketVec<T, C> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, C> Clone for BucketVec<T, C>
 where
     T: Clone,
 {
     fn clone(&self) -> Self {
-        Self {
+        let mut clone = Self {
             len: self.len(),
             buckets: self.buckets.clone(),
+            offsets: self.offsets.clone(),
+            spare: 0,
+            reserved: VecDeque::new(),
+            #[cfg(feature = "debug-stability-checks")]
+            stability: Default::default(),
             config: Default::default(),
+        };
+        clone.sync_spare();
+        clone
+    }
+
+    /// Clones the elements of `source` into `self` reusing the already
+    /// allocated buckets of `self`.
+    ///
+    /// Buckets of `self` that are not required are kept as reserved buckets
+    /// so the capacity of `self` never shrinks.
+    fn clone_from(&mut self, source: &Self) {
+        self.truncate(source.len());
+        for (target, element) in self.iter_mut().zip(source.iter()) {
+            target.clone_from(element);
+        }
+        // Append the remaining elements mirroring the buckets of `source`.
+        let mut skip = self.len();
+        for (index, bucket) in source.buckets.iter().enumerate() {
+            let entries = bucket.as_slice();
+            if skip >= entries.len() {
+                skip -= entries.len();
+                continue;
+            }
+            let remaining = &entries[skip..];
+            skip = 0;
+            if index < self.buckets.len() {
+                self.buckets[index].extend_from_slice(remaining);
+            } else {
+                // Reserved buckets are allocated for the index they are attached at
+                // so they always mirror the bucket of `source` at the same index.
+                let mut new_bucket = self
+                    .reserved
+                    .pop_front()
+                    .unwrap_or_else(|| Bucket::new(bucket.capacity()));
+                debug_assert_eq!(new_bucket.capacity(), bucket.capacity());
+                new_bucket.extend_from_slice(remaining);
+                self.buckets.push(new_bucket);
+                self.offsets.push(source.offsets[index]);
+            }
+            self.len += remaining.len();
+        }
+        self.sync_spare();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U, C1, C2> PartialEq<BucketVec<U, C2>> for BucketVec<T, C1>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &BucketVec<U, C2>) -> bool {
+        self.len() == other.len()
+            && ChunkPairs::new(
+                self.buckets.iter().map(Bucket::as_slice),
+                other.buckets.iter().map(Bucket::as_slice),
+            )
+            .all(|(lhs, rhs)| lhs == rhs)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U, C> PartialEq<[U]> for BucketVec<T, C>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len()
+            && ChunkPairs::new(
+                self.buckets.iter().map(Bucket::as_slice),
+                core::iter::once(other),
+            )
+            .all(|(lhs, rhs)| lhs == rhs)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, U, C> PartialEq<&'a [U]> for BucketVec<T, C>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &&'a [U]) -> bool {
+        *self == **other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U, C> PartialEq<Vec<U>> for BucketVec<T, C>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &Vec<U>) -> bool {
+        *self == **other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U, C, const N: usize> PartialEq<[U; N]> for BucketVec<T, C>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+/// A shared or exclusive slice that can be split into two slices.
+#[cfg(feature = "alloc")]
+trait SplitSlice: Default {
+    /// Returns the number of elements of the slice.
+    fn slice_len(&self) -> usize;
+
+    /// Splits the slice into the elements before and from `mid` on.
+    fn split_slice(self, mid: usize) -> (Self, Self);
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SplitSlice for &[T] {
+    fn slice_len(&self) -> usize {
+        self.len()
+    }
+
+    fn split_slice(self, mid: usize) -> (Self, Self) {
+        self.split_at(mid)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SplitSlice for &mut [T] {
+    fn slice_len(&self) -> usize {
+        self.len()
+    }
+
+    fn split_slice(self, mid: usize) -> (Self, Self) {
+        self.split_at_mut(mid)
+    }
+}
+
+/// Iterator over pairs of equally long slices of two sequences of slices.
+///
+/// The elements of both sequences may be split into slices at different
+/// positions. Yields pairs until either sequence is exhausted.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+struct ChunkPairs<L, R>
+where
+    L: Iterator,
+    R: Iterator,
+{
+    /// The left-hand side sequence of slices.
+    lhs: L,
+    /// The right-hand side sequence of slices.
+    rhs: R,
+    /// The not yet yielded part of the current left-hand side slice.
+    lhs_slice: L::Item,
+    /// The not yet yielded part of the current right-hand side slice.
+    rhs_slice: R::Item,
+}
+
+#[cfg(feature = "alloc")]
+impl<L, R> ChunkPairs<L, R>
+where
+    L: Iterator,
+    R: Iterator,
+    L::Item: SplitSlice,
+    R::Item: SplitSlice,
+{
+    /// Creates a new iterator over pairs of slices of both sequences.
+    fn new(lhs: L, rhs: R) -> Self {
+        Self {
+            lhs,
+            rhs,
+            lhs_slice: Default::default(),
+            rhs_slice: Default::default(),
         }
     }
 }
 
-impl<T, C> PartialEq for BucketVec<T, C>
+#[cfg(feature = "alloc")]
+impl<L, R> Iterator for ChunkPairs<L, R>
 where
-    T: PartialEq,
+    L: Iterator,
+    R: Iterator,
+    L::Item: SplitSlice,
+    R::Item: SplitSlice,
 {
-    fn eq(&self, other: &Self) -> bool {
-        self.iter().zip(other.iter()).all(|(lhs, rhs)| lhs == rhs)
+    type Item = (L::Item, R::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.lhs_slice.slice_len() == 0 {
+            self.lhs_slice = self.lhs.next()?;
+        }
+        while self.rhs_slice.slice_len() == 0 {
+            self.rhs_slice = self.rhs.next()?;
+        }
+        let len = core::cmp::min(self.lhs_slice.slice_len(), self.rhs_slice.slice_len());
+        let (lhs, lhs_rest) = core::mem::take(&mut self.lhs_slice).split_slice(len);
+        let (rhs, rhs_rest) = core::mem::take(&mut self.rhs_slice).split_slice(len);
+        self.lhs_slice = lhs_rest;
+        self.rhs_slice = rhs_rest;
+        Some((lhs, rhs))
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<L, R> core::iter::FusedIterator for ChunkPairs<L, R>
+where
+    L: core::iter::FusedIterator,
+    R: core::iter::FusedIterator,
+    L::Item: SplitSlice,
+    R::Item: SplitSlice,
+{
+}
+
+#[cfg(feature = "alloc")]
 impl<T, C> Eq for BucketVec<T, C> where T: Eq {}
 
+#[cfg(feature = "alloc")]
 impl<T, C> core::cmp::PartialOrd for BucketVec<T, C>
 where
     T: core::cmp::PartialOrd,
 {
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-        for (lhs, rhs) in self.iter().zip(other.iter()) {
+        let chunks = ChunkPairs::new(
+            self.buckets.iter().map(Bucket::as_slice),
+            other.buckets.iter().map(Bucket::as_slice),
+        );
+        for (lhs, rhs) in chunks {
             match lhs.partial_cmp(rhs) {
                 Some(core::cmp::Ordering::Equal) => (),
                 non_eq => return non_eq,
@@ -233,12 +671,17 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, C> core::cmp::Ord for BucketVec<T, C>
 where
     T: core::cmp::Ord,
 {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-        for (lhs, rhs) in self.iter().zip(other.iter()) {
+        let chunks = ChunkPairs::new(
+            self.buckets.iter().map(Bucket::as_slice),
+            other.buckets.iter().map(Bucket::as_slice),
+        );
+        for (lhs, rhs) in chunks {
             match lhs.cmp(rhs) {
                 core::cmp::Ordering::Equal => (),
                 non_eq => return non_eq,
@@ -248,65 +691,254 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, C> core::hash::Hash for BucketVec<T, C>
 where
     T: core::hash::Hash,
 {
+    /// Hashes the bucket vector the same way as an equivalent slice for
+    /// hashers that do not depend on how written bytes are split up.
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.len().hash(state);
-        for elem in self.iter() {
-            elem.hash(state);
+        for bucket in &self.buckets {
+            T::hash_slice(bucket.as_slice(), state);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C> core::fmt::Debug for BucketVec<T, C>
+where
+    T: core::fmt::Debug,
+{
+    /// Formats the elements like a slice.
+    ///
+    /// The alternate mode `{:#?}` shows the buckets of the bucket vector
+    /// instead with vacant entries displayed as `_`.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if f.alternate() {
+            f.debug_list()
+                .entries(self.buckets.iter().map(DebugBucket))
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+/// Formats a bucket on a single line including its vacant entries.
+#[cfg(feature = "alloc")]
+struct DebugBucket<'a, T>(&'a Bucket<T>);
+
+#[cfg(feature = "alloc")]
+impl<'a, T> core::fmt::Debug for DebugBucket<'a, T>
+where
+    T: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        /// Up to this many vacant entries are displayed individually.
+        const MAX_VACANT_ENTRIES: usize = 8;
+        let bucket = self.0;
+        let mut separator = "";
+        f.write_str("[")?;
+        for entry in bucket.iter() {
+            write!(f, "{}{:?}", separator, entry)?;
+            separator = ", ";
+        }
+        match bucket.capacity() - bucket.len() {
+            vacant if vacant <= MAX_VACANT_ENTRIES => {
+                for _ in 0..vacant {
+                    write!(f, "{}_", separator)?;
+                    separator = ", ";
+                }
+            }
+            vacant => write!(f, "{}{} x _", separator, vacant)?,
         }
+        f.write_str("]")
     }
 }
 
-/// Accessor into a recently pushed element.
+/// Exclusive accessor to an element together with its position.
+///
+/// Dereferences to the accessed element.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
 pub struct Access<'a, T> {
-    /// Access by index.
-    index: usize,
+    /// The position of the accessed element.
+    position: Position,
     /// Access by exclusive reference.
     reference: &'a mut T,
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T> Access<'a, T> {
-    /// Creates a new accessor to the given index and reference.
-    pub(crate) fn new(index: usize, reference: &'a mut T) -> Self {
-        Self { index, reference }
+    /// Creates a new accessor to the given position and reference.
+    pub(crate) fn new(position: Position, reference: &'a mut T) -> Self {
+        Self {
+            position,
+            reference,
+        }
     }
 
-    /// Returns the index of the recently pushed element.
+    /// Returns the index of the accessed element.
     pub fn index(&self) -> usize {
-        self.index
+        self.position.index()
+    }
+
+    /// Returns the index of the bucket storing the accessed element.
+    pub fn bucket_index(&self) -> usize {
+        self.position.bucket_index()
+    }
+
+    /// Returns the index of the accessed element within its bucket.
+    pub fn entry_index(&self) -> usize {
+        self.position.entry_index()
     }
 
-    /// Returns a shared reference to the recently pushed element.
+    /// Returns the position of the accessed element.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns a shared reference to the accessed element.
     pub fn into_ref(self) -> &'a T {
         self.reference
     }
 
-    /// Returns an exclusive reference to the recently pushed element.
+    /// Returns an exclusive reference to the accessed element.
     pub fn into_mut(self) -> &'a mut T {
         self.reference
     }
 }
 
-impl<T> Default for BucketVec<T, DefaultConfig> {
+#[cfg(feature = "alloc")]
+impl<'a, T> core::ops::Deref for Access<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.reference
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> core::ops::DerefMut for Access<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.reference
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> AsRef<T> for Access<'a, T> {
+    fn as_ref(&self) -> &T {
+        self.reference
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> AsMut<T> for Access<'a, T> {
+    fn as_mut(&mut self) -> &mut T {
+        self.reference
+    }
+}
+
+/// Shared accessor to an element together with its position.
+///
+/// Dereferences to the accessed element.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct AccessRef<'a, T> {
+    /// The position of the accessed element.
+    position: Position,
+    /// Access by shared reference.
+    reference: &'a T,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Copy for AccessRef<'a, T> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Clone for AccessRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> AccessRef<'a, T> {
+    /// Creates a new accessor to the given position and reference.
+    pub(crate) fn new(position: Position, reference: &'a T) -> Self {
+        Self {
+            position,
+            reference,
+        }
+    }
+
+    /// Returns the index of the accessed element.
+    pub fn index(&self) -> usize {
+        self.position.index()
+    }
+
+    /// Returns the index of the bucket storing the accessed element.
+    pub fn bucket_index(&self) -> usize {
+        self.position.bucket_index()
+    }
+
+    /// Returns the index of the accessed element within its bucket.
+    pub fn entry_index(&self) -> usize {
+        self.position.entry_index()
+    }
+
+    /// Returns the position of the accessed element.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns a shared reference to the accessed element.
+    pub fn into_ref(self) -> &'a T {
+        self.reference
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> core::ops::Deref for AccessRef<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.reference
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> AsRef<T> for AccessRef<'a, T> {
+    fn as_ref(&self) -> &T {
+        self.reference
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C> Default for BucketVec<T, C> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, C> BucketVec<T, C> {
     /// Creates a new empty bucket vector.
     ///
     /// # Note
     ///
     /// This does not allocate any heap memory.
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
             len: 0,
             buckets: Vec::new(),
-            config: Default::default(),
+            offsets: Vec::new(),
+            spare: 0,
+            reserved: VecDeque::new(),
+            #[cfg(feature = "debug-stability-checks")]
+            stability: stability::StabilityChecker::new(),
+            config: PhantomData,
         }
     }
 
@@ -315,25 +947,249 @@ impl<T, C> BucketVec<T, C> {
         self.len
     }
 
+    /// Recomputes the cached spare capacity of the last bucket.
+    fn sync_spare(&mut self) {
+        self.spare = self
+            .buckets
+            .last()
+            .map(|bucket| bucket.capacity() - bucket.len())
+            .unwrap_or(0);
+        // Every structural change ends here so this is where we check.
+        self.check_stability();
+    }
+
+    /// Does nothing unless the `debug-stability-checks` crate feature is enabled.
+    #[cfg(not(feature = "debug-stability-checks"))]
+    #[inline(always)]
+    pub(crate) fn check_stability(&mut self) {}
+
+    /// Returns the entry vector and the number of elements.
+    pub(crate) fn into_entry_vector(mut self) -> (Vec<Bucket<T>>, usize) {
+        self.check_stability();
+        let len = core::mem::replace(&mut self.len, 0);
+        (core::mem::take(&mut self.buckets), len)
+    }
+
     /// Returns `true` if the bucket vector is empty.
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
+    /// Consumes and leaks the bucket vector returning an exclusive reference
+    /// to it that lives for the rest of the program.
+    ///
+    /// # Note
+    ///
+    /// Like `Vec::leak` this is useful for tables that are built once and
+    /// then accessed by `&'static` references. Since elements never move
+    /// further values can still be pushed through the returned reference.
+    pub fn leak<'a>(self) -> &'a mut BucketVec<T, C>
+    where
+        T: 'a,
+        C: 'a,
+    {
+        Box::leak(Box::new(self))
+    }
+
     /// Returns an iterator that yields shared references to the elements of the bucket vector.
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter::new(self)
     }
 
+    /// Returns `true` if the bucket vector contains an element equal to `value`.
+    ///
+    /// # Note
+    ///
+    /// The contiguous elements of every bucket are searched in bulk.
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.buckets
+            .iter()
+            .any(|bucket| bucket.as_slice().contains(value))
+    }
+
+    /// Returns an iterator over runs of consecutive elements for which
+    /// `pred` returns `true` when called on every pair of neighbours.
+    ///
+    /// Runs may span multiple buckets which is why every run is yielded as
+    /// an iterator over its elements.
+    pub fn chunk_by<F>(&self, pred: F) -> ChunkBy<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        ChunkBy::new(self, pred)
+    }
+
+    /// Returns an iterator over chunks of `chunk_size` consecutive elements.
+    ///
+    /// Chunks may span multiple buckets which is why every chunk is yielded
+    /// as an iterator over its elements. The last chunk may be shorter.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_size` is zero.
+    pub fn chunks(&self, chunk_size: usize) -> Chunks<'_, T> {
+        Chunks::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over all overlapping windows of `N` consecutive
+    /// elements, e.g. over pairs of neighbours for `N == 2`.
+    ///
+    /// Windows may span multiple buckets which is why every window is
+    /// yielded as an array of references.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero.
+    pub fn array_windows<const N: usize>(&self) -> ArrayWindows<'_, T, N> {
+        ArrayWindows::new(self)
+    }
+
     /// Returns an iterator that yields exclusive reference to the elements of the bucket vector.
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut::new(self)
     }
 
+    /// Overwrites every element of the bucket vector with values returned
+    /// by calling `f` repeatedly.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn fill_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        for bucket in &mut self.buckets {
+            bucket.as_mut_slice().fill_with(&mut f);
+        }
+    }
+
+    /// Reverses the order of the elements of the bucket vector in place.
+    ///
+    /// # Note
+    ///
+    /// The values of the elements are swapped between their slots which
+    /// themselves stay where they are. Pointers to elements therefore stay
+    /// valid but afterwards point to different values.
+    pub fn reverse(&mut self) {
+        let mut iter = self.iter_mut();
+        while let (Some(front), Some(back)) = (iter.next(), iter.next_back()) {
+            core::mem::swap(front, back);
+        }
+    }
+
+    /// Calls `f` with every element of the bucket vector and the element at
+    /// the same index of the other bucket vector.
+    ///
+    /// Stops once either bucket vector is exhausted.
+    ///
+    /// # Note
+    ///
+    /// Both bucket vectors are walked slice by slice so that no index
+    /// computations are required even if their bucket layouts differ.
+    pub fn zip_mut_with<U, C2, F>(&mut self, other: &BucketVec<U, C2>, mut f: F)
+    where
+        F: FnMut(&mut T, &U),
+    {
+        let chunks = ChunkPairs::new(
+            self.buckets.iter_mut().map(Bucket::as_mut_slice),
+            other.buckets.iter().map(Bucket::as_slice),
+        );
+        for (lhs, rhs) in chunks {
+            for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+                f(lhs, rhs);
+            }
+        }
+    }
+
+    /// Returns an iterator over pairs of the elements of every bucket and the
+    /// elements of the bucket with the same index of the other bucket vector.
+    ///
+    /// Stops once either bucket vector is exhausted.
+    ///
+    /// # Note
+    ///
+    /// Bucket vectors with the same configuration share their bucket layout.
+    /// This allows to process parallel arrays, e.g. keys stored in one bucket
+    /// vector and their payloads stored in another, slice by slice.
+    pub fn zip_buckets<'a, U>(&'a self, other: &'a BucketVec<U, C>) -> ZipBuckets<'a, T, U> {
+        ZipBuckets::new(self, other)
+    }
+
+    /// Returns an iterator over pairs of the exclusive elements of every
+    /// bucket and the elements of the bucket with the same index of the
+    /// other bucket vector.
+    ///
+    /// Stops once either bucket vector is exhausted.
+    pub fn zip_buckets_mut<'a, U>(
+        &'a mut self,
+        other: &'a mut BucketVec<U, C>,
+    ) -> ZipBucketsMut<'a, T, U> {
+        ZipBucketsMut::new(self, other)
+    }
+
+    /// Returns an iterator that yields shared references to the elements of the bucket vector
+    /// together with their positions.
+    ///
+    /// The position of an element consists of its global index as well as the
+    /// index of its bucket and its index within that bucket.
+    pub fn iter_indexed(&self) -> IterIndexed<'_, T> {
+        IterIndexed::new(self)
+    }
+
+    /// Returns an iterator that yields exclusive references to the elements of the bucket vector
+    /// together with their positions.
+    ///
+    /// The position of an element consists of its global index as well as the
+    /// index of its bucket and its index within that bucket.
+    pub fn iter_mut_indexed(&mut self) -> IterIndexedMut<'_, T> {
+        IterIndexedMut::new(self)
+    }
+
+    /// Returns a table of pointers to all elements in order.
+    ///
+    /// # Note
+    ///
+    /// The pointers stay valid until their elements are dropped since pushing
+    /// never moves elements and may be used to mutate the elements.
+    pub fn ptr_table(&mut self) -> Vec<NonNull<T>> {
+        self.iter_mut().map(NonNull::from).collect()
+    }
+
+    /// Returns the index of the referenced element if it is contained in the
+    /// bucket vector.
+    ///
+    /// # Note
+    ///
+    /// Compares the address of the reference against the address ranges of
+    /// all buckets. Always returns `None` for zero-sized types since their
+    /// elements do not have distinct addresses.
+    pub fn index_of_ref(&self, element: &T) -> Option<usize> {
+        let size = core::mem::size_of::<T>();
+        if size == 0 {
+            return None;
+        }
+        let address = element as *const T;
+        let mut offset = 0;
+        for bucket in &self.buckets {
+            let entries = bucket.as_slice();
+            if entries.as_ptr_range().contains(&address) {
+                let start = entries.as_ptr() as usize;
+                return Some(offset + (address as usize - start) / size);
+            }
+            offset += entries.len();
+        }
+        None
+    }
+
     /// Returns a shared reference to the first element of the bucket vector.
     pub fn first(&self) -> Option<&T> {
         if self.is_empty() {
-            return None
+            return None;
         }
         Some(&self.buckets[0][0])
     }
@@ -341,7 +1197,7 @@ impl<T, C> BucketVec<T, C> {
     /// Returns an exclusive reference to the first element of the bucket vector.
     pub fn first_mut(&mut self) -> Option<&mut T> {
         if self.is_empty() {
-            return None
+            return None;
         }
         Some(&mut self.buckets[0][0])
     }
@@ -349,7 +1205,7 @@ impl<T, C> BucketVec<T, C> {
     /// Returns a shared reference to the last element of the bucket vector.
     pub fn last(&self) -> Option<&T> {
         if self.is_empty() {
-            return None
+            return None;
         }
         let len_buckets = self.buckets.len();
         let len_entries = self.buckets[len_buckets - 1].len();
@@ -359,30 +1215,255 @@ impl<T, C> BucketVec<T, C> {
     /// Returns an exclusive reference to the last element of the bucket vector.
     pub fn last_mut(&mut self) -> Option<&mut T> {
         if self.is_empty() {
-            return None
+            return None;
         }
         let len_buckets = self.buckets.len();
         let len_entries = self.buckets[len_buckets - 1].len();
         Some(&mut self.buckets[len_buckets - 1][len_entries - 1])
     }
-}
 
-impl<T, C> BucketVec<T, C>
-where
-    C: BucketVecConfig,
-{
-    /// Returns the bucket index and its internal entry index for the given
-    /// bucket vector index into an element.
+    /// Returns an exclusive reference to the first element of the bucket vector
+    /// together with an iterator over the remaining elements.
     ///
-    /// Returns `None` if the index is out of bounds.
-    fn bucket_entry_indices(&self, index: usize) -> Option<(usize, usize)> {
-        if index >= self.len() {
-            return None;
-        }
-        Some(config::bucket_entry_indices::<C>(index))
+    /// Returns `None` if the bucket vector is empty.
+    pub fn split_first_mut(&mut self) -> Option<(&mut T, IterMut<'_, T>)> {
+        let mut rest = self.iter_mut();
+        let first = rest.next()?;
+        Some((first, rest))
     }
 
-    /// Returns a shared reference to the element at the given index if any.
+    /// Returns an exclusive reference to the last element of the bucket vector
+    /// together with an iterator over the remaining elements.
+    ///
+    /// Returns `None` if the bucket vector is empty.
+    pub fn split_last_mut(&mut self) -> Option<(&mut T, IterMut<'_, T>)> {
+        let mut rest = self.iter_mut();
+        let last = rest.next_back()?;
+        Some((last, rest))
+    }
+
+    /// Removes elements from the end of the bucket vector while `pred`
+    /// returns `true` for the last element and returns them in the order
+    /// they have been removed.
+    ///
+    /// # Note
+    ///
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn pop_while<F>(&mut self, mut pred: F) -> Vec<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut popped = Vec::new();
+        while self.last().map(&mut pred).unwrap_or(false) {
+            popped.extend(self.pop());
+        }
+        popped
+    }
+
+    /// Drops elements from the end of the bucket vector while `pred`
+    /// returns `true` for the last element.
+    ///
+    /// # Note
+    ///
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn truncate_while<F>(&mut self, mut pred: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let kept = self
+            .iter()
+            .rposition(|value| !pred(value))
+            .map_or(0, |index| index + 1);
+        self.truncate(kept);
+    }
+
+    /// Retains only the elements for which `f` returns `true` and frees all
+    /// buckets that are no longer required.
+    ///
+    /// The retained elements keep their relative order and are packed to
+    /// the front of the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// Unlike most operations this moves retained elements to other
+    /// positions and thus invalidates all previously obtained references,
+    /// pointers and indices of elements of the bucket vector.
+    /// Reserved buckets are freed as well.
+    pub fn retain_invalidating<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut kept = 0;
+        let (mut write_bucket, mut write_entry) = (0, 0);
+        for read_bucket in 0..self.buckets.len() {
+            for read_entry in 0..self.buckets[read_bucket].len() {
+                if !f(&self.buckets[read_bucket][read_entry]) {
+                    continue;
+                }
+                if write_bucket == read_bucket {
+                    self.buckets[read_bucket]
+                        .as_mut_slice()
+                        .swap(write_entry, read_entry);
+                } else {
+                    let (head, tail) = self.buckets.split_at_mut(read_bucket);
+                    core::mem::swap(
+                        &mut head[write_bucket][write_entry],
+                        &mut tail[0][read_entry],
+                    );
+                }
+                kept += 1;
+                write_entry += 1;
+                if write_entry == self.buckets[write_bucket].len() {
+                    write_bucket += 1;
+                    write_entry = 0;
+                }
+            }
+        }
+        self.truncate(kept);
+        self.reserved.clear();
+    }
+
+    /// Removes all but the first of consecutive elements that are equal.
+    ///
+    /// # Note
+    ///
+    /// Unlike most operations this moves retained elements to other
+    /// positions and thus invalidates all previously obtained references,
+    /// pointers and indices of elements after the first removed element.
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Removes all but the first of consecutive elements that resolve to the
+    /// same key.
+    ///
+    /// # Note
+    ///
+    /// Unlike most operations this moves retained elements to other
+    /// positions and thus invalidates all previously obtained references,
+    /// pointers and indices of elements after the first removed element.
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes all but the first of consecutive elements for which
+    /// `same_bucket` returns `true`.
+    ///
+    /// `same_bucket` is called with the element in question and the last
+    /// retained element before it.
+    ///
+    /// # Note
+    ///
+    /// Unlike most operations this moves retained elements to other
+    /// positions and thus invalidates all previously obtained references,
+    /// pointers and indices of elements after the first removed element.
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        if self.is_empty() {
+            return;
+        }
+        // The first element is always retained.
+        let mut kept = 1;
+        let mut last_kept = (0, 0);
+        let (mut write_bucket, mut write_entry) = (0, 1);
+        for read_bucket in 0..self.buckets.len() {
+            let start = if read_bucket == 0 { 1 } else { 0 };
+            for read_entry in start..self.buckets[read_bucket].len() {
+                let read = (read_bucket, read_entry);
+                let (previous, current) = self.pair_mut(last_kept, read);
+                if same_bucket(current, previous) {
+                    continue;
+                }
+                if write_entry == self.buckets[write_bucket].len() {
+                    write_bucket += 1;
+                    write_entry = 0;
+                }
+                let write = (write_bucket, write_entry);
+                if write != read {
+                    let (write, read) = self.pair_mut(write, read);
+                    core::mem::swap(write, read);
+                }
+                last_kept = write;
+                kept += 1;
+                write_entry += 1;
+            }
+        }
+        self.truncate(kept);
+    }
+
+    /// Returns exclusive references to two elements given by their bucket and
+    /// entry indices where the first element precedes the second one.
+    fn pair_mut(&mut self, (lx, ly): (usize, usize), (rx, ry): (usize, usize)) -> (&mut T, &mut T) {
+        if lx == rx {
+            let (head, tail) = self.buckets[lx].as_mut_slice().split_at_mut(ry);
+            return (&mut head[ly], &mut tail[0]);
+        }
+        let (head, tail) = self.buckets.split_at_mut(rx);
+        (&mut head[lx][ly], &mut tail[0][ry])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C> BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns the bucket index and its internal entry index for the given
+    /// bucket vector index into an element.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    fn bucket_entry_indices(&self, index: usize) -> Option<(usize, usize)> {
+        if index >= self.len() {
+            return None;
+        }
+        if core::mem::size_of::<T>() == 0 {
+            // Buckets of zero-sized elements have unbounded capacity so
+            // all elements are stored in the first bucket.
+            return Some((0, index));
+        }
+        if config::integral_growth_rate::<C>().is_some()
+            && <C as BucketVecConfig>::MAX_BUCKET_CAPACITY.is_none()
+        {
+            // Index math is exact and cheap for uncapped integral growth rates.
+            return Some(config::bucket_entry_indices::<C>(index));
+        }
+        let x = self.offsets.partition_point(|&offset| offset <= index) - 1;
+        Some((x, index - self.offsets[x]))
+    }
+
+    /// Returns an iterator over shared references to every `step`th element
+    /// starting at the given index.
+    ///
+    /// # Note
+    ///
+    /// The iterator advances through the index math of the bucket vector and
+    /// never visits skipped elements. Extracting a column of interleaved data
+    /// therefore takes time linear in the number of yielded elements.
+    ///
+    /// # Panics
+    ///
+    /// If `step` is zero.
+    pub fn iter_step_by(&self, start: usize, step: usize) -> IterStepBy<'_, T, C> {
+        IterStepBy::new(self, start, step)
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
     pub fn get(&self, index: usize) -> Option<&T> {
         self.bucket_entry_indices(index)
             .and_then(|(x, y)| self.buckets[x].get(y))
@@ -394,14 +1475,317 @@ where
             .and_then(move |(x, y)| self.buckets[x].get_mut(y))
     }
 
+    /// Returns the elements within the range as a slice if they are all
+    /// stored contiguously in the same bucket.
+    ///
+    /// Returns `None` if the range is out of bounds or spans multiple buckets.
+    pub fn try_slice<R>(&self, range: R) -> Option<&[T]>
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = self.index_range(range)?;
+        if range.is_empty() {
+            return Some(&[]);
+        }
+        let (x, entries) = self.contiguous_entries(range)?;
+        Some(&self.buckets[x].as_slice()[entries])
+    }
+
+    /// Returns the elements within the range as an exclusive slice if they
+    /// are all stored contiguously in the same bucket.
+    ///
+    /// Returns `None` if the range is out of bounds or spans multiple buckets.
+    pub fn try_slice_mut<R>(&mut self, range: R) -> Option<&mut [T]>
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = self.index_range(range)?;
+        if range.is_empty() {
+            return Some(&mut []);
+        }
+        let (x, entries) = self.contiguous_entries(range)?;
+        Some(&mut self.buckets[x].as_mut_slice()[entries])
+    }
+
+    /// Returns the bounds checked indices of the range.
+    fn index_range<R>(&self, range: R) -> Option<core::ops::Range<usize>>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1)?,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len(),
+        };
+        if start > end || end > self.len() {
+            return None;
+        }
+        Some(start..end)
+    }
+
+    /// Returns the bucket index and the entry indices of the non-empty
+    /// in bounds range if all of its elements are stored in the same bucket.
+    fn contiguous_entries(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Option<(usize, core::ops::Range<usize>)> {
+        let (x, first) = self.bucket_entry_indices(range.start)?;
+        let (x_last, last) = self.bucket_entry_indices(range.end - 1)?;
+        if x != x_last {
+            return None;
+        }
+        Some((x, first..last + 1))
+    }
+
+    /// Returns an exclusive reference to the element at the given index
+    /// pushing values returned by `fill` until the index is in bounds.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn get_mut_or_grow<F>(&mut self, index: usize, fill: F) -> &mut T
+    where
+        F: FnMut() -> T,
+    {
+        if index >= self.len() {
+            let missing = index - self.len() + 1;
+            self.extend(core::iter::repeat_with(fill).take(missing));
+        }
+        self.get_mut(index)
+            .expect("encountered out of bounds index after growing")
+    }
+
+    /// Overwrites the elements at the indices of the updates with their values.
+    ///
+    /// Later updates of the same index win over earlier ones.
+    ///
+    /// # Note
+    ///
+    /// The updates are sorted by index and grouped by bucket so that the
+    /// bucket of every group is only resolved once.
+    /// Allocates a temporary buffer of all updates.
+    ///
+    /// # Panics
+    ///
+    /// If any of the indices is out of bounds. No element is overwritten then.
+    #[track_caller]
+    pub fn write_many<I>(&mut self, updates: I)
+    where
+        I: IntoIterator<Item = (usize, T)>,
+    {
+        let mut updates = updates.into_iter().collect::<Vec<_>>();
+        // Sorting stably keeps later updates of the same index behind earlier ones.
+        updates.sort_by_key(|&(index, _)| index);
+        if let Some(&(last, _)) = updates.last() {
+            if last >= self.len() {
+                index_out_of_bounds(last, self.len())
+            }
+        }
+        let mut updates = updates.into_iter().peekable();
+        while let Some(&(index, _)) = updates.peek() {
+            let (x, y) = self
+                .bucket_entry_indices(index)
+                .expect("encountered out of bounds index");
+            let start = index - y;
+            let entries = self.buckets[x].as_mut_slice();
+            while let Some((index, new_value)) =
+                updates.next_if(|&(index, _)| index - start < entries.len())
+            {
+                entries[index - start] = new_value;
+            }
+        }
+    }
+
+    /// Removes the element at the given index and returns it if any.
+    ///
+    /// The removed element is replaced by the last element of the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// Unlike most operations this moves the last element into the slot of
+    /// the removed element and thus invalidates all previously obtained
+    /// references, pointers and indices of the last element.
+    /// All other elements are never moved.
+    pub fn swap_remove_last(&mut self, index: usize) -> Option<T> {
+        let (x, y) = self.bucket_entry_indices(index)?;
+        let last = self.pop()?;
+        if index == self.len() {
+            // The removed element has been the last element.
+            return Some(last);
+        }
+        Some(core::mem::replace(&mut self.buckets[x][y], last))
+    }
+
+    /// Inserts the value at the given index shifting all elements at and after
+    /// it one position towards the end.
+    ///
+    /// # Note
+    ///
+    /// Unlike most operations this moves all elements at and after `index`
+    /// and thus invalidates all previously obtained references, pointers and
+    /// indices of them. Elements before `index` are never moved.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the length of the bucket vector.
+    #[track_caller]
+    pub fn insert_shift(&mut self, index: usize, new_value: T) {
+        let len = self.len();
+        if index == len {
+            return self.push(new_value);
+        }
+        let (x, y) = self
+            .bucket_entry_indices(index)
+            .unwrap_or_else(|| index_out_of_bounds(index, len));
+        // Rotate every affected bucket by one carrying its last element over
+        // into the first affected entry of the next bucket.
+        let mut carry = new_value;
+        for (n, bucket) in self.buckets[x..].iter_mut().enumerate() {
+            let start = if n == 0 { y } else { 0 };
+            let entries = &mut bucket.as_mut_slice()[start..];
+            entries.rotate_right(1);
+            core::mem::swap(&mut entries[0], &mut carry);
+        }
+        self.push(carry);
+    }
+
+    /// Removes the element at the given index shifting all elements after it
+    /// one position towards the front and returns it if any.
+    ///
+    /// # Note
+    ///
+    /// Unlike most operations this moves all elements after `index` and thus
+    /// invalidates all previously obtained references, pointers and indices
+    /// of them. Elements before `index` are never moved.
+    pub fn remove_shift(&mut self, index: usize) -> Option<T> {
+        let (x, y) = self.bucket_entry_indices(index)?;
+        let mut carry = self.pop()?;
+        if index == self.len() {
+            // The removed element has been the last element.
+            return Some(carry);
+        }
+        // Rotate every affected bucket by one carrying its first affected
+        // entry over into the last entry of the previous bucket.
+        for (n, bucket) in self.buckets[x..].iter_mut().enumerate().rev() {
+            let start = if n == 0 { y } else { 0 };
+            let entries = &mut bucket.as_mut_slice()[start..];
+            entries.rotate_left(1);
+            let last = entries.len() - 1;
+            core::mem::swap(&mut entries[last], &mut carry);
+        }
+        Some(carry)
+    }
+
+    /// Returns a shared accessor to the element at the given index if any.
+    pub fn access(&self, index: usize) -> Option<AccessRef<'_, T>> {
+        let (x, y) = self.bucket_entry_indices(index)?;
+        let reference = self.buckets[x].get(y)?;
+        Some(AccessRef::new(Position::new(index, x, y), reference))
+    }
+
+    /// Returns an exclusive accessor to the element at the given index if any.
+    pub fn access_mut(&mut self, index: usize) -> Option<Access<'_, T>> {
+        let (x, y) = self.bucket_entry_indices(index)?;
+        let reference = self.buckets[x].get_mut(y)?;
+        Some(Access::new(Position::new(index, x, y), reference))
+    }
+
+    /// Returns a pointer to the element at the given index if any.
+    ///
+    /// # Note
+    ///
+    /// The pointer stays valid until the element is dropped since pushing
+    /// never moves elements. It must not be used to mutate the element.
+    pub fn get_ptr(&self, index: usize) -> Option<NonNull<T>> {
+        self.get(index).map(NonNull::from)
+    }
+
+    /// Returns a pointer to the element at the given index if any.
+    ///
+    /// # Note
+    ///
+    /// The pointer stays valid until the element is dropped since pushing
+    /// never moves elements and may be used to mutate the element.
+    pub fn get_ptr_mut(&mut self, index: usize) -> Option<NonNull<T>> {
+        self.get_mut(index).map(NonNull::from)
+    }
+
+    /// Returns the `len` elements starting at the given index if they are
+    /// stored contiguously within a single bucket.
+    ///
+    /// Returns `None` if the elements are out of bounds or span multiple buckets.
+    pub(crate) fn get_contiguous(&self, index: usize, len: usize) -> Option<&[T]> {
+        if len == 0 {
+            return Some(&[]);
+        }
+        let (x, y) = self.bucket_entry_indices(index)?;
+        self.buckets[x].as_slice().get(y..y.checked_add(len)?)
+    }
+
+    /// Returns the number of elements the bucket vector can hold without
+    /// allocating new buckets.
+    ///
+    /// # Note
+    ///
+    /// Always returns `usize::MAX` for zero-sized types since they never
+    /// require any allocations.
+    pub fn capacity(&self) -> usize {
+        BucketStorage::capacity(self)
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// # Note
+    ///
+    /// The reserved buckets are allocated eagerly but only become part of
+    /// the bucket vector once pushed elements require them.
+    pub fn reserve(&mut self, additional: usize) {
+        BucketSchedule::reserve(self, additional)
+    }
+
+    /// Returns the number of elements that can be pushed onto the bucket
+    /// vector without allocating another bucket.
+    pub fn spare_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Returns `true` if the next push onto the bucket vector allocates
+    /// another bucket.
+    pub fn next_push_allocates(&self) -> bool {
+        self.spare == 0 && self.reserved.is_empty()
+    }
+
+    /// Eagerly allocates the next bucket without pushing any elements.
+    ///
+    /// Does nothing if the next bucket has already been allocated.
+    ///
+    /// # Note
+    ///
+    /// Afterwards at least all elements fitting into the spare capacity of
+    /// the last bucket and the next bucket can be pushed without allocating.
+    pub fn prealloc_next_bucket(&mut self) {
+        // Zero-sized elements never require another bucket.
+        if self.reserved.is_empty() && core::mem::size_of::<T>() != 0 {
+            let index = self.buckets.len();
+            let new_capacity = config::allocate_bucket::<C>(index);
+            self.reserved.push_back(Bucket::new(new_capacity));
+        }
+        self.check_stability();
+    }
+
     /// Pushes a new bucket containing the new value onto the bucket vector.
     fn push_bucket(&mut self, new_value: T) {
-        let len_buckets = self.buckets.len();
-        let new_capacity = config::bucket_capacity::<C>(len_buckets);
-        let mut new_bucket = Bucket::new(new_capacity);
+        let mut new_bucket = self.next_bucket();
         new_bucket.push(new_value);
-        self.buckets.push(new_bucket);
+        self.attach_bucket(new_bucket);
         self.len += 1;
+        self.sync_spare();
     }
 
     /// Pushes a new element onto the bucket vector.
@@ -411,12 +1795,15 @@ where
     /// This operation will never move other elements, reallocates or otherwise
     /// invalidate pointers of elements contained by the bucket vector.
     pub fn push(&mut self, new_value: T) {
-        if let Some(bucket) = self.buckets.last_mut() {
-            if bucket.len() < bucket.capacity() {
-                bucket.push(new_value);
-                self.len += 1;
-                return;
-            }
+        if self.spare != 0 {
+            self.spare -= 1;
+            self.len += 1;
+            self.buckets
+                .last_mut()
+                .expect("encountered missing bucket with spare capacity")
+                .push(new_value);
+            self.check_stability();
+            return;
         }
         self.push_bucket(new_value);
     }
@@ -427,15 +1814,605 @@ where
     ///
     /// This operation will never move other elements, reallocates or otherwise
     /// invalidate pointers of elements contained by the bucket vector.
-    pub fn push_get(&mut self, new_value: T) -> Access<T> {
+    pub fn push_get(&mut self, new_value: T) -> Access<'_, T> {
+        self.push_get_with(|_| new_value)
+    }
+
+    /// Pushes all elements of the iterator onto the bucket vector and returns
+    /// the index range of the pushed elements.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn extend_get<I>(&mut self, iter: I) -> core::ops::Range<usize>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let start = self.len();
+        self.extend(iter);
+        start..self.len()
+    }
+
+    /// Pushes the element created by `f` from its index onto the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push_with<F>(&mut self, f: F)
+    where
+        F: FnOnce(usize) -> T,
+    {
+        let index = self.len();
+        self.push(f(index))
+    }
+
+    /// Pushes the element created by `f` from its index onto the bucket vector
+    /// and returns access to it.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push_get_with<F>(&mut self, f: F) -> Access<'_, T>
+    where
+        F: FnOnce(usize) -> T,
+    {
         let index = self.len();
-        self.push(new_value);
+        self.push(f(index));
         let len_buckets = self.buckets.len();
         let len_entries = self.buckets[len_buckets - 1].len();
-        Access::new(index, &mut self.buckets[len_buckets - 1][len_entries - 1])
+        let position = Position::new(index, len_buckets - 1, len_entries - 1);
+        Access::new(
+            position,
+            &mut self.buckets[len_buckets - 1][len_entries - 1],
+        )
+    }
+
+    /// Rebuilds the bucket vector under another configuration.
+    ///
+    /// This is useful to repack the elements after a build phase, e.g. into
+    /// a configuration with equally sized buckets for faster indexing.
+    ///
+    /// # Note
+    ///
+    /// Unlike most operations this moves all elements and thus invalidates
+    /// all pointers to elements of the bucket vector.
+    pub fn rebalance<C2>(self) -> BucketVec<T, C2>
+    where
+        C2: BucketVecConfig,
+    {
+        self.into_iter().collect()
+    }
+
+    /// Maps all elements of the bucket vector to another element type.
+    ///
+    /// The mapped bucket vector has exactly the same bucket structure, so
+    /// positions of elements within the original bucket vector remain valid
+    /// for the mapped bucket vector.
+    ///
+    /// # Note
+    ///
+    /// Reserved buckets are not carried over to the mapped bucket vector.
+    /// Zero-sized elements are always stored within the first bucket which
+    /// is why the bucket structure is not preserved if either element type
+    /// is zero-sized.
+    pub fn map<U, F>(self, mut f: F) -> BucketVec<U, C>
+    where
+        F: FnMut(T) -> U,
+    {
+        match self.try_map(|value| Ok::<U, core::convert::Infallible>(f(value))) {
+            Ok(mapped) => mapped,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Maps all elements of the bucket vector to another element type
+    /// using a fallible mapping.
+    ///
+    /// The mapped bucket vector has exactly the same bucket structure.
+    /// See [`BucketVec::map`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Stops at and returns the first error produced by `f`.
+    /// All remaining elements are dropped in this case.
+    pub fn try_map<U, E, F>(self, mut f: F) -> Result<BucketVec<U, C>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        if core::mem::size_of::<T>() == 0 || core::mem::size_of::<U>() == 0 {
+            return self.into_iter().map(f).collect();
+        }
+        let offsets = self.offsets.clone();
+        let (buckets, len) = self.into_entry_vector();
+        let mut mapped = BucketVec::<U, C>::new();
+        for bucket in buckets {
+            let mut new_bucket = Bucket::new(bucket.capacity());
+            for value in bucket.into_vec() {
+                new_bucket.push(f(value)?);
+            }
+            mapped.buckets.push(new_bucket);
+        }
+        mapped.offsets = offsets;
+        mapped.len = len;
+        mapped.sync_spare();
+        Ok(mapped)
+    }
+
+    /// Pushes the values of the iterator until it is exhausted or yields
+    /// an error and returns the number of pushed values.
+    ///
+    /// # Errors
+    ///
+    /// Stops at and returns the first error yielded by the iterator.
+    /// Values pushed before the error remain in the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn try_extend<E, I>(&mut self, iter: I) -> Result<usize, E>
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+    {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.reserve(lower);
+        let start = self.len();
+        let mut iter = iter.fuse().peekable();
+        while iter.peek().is_some() {
+            let filled = self.try_fill_bucket(usize::MAX, || match iter.next() {
+                Some(result) => result.map_err(Some),
+                None => Err(None),
+            });
+            if let Err(Some(error)) = filled {
+                return Err(error);
+            }
+        }
+        Ok(self.len() - start)
+    }
+
+    /// Concatenates all parts into a single bucket vector in order.
+    ///
+    /// # Note
+    ///
+    /// The first non-empty part is adopted as is together with all of its
+    /// buckets so that its elements are never moved. The buckets of every
+    /// following part are moved over as well as long as the concatenated
+    /// elements end on a bucket boundary and the capacities of the buckets
+    /// match the buckets that would be allocated next, e.g. for equally
+    /// sized buckets. All other elements are moved bucket by bucket into
+    /// buckets that are allocated up front.
+    pub fn concat<I>(parts: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut parts = parts.into_iter().filter(|part| !part.is_empty());
+        let mut concat = match parts.next() {
+            Some(first) => first,
+            None => return Self::new(),
+        };
+        for part in parts {
+            concat.append_buckets(part);
+        }
+        concat
+    }
+
+    /// Appends all elements of the other bucket vector.
+    ///
+    /// Adopts the leading buckets of the other bucket vector as long as they
+    /// line up with the buckets that would be allocated next and moves the
+    /// elements of all remaining buckets.
+    fn append_buckets(&mut self, other: Self) {
+        let (buckets, len) = other.into_entry_vector();
+        let mut buckets = buckets.into_iter().peekable();
+        let mut remaining = len;
+        while self.spare == 0 && self.reserved.is_empty() {
+            let index = self.buckets.len();
+            let adopted =
+                buckets.next_if(|bucket| bucket.capacity() == config::bucket_capacity::<C>(index));
+            match adopted {
+                Some(bucket) => {
+                    remaining -= bucket.len();
+                    self.len += bucket.len();
+                    self.attach_bucket(bucket);
+                    self.sync_spare();
+                }
+                None => break,
+            }
+        }
+        self.reserve(remaining);
+        self.extend(buckets.flatten());
+    }
+
+    /// Sorts the elements of the bucket vector with the comparator function.
+    ///
+    /// This sort is unstable, i.e. may reorder equal elements.
+    ///
+    /// # Note
+    ///
+    /// The values of the elements are swapped between their slots which
+    /// themselves stay where they are. Pointers to elements therefore stay
+    /// valid but afterwards point to different values.
+    /// Allocates a temporary buffer of one index per element.
+    pub fn sort_unstable_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> core::cmp::Ordering,
+    {
+        let refs = self.iter().collect::<Vec<_>>();
+        let mut order = (0..refs.len()).collect::<Vec<_>>();
+        order.sort_unstable_by(|&lhs, &rhs| compare(refs[lhs], refs[rhs]));
+        self.permute(order);
+    }
+
+    /// Sorts the elements of the bucket vector with the key extraction function.
+    ///
+    /// This sort is stable, i.e. does not reorder equal elements.
+    ///
+    /// # Note
+    ///
+    /// The values of the elements are swapped between their slots which
+    /// themselves stay where they are. Pointers to elements therefore stay
+    /// valid but afterwards point to different values.
+    /// Allocates a temporary buffer of one index per element.
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        let refs = self.iter().collect::<Vec<_>>();
+        let mut order = (0..refs.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| f(refs[index]));
+        self.permute(order);
+    }
+
+    /// Moves the value at index `order[i]` into the slot at index `i` for all `i`.
+    ///
+    /// The order must be a permutation of the indices of the bucket vector.
+    fn permute(&mut self, mut order: Vec<usize>) {
+        for start in 0..order.len() {
+            let mut current = start;
+            loop {
+                let next = core::mem::replace(&mut order[current], current);
+                if next == start {
+                    break;
+                }
+                self.swap_values(current, next);
+                current = next;
+            }
+        }
+    }
+
+    /// Swaps the values of the elements at the given indices.
+    ///
+    /// # Panics
+    ///
+    /// If any of the indices is out of bounds.
+    #[track_caller]
+    fn swap_values(&mut self, lhs: usize, rhs: usize) {
+        let len = self.len();
+        let (lx, ly) = self
+            .bucket_entry_indices(lhs)
+            .unwrap_or_else(|| index_out_of_bounds(lhs, len));
+        let (rx, ry) = self
+            .bucket_entry_indices(rhs)
+            .unwrap_or_else(|| index_out_of_bounds(rhs, len));
+        if lx == rx {
+            self.buckets[lx].as_mut_slice().swap(ly, ry);
+            return;
+        }
+        let (lx, ly, rx, ry) = if lx < rx {
+            (lx, ly, rx, ry)
+        } else {
+            (rx, ry, lx, ly)
+        };
+        let (head, tail) = self.buckets.split_at_mut(rx);
+        core::mem::swap(&mut head[lx][ly], &mut tail[0][ry]);
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<T, C> BucketVec<T, C>
+where
+    T: Clone,
+    C: BucketVecConfig,
+{
+    /// Overwrites every element of the bucket vector with clones of `value`.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn fill(&mut self, value: T) {
+        for bucket in &mut self.buckets {
+            bucket.as_mut_slice().fill(value.clone());
+        }
+    }
+
+    /// Clones all elements of the bucket vector into a vector.
+    ///
+    /// # Note
+    ///
+    /// The vector is allocated once and filled bucket by bucket.
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.len());
+        for bucket in &self.buckets {
+            vec.extend_from_slice(bucket.as_slice());
+        }
+        vec
+    }
+
+    /// Clones all elements of the bucket vector into a boxed slice.
+    ///
+    /// # Note
+    ///
+    /// The boxed slice is allocated once and filled bucket by bucket.
+    pub fn to_boxed_slice(&self) -> Box<[T]> {
+        self.to_vec().into_boxed_slice()
+    }
+
+    /// Clones and pushes all of the given values onto the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// The values are copied in bulk into the spare capacity of the buckets.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn extend_from_slice(&mut self, values: &[T]) {
+        let mut cloned = 0;
+        while cloned < values.len() {
+            cloned += self.fill_bucket_from_slice(&values[cloned..]);
+        }
+    }
+
+    /// Pushes `count` clones of the value onto the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// The clones fill the spare capacity of the buckets in bulk instead of
+    /// being pushed one by one.
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn push_repeated(&mut self, count: usize, value: T) {
+        let mut remaining = count;
+        while remaining != 0 {
+            self.ensure_spare_bucket();
+            let bucket = self
+                .buckets
+                .last_mut()
+                .expect("encountered missing bucket with spare capacity");
+            let pushed = core::cmp::min(bucket.capacity() - bucket.len(), remaining);
+            bucket.push_repeated(pushed, value.clone());
+            self.len += pushed;
+            remaining -= pushed;
+            self.sync_spare();
+        }
+    }
+
+    /// Clones the values into the spare capacity of the last bucket.
+    ///
+    /// The length is updated after every clone so that a panicking clone
+    /// never leaves behind elements that are not accounted for.
+    fn push_clones<'a, I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+        T: 'a,
+    {
+        for value in values {
+            let new_value = value.clone();
+            self.spare -= 1;
+            self.len += 1;
+            self.buckets
+                .last_mut()
+                .expect("encountered missing bucket with spare capacity")
+                .push(new_value);
+        }
+    }
+
+    /// Clones all of the given values into a single bucket so that they are
+    /// stored contiguously padding with clones of `padding`.
+    ///
+    /// Elements are always stored in the order of their indices so the spare
+    /// capacity of the last bucket cannot be skipped. If the values do not fit
+    /// into it, it is filled up with padding and so is every following bucket
+    /// until one is large enough to hold the values. Padding elements are
+    /// regular elements that count towards the length of the bucket vector.
+    ///
+    /// Returns the index of the first stored value together with the stored
+    /// values or `None` if no following bucket is large enough to store all
+    /// values. Nothing is pushed in this case.
+    pub(crate) fn push_slice_padded(
+        &mut self,
+        values: &[T],
+        padding: &T,
+    ) -> Option<(usize, &mut [T])> {
+        if values.is_empty() {
+            return Some((self.len(), &mut []));
+        }
+        if values.len() > self.spare && !self.next_buckets_fit(values.len()) {
+            return None;
+        }
+        loop {
+            self.ensure_spare_bucket();
+            self.sync_spare();
+            if values.len() <= self.spare {
+                break;
+            }
+            let spare = self.spare;
+            self.push_clones(core::iter::repeat_n(padding, spare));
+        }
+        Some(self.push_slice_into_spare(values))
+    }
+
+    /// Returns `true` if any bucket following the last bucket is large
+    /// enough to hold `len` elements.
+    ///
+    /// Geometric schedules grow up to their maximum bucket capacity. Custom
+    /// schedules are followed for as long as their bucket capacities grow
+    /// and the spare capacity skipped on the way stays below twice `len`.
+    fn next_buckets_fit(&self, len: usize) -> bool {
+        if <C as BucketVecConfig>::GEOMETRIC {
+            return match config::max_bucket_capacity::<C>() {
+                Some(max_capacity) => len <= max_capacity,
+                None => true,
+            };
+        }
+        let mut skipped = self.spare;
+        let mut previous = 0;
+        for index in self.buckets.len().. {
+            let capacity = config::bucket_capacity::<C>(index);
+            if len <= capacity {
+                return true;
+            }
+            skipped = skipped.saturating_add(capacity);
+            if capacity <= previous || skipped >= len.saturating_mul(2) {
+                return false;
+            }
+            previous = capacity;
+        }
+        false
+    }
+
+    /// Clones the values into the spare capacity of the last bucket which
+    /// must be large enough to hold all of them.
+    ///
+    /// Returns the index of the first pushed value and the pushed values.
+    fn push_slice_into_spare(&mut self, values: &[T]) -> (usize, &mut [T]) {
+        let index = self.len();
+        self.push_clones(values);
+        self.sync_spare();
+        let bucket = self
+            .buckets
+            .last_mut()
+            .expect("encountered missing bucket of pushed values");
+        let start = bucket.len() - values.len();
+        (index, &mut bucket.as_mut_slice()[start..])
+    }
+
+    /// Clones and pushes all of the given values onto the bucket vector so
+    /// that they are stored contiguously within a single bucket.
+    ///
+    /// Returns the index of the first pushed value and the pushed values or
+    /// `None` if the values do not fit into a single bucket. Nothing is pushed
+    /// in this case.
+    ///
+    /// # Note
+    ///
+    /// The values are stored in the spare capacity of the last bucket or in
+    /// the next bucket if the last bucket is already full. Elements are always
+    /// stored in the order of their indices so the spare capacity of the last
+    /// bucket cannot be skipped.
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the bucket vector.
+    pub fn try_push_slice_contiguous(&mut self, values: &[T]) -> Option<(usize, &mut [T])> {
+        if values.is_empty() {
+            return Some((self.len(), &mut []));
+        }
+        if self.spare == 0 {
+            let new_bucket = self.next_bucket();
+            if values.len() > new_bucket.capacity() {
+                // Keep the bucket around for the next push.
+                self.reserved.push_front(new_bucket);
+                return None;
+            }
+            self.attach_bucket(new_bucket);
+            self.sync_spare();
+        } else if values.len() > self.spare {
+            return None;
+        }
+        Some(self.push_slice_into_spare(values))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C> BucketStorage<T> for BucketVec<T, C> {
+    fn parts(&self) -> Parts<'_, T> {
+        Parts {
+            len: self.len,
+            buckets: &self.buckets,
+            reserved: &self.reserved,
+        }
+    }
+
+    fn parts_mut(&mut self) -> PartsMut<'_, T> {
+        PartsMut {
+            len: &mut self.len,
+            buckets: &mut self.buckets,
+            offsets: &mut self.offsets,
+            reserved: &mut self.reserved,
+        }
+    }
+
+    fn sync(&mut self) {
+        self.sync_spare()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C> BucketSchedule<T> for BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    fn allocate_bucket(&self, index: usize) -> usize {
+        config::allocate_bucket::<C>(index)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C, const N: usize> From<[T; N]> for BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    fn from(array: [T; N]) -> Self {
+        IntoIterator::into_iter(array).collect()
+    }
+}
+
+/// Panics reporting an index that is out of bounds of a bucket vector.
+#[cfg(feature = "alloc")]
+#[cold]
+#[track_caller]
+fn index_out_of_bounds(index: usize, len: usize) -> ! {
+    panic!(
+        "index {} is out of bounds for bucket vector length {}",
+        index, len
+    )
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C> core::ops::Index<usize> for BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.bucket_entry_indices(index) {
+            Some((x, y)) => &self.buckets[x][y],
+            None => index_out_of_bounds(index, self.len()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C> core::ops::IndexMut<usize> for BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match self.bucket_entry_indices(index) {
+            Some((x, y)) => &mut self.buckets[x][y],
+            None => index_out_of_bounds(index, self.len()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl<T, C> core::iter::FromIterator<T> for BucketVec<T, C>
 where
     C: BucketVecConfig,
@@ -447,13 +2424,23 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, C> core::iter::Extend<T> for BucketVec<T, C>
 where
     C: BucketVecConfig,
 {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for item in iter {
-            self.push(item)
-        }
+        self.extend_from_iter(iter.into_iter())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, C> core::iter::Extend<&'a T> for BucketVec<T, C>
+where
+    T: Copy + 'a,
+    C: BucketVecConfig,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        <Self as core::iter::Extend<T>>::extend(self, iter.into_iter().copied())
     }
 }
@@ -0,0 +1,451 @@
+//! Append-only log persistence of bucket vectors.
+//!
+//! A log is a sequence of records that each start with a tag byte followed
+//! by the `bincode` encoded number of elements and the elements themselves.
+//! Append records extend the replayed bucket vector by their elements while
+//! snapshot records replace it.
+//!
+//! Records are replayed as a whole. A trailing record that has only been
+//! written partially, e.g. because of a crash while appending it, is a
+//! [`TornTail`] that is skipped by replaying.
+
+use super::{storage::BucketStorage, BucketVec, BucketVecConfig, DefaultConfig, Iter};
+use bincode::{
+    error::{DecodeError, EncodeError},
+    Decode, Encode,
+};
+use std::io;
+
+/// The tag of records appending their elements to the replayed bucket vector.
+const APPEND_RECORD: u8 = 0;
+
+/// The tag of records replacing the replayed bucket vector by their elements.
+const SNAPSHOT_RECORD: u8 = 1;
+
+/// Writes a record of the given kind holding the elements to the writer.
+fn write_record<'a, T, I, W>(tag: u8, elements: I, writer: &mut W) -> Result<(), EncodeError>
+where
+    T: Encode + 'a,
+    I: ExactSizeIterator<Item = &'a T>,
+    W: io::Write,
+{
+    let config = bincode::config::standard();
+    bincode::encode_into_std_write(tag, writer, config)?;
+    bincode::encode_into_std_write(elements.len() as u64, writer, config)?;
+    for element in elements {
+        bincode::encode_into_std_write(element, writer, config)?;
+    }
+    Ok(())
+}
+
+/// The incomplete trailing record of a log.
+///
+/// Returned by replaying a log whose last record has only been written
+/// partially. Truncate the log to [`offset`](TornTail::offset) bytes before
+/// appending to it again since records appended after the torn tail cannot
+/// be replayed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TornTail {
+    /// The byte offset of the incomplete record within the log.
+    offset: u64,
+    /// The number of complete records before the incomplete record.
+    records: usize,
+}
+
+impl TornTail {
+    /// Returns the byte offset of the incomplete record within the log.
+    ///
+    /// This is the length of the valid prefix of the log.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the number of complete records before the incomplete record.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+}
+
+/// A reader counting the number of bytes read from it.
+struct CountingReader<R> {
+    /// The counted reader.
+    reader: R,
+    /// The number of bytes read so far.
+    count: u64,
+}
+
+impl<R> io::Read for CountingReader<R>
+where
+    R: io::Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.count += read as u64;
+        Ok(read)
+    }
+}
+
+/// Returns `true` if the error has been caused by the log ending within a record.
+fn is_unexpected_end(error: &DecodeError) -> bool {
+    match error {
+        DecodeError::UnexpectedEnd { .. } => true,
+        DecodeError::Io { inner, .. } => inner.kind() == io::ErrorKind::UnexpectedEof,
+        _ => false,
+    }
+}
+
+/// Reads the tag of the next record or `None` if the log is exhausted.
+fn read_tag<R>(reader: &mut R) -> Result<Option<u8>, DecodeError>
+where
+    R: io::Read,
+{
+    let mut tag = [0x00];
+    loop {
+        match reader.read(&mut tag) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(tag[0])),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(inner) => {
+                return Err(DecodeError::Io {
+                    inner,
+                    additional: 1,
+                })
+            }
+        }
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    T: Encode,
+{
+    /// Appends a snapshot record of all elements to the log.
+    ///
+    /// # Note
+    ///
+    /// Replaying the log discards all records before the snapshot.
+    /// Snapshots allow to persist changes other than pushes and to start
+    /// compacted logs. Use a [`LoggedBucketVec`] in order to append only the
+    /// elements pushed since the last sync.
+    ///
+    /// # Errors
+    ///
+    /// If encoding an element or writing to the log fails.
+    pub fn snapshot_to_log<W>(&self, mut writer: W) -> Result<(), EncodeError>
+    where
+        W: io::Write,
+    {
+        write_record(SNAPSHOT_RECORD, self.iter(), &mut writer)
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    T: Decode<()>,
+    C: BucketVecConfig,
+{
+    /// Replays all complete records of the log and returns the resulting
+    /// bucket vector.
+    ///
+    /// Also returns the [`TornTail`] of the log if its last record is
+    /// incomplete, e.g. because writing it has been interrupted by a crash.
+    /// The incomplete record is not replayed.
+    ///
+    /// # Errors
+    ///
+    /// - If a record has an unknown tag.
+    /// - If decoding an element or reading from the log fails other than by
+    ///   the log ending within its last record.
+    pub fn replay_from_log<R>(reader: R) -> Result<(Self, Option<TornTail>), DecodeError>
+    where
+        R: io::Read,
+    {
+        let mut reader = CountingReader { reader, count: 0 };
+        let mut vec = Self::new();
+        let mut records = 0;
+        loop {
+            let offset = reader.count;
+            match replay_record(&mut reader, &mut vec) {
+                Ok(true) => records += 1,
+                Ok(false) => return Ok((vec, None)),
+                Err(error) if is_unexpected_end(&error) => {
+                    return Ok((vec, Some(TornTail { offset, records })))
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+/// Replays the next record of the log onto the bucket vector.
+///
+/// Returns `false` if the log is exhausted. The bucket vector is left
+/// unchanged unless the whole record has been read.
+fn replay_record<T, C, R>(reader: &mut R, vec: &mut BucketVec<T, C>) -> Result<bool, DecodeError>
+where
+    T: Decode<()>,
+    C: BucketVecConfig,
+    R: io::Read,
+{
+    let config = bincode::config::standard();
+    let tag = match read_tag(reader)? {
+        Some(tag) => tag,
+        None => return Ok(false),
+    };
+    if tag != APPEND_RECORD && tag != SNAPSHOT_RECORD {
+        return Err(DecodeError::OtherString(format!(
+            "encountered unknown log record tag {}",
+            tag
+        )));
+    }
+    let len: u64 = bincode::decode_from_std_read(reader, config)?;
+    // Elements are only applied once the record is known to be complete.
+    let mut elements = Vec::new();
+    for _ in 0..len {
+        elements.push(bincode::decode_from_std_read(reader, config)?);
+    }
+    if tag == SNAPSHOT_RECORD {
+        *vec = BucketVec::new();
+    }
+    vec.extend(elements);
+    Ok(true)
+}
+
+/// A bucket vector that keeps track of the elements it has synced to an
+/// append-only log.
+///
+/// [`append_to_log`](LoggedBucketVec::append_to_log) writes only the
+/// elements pushed since the last sync. Changes to already synced elements,
+/// i.e. mutable accesses and removals, cannot be appended. They are
+/// recorded and persisted by a snapshot record upon the next sync instead.
+#[derive(Debug, Clone)]
+pub struct LoggedBucketVec<T, C = DefaultConfig> {
+    /// The logged elements.
+    values: BucketVec<T, C>,
+    /// The number of elements synced to the log.
+    synced: usize,
+    /// Whether any synced element has been changed or removed since the last sync.
+    stale: bool,
+}
+
+impl<T, C> Default for LoggedBucketVec<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> From<BucketVec<T, C>> for LoggedBucketVec<T, C> {
+    /// Starts logging the bucket vector with none of its elements synced.
+    fn from(values: BucketVec<T, C>) -> Self {
+        Self {
+            values,
+            synced: 0,
+            stale: false,
+        }
+    }
+}
+
+impl<T, C> LoggedBucketVec<T, C> {
+    /// Creates a new empty logged bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self::from(BucketVec::new())
+    }
+
+    /// Returns the number of elements stored in the logged bucket vector.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the logged bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` if the log is in sync with the logged bucket vector.
+    pub fn is_synced(&self) -> bool {
+        !self.stale && self.synced == self.values.len()
+    }
+
+    /// Returns an iterator over shared references to the elements.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.values.iter()
+    }
+
+    /// Returns the underlying bucket vector.
+    pub fn as_bucket_vec(&self) -> &BucketVec<T, C> {
+        &self.values
+    }
+
+    /// Returns the underlying bucket vector and stops logging it.
+    pub fn into_bucket_vec(self) -> BucketVec<T, C> {
+        self.values
+    }
+}
+
+impl<T, C> LoggedBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    ///
+    /// # Note
+    ///
+    /// Accessing a synced element makes the next sync write a snapshot.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.synced {
+            self.stale = true;
+        }
+        self.values.get_mut(index)
+    }
+
+    /// Pushes the value and returns its index.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the logged bucket vector.
+    pub fn push(&mut self, new_value: T) -> usize {
+        let index = self.values.len();
+        self.values.push(new_value);
+        index
+    }
+
+    /// Removes the last element and returns it if any.
+    ///
+    /// # Note
+    ///
+    /// Removing a synced element makes the next sync write a snapshot.
+    pub fn pop(&mut self) -> Option<T> {
+        let popped = self.values.pop();
+        self.clamp_synced();
+        popped
+    }
+
+    /// Shortens the logged bucket vector to the given length dropping all
+    /// elements beyond it.
+    ///
+    /// # Note
+    ///
+    /// Removing synced elements makes the next sync write a snapshot.
+    pub fn truncate(&mut self, len: usize) {
+        self.values.truncate(len);
+        self.clamp_synced();
+    }
+
+    /// Marks the log as stale if synced elements have been removed.
+    fn clamp_synced(&mut self) {
+        if self.values.len() < self.synced {
+            self.synced = self.values.len();
+            self.stale = true;
+        }
+    }
+}
+
+impl<T, C> Extend<T> for LoggedBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.values.extend(iter)
+    }
+}
+
+impl<T, C> LoggedBucketVec<T, C>
+where
+    T: Encode,
+{
+    /// Syncs the log with the logged bucket vector.
+    ///
+    /// Appends a record of all elements pushed since the last sync. If synced
+    /// elements have been changed or removed since, a snapshot record of all
+    /// elements is written instead.
+    ///
+    /// # Note
+    ///
+    /// Only the new elements are written so that the log grows with the
+    /// bucket vector instead of being rewritten.
+    ///
+    /// # Errors
+    ///
+    /// If encoding an element or writing to the log fails. The log is
+    /// considered stale in this case so that the next sync writes a snapshot.
+    pub fn append_to_log<W>(&mut self, mut writer: W) -> Result<(), EncodeError>
+    where
+        W: io::Write,
+    {
+        if self.stale {
+            return self.snapshot_to_log(writer);
+        }
+        self.stale = true;
+        write_record(
+            APPEND_RECORD,
+            self.values.iter().skip(self.synced),
+            &mut writer,
+        )?;
+        self.mark_synced();
+        Ok(())
+    }
+
+    /// Appends a snapshot record of all elements to the log.
+    ///
+    /// # Errors
+    ///
+    /// If encoding an element or writing to the log fails. The log is
+    /// considered stale in this case so that the next sync writes a snapshot.
+    pub fn snapshot_to_log<W>(&mut self, writer: W) -> Result<(), EncodeError>
+    where
+        W: io::Write,
+    {
+        self.stale = true;
+        self.values.snapshot_to_log(writer)?;
+        self.mark_synced();
+        Ok(())
+    }
+
+    /// Records that all elements have been synced to the log.
+    fn mark_synced(&mut self) {
+        self.synced = self.values.len();
+        self.stale = false;
+    }
+}
+
+impl<T, C> LoggedBucketVec<T, C>
+where
+    T: Decode<()>,
+    C: BucketVecConfig,
+{
+    /// Replays all complete records of the log and returns the resulting
+    /// logged bucket vector in sync with the log.
+    ///
+    /// Also returns the [`TornTail`] of the log if its last record is
+    /// incomplete. The log must be truncated to the valid prefix before
+    /// syncing the logged bucket vector to it again.
+    ///
+    /// # Errors
+    ///
+    /// See [`BucketVec::replay_from_log`].
+    pub fn replay_from_log<R>(reader: R) -> Result<(Self, Option<TornTail>), DecodeError>
+    where
+        R: io::Read,
+    {
+        let (values, torn_tail) = BucketVec::replay_from_log(reader)?;
+        let logged = Self {
+            synced: values.len(),
+            values,
+            stale: false,
+        };
+        Ok((logged, torn_tail))
+    }
+}
@@ -0,0 +1,237 @@
+//! Insertion ordered map that never moves its values.
+
+use super::{BucketVec, BucketVecConfig, DefaultConfig, Iter};
+use core::{borrow::Borrow, hash::Hash};
+use std::collections::HashMap;
+
+/// An insertion ordered map that stores its entries in a bucket vector.
+///
+/// Entries are never moved after their insertion so references to values
+/// stay valid across insertions. Every entry is identified by its index in
+/// insertion order.
+///
+/// # Note
+///
+/// Entries cannot be removed since this would shift the indices of the
+/// following entries.
+#[derive(Debug, Clone)]
+pub struct BucketMap<K, V, C = DefaultConfig> {
+    /// The entries in insertion order.
+    entries: BucketVec<(K, V), C>,
+    /// The index of the entry of every key.
+    indices: HashMap<K, usize>,
+}
+
+impl<K, V, C> Default for BucketMap<K, V, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, C> BucketMap<K, V, C> {
+    /// Creates a new empty bucket map.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            entries: BucketVec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of entries stored in the bucket map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the bucket map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the keys and values in insertion order.
+    pub fn iter(&self) -> BucketMapIter<'_, K, V> {
+        BucketMapIter {
+            entries: self.entries.iter(),
+        }
+    }
+}
+
+impl<K, V, C> BucketMap<K, V, C>
+where
+    K: Hash + Eq,
+    C: BucketVecConfig,
+{
+    /// Returns the index of the entry of the given key if any.
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.indices.get(key).copied()
+    }
+
+    /// Returns `true` if the bucket map contains an entry for the given key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.indices.contains_key(key)
+    }
+
+    /// Returns a shared reference to the value of the given key if any.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.get_index_of(key)?;
+        self.get_index(index).map(|(_, value)| value)
+    }
+
+    /// Returns an exclusive reference to the value of the given key if any.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.get_index_of(key)?;
+        self.get_index_mut(index).map(|(_, value)| value)
+    }
+
+    /// Returns the key and a shared reference to the value of the entry at
+    /// the given index if any.
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.entries.get(index).map(|(key, value)| (key, value))
+    }
+
+    /// Returns the key and an exclusive reference to the value of the entry
+    /// at the given index if any.
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.entries
+            .get_mut(index)
+            .map(|(key, value)| (&*key, value))
+    }
+}
+
+impl<K, V, C> BucketMap<K, V, C>
+where
+    K: Hash + Eq + Clone,
+    C: BucketVecConfig,
+{
+    /// Inserts the value for the given key and returns the index of its entry
+    /// together with the previous value of the key if any.
+    ///
+    /// # Note
+    ///
+    /// The value of an already existing key is replaced in place.
+    /// This operation will never move other values, reallocates or otherwise
+    /// invalidate pointers of values contained by the bucket map.
+    pub fn insert(&mut self, key: K, value: V) -> (usize, Option<V>) {
+        match self.indices.get(&key) {
+            Some(&index) => {
+                let (_, old) = self
+                    .entries
+                    .get_mut(index)
+                    .expect("encountered missing entry of indexed key");
+                (index, Some(core::mem::replace(old, value)))
+            }
+            None => {
+                let index = self.entries.len();
+                self.indices.insert(key.clone(), index);
+                self.entries.push((key, value));
+                (index, None)
+            }
+        }
+    }
+
+    /// Returns an exclusive reference to the value of the given key after
+    /// inserting the value returned by `f` if the key is not yet present.
+    ///
+    /// # Note
+    ///
+    /// This operation will never move other values, reallocates or otherwise
+    /// invalidate pointers of values contained by the bucket map.
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        F: FnOnce() -> V,
+    {
+        let index = match self.indices.get(&key) {
+            Some(&index) => index,
+            None => {
+                let index = self.entries.len();
+                self.indices.insert(key.clone(), index);
+                self.entries.push((key, f()));
+                index
+            }
+        };
+        let (_, value) = self
+            .entries
+            .get_mut(index)
+            .expect("encountered missing entry of indexed key");
+        value
+    }
+}
+
+impl<K, V, C> Extend<(K, V)> for BucketMap<K, V, C>
+where
+    K: Hash + Eq + Clone,
+    C: BucketVecConfig,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V, C> core::iter::FromIterator<(K, V)> for BucketMap<K, V, C>
+where
+    K: Hash + Eq + Clone,
+    C: BucketVecConfig,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V, C> IntoIterator for &'a BucketMap<K, V, C> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = BucketMapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the keys and values of a bucket map in insertion order.
+#[derive(Debug, Clone)]
+pub struct BucketMapIter<'a, K, V> {
+    /// The entries in insertion order.
+    entries: Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for BucketMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(key, value)| (key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for BucketMapIter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back().map(|(key, value)| (key, value))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for BucketMapIter<'a, K, V> {}
@@ -1,81 +1,27 @@
-//! Utility module to help with `no_std` Rust not supporting certain float operations.
-
-/// Trait to either forward implementation to `std` library or `libm` for `no_std`.
-pub trait FloatExt {
-    /// Calculates the absolute value for the float.
-    fn abs(self) -> Self;
-    /// Returns the fraction part of the float.
-    fn fract(self) -> Self;
-    /// Rounds the float down to the next natural representable float.
-    fn floor(self) -> Self;
-    /// Rounds teh float up to the next natural representable float.
-    fn ceil(self) -> Self;
-    /// Calculates the natural logarithm of the float to the base.
-    fn log(self, base: Self) -> Self;
-    /// Caululates the logarithm of the float to base 2.
-    fn log2(self) -> Self;
-    /// Calculates the power of the float to the integer exponent.
-    fn powi(self, exp: i32) -> Self;
-}
-
-#[cfg(feature = "std")]
-impl FloatExt for f64 {
-    fn abs(self) -> Self {
-        f64::abs(self)
-    }
-
-    fn fract(self) -> Self {
-        f64::fract(self)
-    }
-
-    fn floor(self) -> Self {
-        f64::floor(self)
-    }
-
-    fn ceil(self) -> Self {
-        f64::ceil(self)
-    }
-
-    fn log(self, base: Self) -> Self {
-        f64::log(self, base)
-    }
-
-    fn log2(self) -> Self {
-        f64::log2(self)
-    }
-
-    fn powi(self, exp: i32) -> Self {
-        f64::powi(self, exp)
-    }
+//! Float utilities that only require basic arithmetic.
+//!
+//! This keeps `no_std` builds free of `libm` and yields identical results
+//! with and without the `std` crate feature.
+
+/// Returns `true` if the growth rate is equal to `1` within float precision.
+pub fn is_unit_growth_rate(growth_rate: f64) -> bool {
+    let difference = growth_rate - 1.0;
+    -f64::EPSILON < difference && difference < f64::EPSILON
 }
 
-#[cfg(not(feature = "std"))]
-impl FloatExt for f64 {
-    fn abs(self) -> Self {
-        libm::fabs(self)
-    }
-
-    fn fract(self) -> Self {
-        self - libm::trunc(self)
-    }
-
-    fn floor(self) -> Self {
-        libm::floor(self)
-    }
-
-    fn ceil(self) -> Self {
-        libm::ceil(self)
-    }
-
-    fn log(self, base: Self) -> Self {
-        libm::log(self) / libm::log(base)
-    }
-
-    fn log2(self) -> Self {
-        libm::log2(self)
-    }
-
-    fn powi(self, exp: i32) -> Self {
-        libm::pow(self, exp as f64)
+/// Raises the base to the power of the exponent.
+///
+/// Uses exponentiation by squaring in the same order as `f64::powi`.
+pub const fn powi(mut base: f64, mut exp: u32) -> f64 {
+    let mut result = 1.0;
+    loop {
+        if exp & 1 == 1 {
+            result *= base;
+        }
+        exp /= 2;
+        if exp == 0 {
+            return result;
+        }
+        base *= base;
     }
 }
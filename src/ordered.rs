@@ -0,0 +1,204 @@
+//! Sorted index over the elements of a bucket vector.
+
+use super::{BucketVec, BucketVecConfig};
+use core::{borrow::Borrow, ops::RangeBounds};
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::{btree_map, BTreeMap};
+
+#[cfg(feature = "std")]
+use std::collections::{btree_map, BTreeMap};
+
+/// A sorted index mapping keys to the indices of elements of a bucket vector.
+///
+/// The index is kept beside the bucket vector so that the elements can be
+/// looked up and iterated in key order while they stay pinned in place.
+///
+/// # Note
+///
+/// Every key refers to at most one element. The index is not updated by
+/// operations on the bucket vector itself and must be maintained through
+/// [`OrderedIndex::push`] or [`OrderedIndex::insert`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderedIndex<K> {
+    /// The index of the element of every key.
+    indices: BTreeMap<K, usize>,
+}
+
+impl<K> Default for OrderedIndex<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> OrderedIndex<K> {
+    /// Creates a new empty ordered index.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            indices: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of indexed keys.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if no keys are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+impl<K> OrderedIndex<K>
+where
+    K: Ord,
+{
+    /// Creates an ordered index over all elements of the bucket vector using
+    /// the keys extracted from the elements.
+    ///
+    /// Later elements win over earlier elements with the same key.
+    pub fn build<T, C, F>(vec: &BucketVec<T, C>, mut key_of: F) -> Self
+    where
+        F: FnMut(&T) -> K,
+    {
+        Self {
+            indices: vec
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (key_of(value), index))
+                .collect(),
+        }
+    }
+
+    /// Maps the key to the element index and returns the previously mapped
+    /// element index if any.
+    pub fn insert(&mut self, key: K, index: usize) -> Option<usize> {
+        self.indices.insert(key, index)
+    }
+
+    /// Pushes the value to the bucket vector, maps the key to it and returns
+    /// the index of the pushed value.
+    ///
+    /// # Note
+    ///
+    /// If the key was already mapped the previously mapped element stays in
+    /// the bucket vector but is no longer reachable through the index.
+    pub fn push<T, C>(&mut self, vec: &mut BucketVec<T, C>, key: K, new_value: T) -> usize
+    where
+        C: BucketVecConfig,
+    {
+        let index = vec.len();
+        vec.push(new_value);
+        self.indices.insert(key, index);
+        index
+    }
+
+    /// Returns the index of the element mapped to the key if any.
+    pub fn index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.indices.get(key).copied()
+    }
+
+    /// Returns a shared reference to the element of the bucket vector mapped
+    /// to the key if any.
+    pub fn get_by_key<'a, Q, T, C>(&self, vec: &'a BucketVec<T, C>, key: &Q) -> Option<&'a T>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: BucketVecConfig,
+    {
+        self.index_of(key).and_then(|index| vec.get(index))
+    }
+
+    /// Returns an iterator over the keys within the range and the elements of
+    /// the bucket vector mapped to them in key order.
+    ///
+    /// # Panics
+    ///
+    /// Under the same conditions as [`BTreeMap::range`].
+    pub fn range<'a, Q, R, T, C>(
+        &'a self,
+        vec: &'a BucketVec<T, C>,
+        range: R,
+    ) -> OrderedRange<'a, K, T, C>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        OrderedRange {
+            indices: self.indices.range(range),
+            vec,
+        }
+    }
+
+    /// Returns an iterator over all keys and the elements of the bucket vector
+    /// mapped to them in key order.
+    pub fn iter<'a, T, C>(&'a self, vec: &'a BucketVec<T, C>) -> OrderedRange<'a, K, T, C> {
+        OrderedRange {
+            indices: self.indices.range(..),
+            vec,
+        }
+    }
+}
+
+/// An iterator over keys and the elements of a bucket vector mapped to them
+/// in key order.
+#[derive(Debug)]
+pub struct OrderedRange<'a, K, T, C> {
+    /// The range of the ordered index.
+    indices: btree_map::Range<'a, K, usize>,
+    /// The bucket vector storing the elements.
+    vec: &'a BucketVec<T, C>,
+}
+
+impl<'a, K, T, C> Clone for OrderedRange<'a, K, T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            indices: self.indices.clone(),
+            vec: self.vec,
+        }
+    }
+}
+
+impl<'a, K, T, C> Iterator for OrderedRange<'a, K, T, C>
+where
+    C: BucketVecConfig,
+{
+    type Item = (&'a K, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, index) = self.indices.next()?;
+        let value = self
+            .vec
+            .get(*index)
+            .expect("encountered out of bounds index in ordered index");
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<'a, K, T, C> DoubleEndedIterator for OrderedRange<'a, K, T, C>
+where
+    C: BucketVecConfig,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (key, index) = self.indices.next_back()?;
+        let value = self
+            .vec
+            .get(*index)
+            .expect("encountered out of bounds index in ordered index");
+        Some((key, value))
+    }
+}
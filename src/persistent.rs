@@ -0,0 +1,143 @@
+//! Immutable bucket vector versions that share their buckets.
+
+use super::{config, BucketVecConfig, DefaultConfig};
+use core::marker::PhantomData;
+
+#[cfg(not(feature = "std"))]
+use alloc::{sync::Arc, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// An immutable version of a bucket vector that shares its buckets with
+/// all other versions it has been derived from.
+///
+/// Pushing onto a version returns a new version and leaves the original
+/// untouched. All full buckets are shared between the versions and only
+/// the last partially filled bucket is copied if it is shared.
+/// Every version can therefore be kept around cheaply, e.g. for undo
+/// histories or readers that observe a fixed length.
+#[derive(Debug)]
+pub struct PersistentBucketVec<T, C = DefaultConfig> {
+    /// The shared buckets of this version.
+    buckets: Vec<Arc<Vec<T>>>,
+    /// The number of elements of this version.
+    len: usize,
+    /// The configuration of the bucket capacities.
+    config: PhantomData<fn() -> C>,
+}
+
+impl<T, C> Clone for PersistentBucketVec<T, C> {
+    /// Clones the version sharing all of its buckets.
+    fn clone(&self) -> Self {
+        Self {
+            buckets: self.buckets.clone(),
+            len: self.len,
+            config: PhantomData,
+        }
+    }
+}
+
+impl<T, C> Default for PersistentBucketVec<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> PersistentBucketVec<T, C> {
+    /// Creates a new empty persistent bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            buckets: Vec::new(),
+            len: 0,
+            config: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements of this version.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this version is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over shared references to the elements of this version.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.buckets.iter().flat_map(|bucket| bucket.iter())
+    }
+}
+
+impl<T, C> PersistentBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        if core::mem::size_of::<T>() == 0 {
+            // Zero-sized elements are all stored in the first bucket.
+            return self.buckets[0].get(index);
+        }
+        let (x, y) = config::bucket_entry_indices::<C>(index);
+        self.buckets[x].get(y)
+    }
+}
+
+impl<T, C> PersistentBucketVec<T, C>
+where
+    T: Clone,
+    C: BucketVecConfig,
+{
+    /// Returns a new version with the value pushed onto this version.
+    ///
+    /// # Note
+    ///
+    /// Shares all full buckets with this version and copies the last bucket
+    /// if it still has spare capacity.
+    pub fn push(&self, new_value: T) -> Self {
+        let mut pushed = self.clone();
+        pushed.push_mut(new_value);
+        pushed
+    }
+
+    /// Pushes the value onto this version.
+    ///
+    /// # Note
+    ///
+    /// The last bucket is copied before the push only if it is shared with
+    /// other versions. Other versions are never affected.
+    pub fn push_mut(&mut self, new_value: T) {
+        let len_buckets = self.buckets.len();
+        let has_spare = |last: &Vec<T>| {
+            core::mem::size_of::<T>() == 0
+                || last.len() < config::bucket_capacity::<C>(len_buckets - 1)
+        };
+        match self.buckets.last_mut() {
+            Some(last) if has_spare(last) => match Arc::get_mut(last) {
+                Some(bucket) => bucket.push(new_value),
+                None => {
+                    let capacity = config::bucket_capacity::<C>(len_buckets - 1);
+                    let mut bucket = Vec::with_capacity(capacity);
+                    bucket.extend_from_slice(last);
+                    bucket.push(new_value);
+                    *last = Arc::new(bucket);
+                }
+            },
+            _ => {
+                let mut bucket = Vec::with_capacity(config::allocate_bucket::<C>(len_buckets));
+                bucket.push(new_value);
+                self.buckets.push(Arc::new(bucket));
+            }
+        }
+        self.len += 1;
+    }
+}
@@ -0,0 +1,140 @@
+//! Recycling of buckets across bucket vectors.
+
+use super::{config, Bucket, BucketVec, BucketVecConfig};
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// A pool of empty buckets that bucket vectors return their buckets to and
+/// draw new buckets from.
+///
+/// This avoids allocator pressure when many bucket vectors are created and
+/// destroyed in quick succession. Buckets are pooled by their capacity so
+/// that they can only be reused at positions of the same capacity.
+#[derive(Debug)]
+pub struct BucketPool<T> {
+    /// The pooled empty buckets by their capacity.
+    buckets: BTreeMap<usize, Vec<Bucket<T>>>,
+    /// The number of pooled buckets.
+    len: usize,
+}
+
+impl<T> Default for BucketPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> BucketPool<T> {
+    /// Creates a new empty bucket pool.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            buckets: BTreeMap::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of pooled buckets.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no buckets are pooled.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the sum of the capacities of all pooled buckets.
+    ///
+    /// # Note
+    ///
+    /// Saturates at `usize::MAX` since buckets of zero-sized elements each
+    /// have a capacity of `usize::MAX`.
+    pub fn capacity(&self) -> usize {
+        self.buckets
+            .iter()
+            .map(|(capacity, buckets)| capacity.saturating_mul(buckets.len()))
+            .fold(0, usize::saturating_add)
+    }
+
+    /// Frees all pooled buckets.
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.len = 0;
+    }
+
+    /// Empties the bucket and adds it to the pool.
+    fn put(&mut self, mut bucket: Bucket<T>) {
+        bucket.truncate(0);
+        self.buckets
+            .entry(bucket.capacity())
+            .or_default()
+            .push(bucket);
+        self.len += 1;
+    }
+
+    /// Removes a pooled bucket with the given capacity if any.
+    fn take(&mut self, capacity: usize) -> Option<Bucket<T>> {
+        let buckets = self.buckets.get_mut(&capacity)?;
+        let bucket = buckets.pop()?;
+        if buckets.is_empty() {
+            self.buckets.remove(&capacity);
+        }
+        self.len -= 1;
+        Some(bucket)
+    }
+}
+
+impl<T, C> BucketVec<T, C> {
+    /// Removes all elements and returns all buckets including the reserved
+    /// ones to the pool.
+    pub fn clear_into(&mut self, pool: &mut BucketPool<T>) {
+        for bucket in self.buckets.drain(..).chain(self.reserved.drain(..)) {
+            pool.put(bucket);
+        }
+        self.offsets.clear();
+        self.len = 0;
+        self.sync_spare();
+    }
+
+    /// Drops the bucket vector returning all of its buckets to the pool.
+    pub fn recycle(mut self, pool: &mut BucketPool<T>) {
+        self.clear_into(pool);
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Reserves capacity for at least `additional` more elements drawing the
+    /// buckets from the pool.
+    ///
+    /// # Note
+    ///
+    /// Buckets missing from the pool are allocated as usual.
+    /// The reserved buckets only become part of the bucket vector once pushed
+    /// elements require them.
+    pub fn reserve_from_pool(&mut self, additional: usize, pool: &mut BucketPool<T>) {
+        let required = self.len().saturating_add(additional);
+        let mut capacity = self.capacity();
+        while capacity < required {
+            let index = self.buckets.len() + self.reserved.len();
+            let new_capacity = config::bucket_capacity::<C>(index);
+            let bucket = match pool.take(new_capacity) {
+                Some(bucket) => bucket,
+                None => Bucket::new(config::allocate_bucket::<C>(index)),
+            };
+            capacity = capacity.saturating_add(new_capacity);
+            self.reserved.push_back(bucket);
+        }
+        self.check_stability();
+    }
+}
@@ -0,0 +1,77 @@
+//! Proptest strategies for bucket vectors.
+
+use super::{config, BucketVec, BucketVecConfig};
+use proptest::{
+    collection::{vec, SizeRange},
+    sample::select,
+    strategy::{Strategy, Union},
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Creates a strategy to generate bucket vectors with elements drawn from
+/// `element` and a length within `size`.
+///
+/// # Note
+///
+/// Half of the generated bucket vectors have a length right at, before or
+/// after the boundary of one of their buckets according to the configuration
+/// `C`, since these are the lengths where most edge cases are hiding.
+///
+/// # Example
+///
+/// ```
+/// # use bucket_vec::{bucket_vec_strategy, DefaultConfig};
+/// # use proptest::prelude::*;
+/// proptest! {
+///     fn push_works(mut vec in bucket_vec_strategy::<_, DefaultConfig>(any::<u8>(), 0..100)) {
+///         let len = vec.len();
+///         vec.push(42);
+///         prop_assert_eq!(vec.last(), Some(&42));
+///         prop_assert_eq!(vec.len(), len + 1);
+///     }
+/// }
+/// # push_works();
+/// ```
+pub fn bucket_vec_strategy<S, C>(
+    element: S,
+    size: impl Into<SizeRange>,
+) -> impl Strategy<Value = BucketVec<S::Value, C>>
+where
+    S: Strategy + Clone,
+    C: BucketVecConfig,
+{
+    let (start, end) = size.into().start_end_incl();
+    let boundaries = boundary_lens::<C>(start, end);
+    let lens = if boundaries.is_empty() {
+        (start..=end).boxed()
+    } else {
+        Union::new(vec![(start..=end).boxed(), select(boundaries).boxed()]).boxed()
+    };
+    lens.prop_flat_map(move |len| vec(element.clone(), len))
+        .prop_map(|values| values.into_iter().collect())
+}
+
+/// Returns all lengths within `start..=end` that are right at, before or after
+/// the boundary of a bucket of the configuration `C`.
+fn boundary_lens<C>(start: usize, end: usize) -> Vec<usize>
+where
+    C: BucketVecConfig,
+{
+    let mut lens = Vec::new();
+    for index in 1.. {
+        let boundary = match config::checked_total_capacity::<C>(index) {
+            Some(boundary) if boundary - 1 <= end => boundary,
+            _ => break,
+        };
+        lens.extend(
+            [boundary - 1, boundary, boundary.saturating_add(1)]
+                .iter()
+                .copied()
+                .filter(|len| (start..=end).contains(len)),
+        );
+    }
+    lens.dedup();
+    lens
+}
@@ -0,0 +1,59 @@
+//! Shuffling and random sampling of bucket vectors using `rand`.
+
+use super::{BucketVec, BucketVecConfig};
+use rand::{seq::index, Rng};
+
+impl<T, C> BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Shuffles the elements of the bucket vector in place.
+    ///
+    /// # Note
+    ///
+    /// The values of the elements are swapped between their slots which
+    /// themselves stay where they are. Pointers to elements therefore stay
+    /// valid but afterwards point to different values.
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        for index in (1..self.len()).rev() {
+            let other = rng.gen_range(0, index + 1);
+            self.swap_values(index, other);
+        }
+    }
+
+    /// Returns a shared reference to a random element or `None` if the
+    /// bucket vector is empty.
+    pub fn choose<R>(&self, rng: &mut R) -> Option<&T>
+    where
+        R: Rng + ?Sized,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        self.get(rng.gen_range(0, self.len()))
+    }
+
+    /// Returns an iterator over `amount` distinct random elements.
+    ///
+    /// Yields all elements in random order if `amount` exceeds the length
+    /// of the bucket vector.
+    ///
+    /// # Note
+    ///
+    /// Allocates a temporary buffer for the sampled indices.
+    pub fn choose_multiple<R>(&self, rng: &mut R, amount: usize) -> impl Iterator<Item = &T>
+    where
+        R: Rng + ?Sized,
+    {
+        let amount = core::cmp::min(amount, self.len());
+        index::sample(rng, self.len(), amount)
+            .into_iter()
+            .map(move |index| {
+                self.get(index)
+                    .expect("encountered out of bounds sampled index")
+            })
+    }
+}
@@ -0,0 +1,170 @@
+//! Interoperability of bucket vectors with raw buckets.
+
+use super::{config, storage::BucketStorage, Bucket, BucketVec, BucketVecConfig};
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Errors returned by [`BucketVec::from_buckets`] and
+/// [`BucketVec::from_raw_parts`] for invalid bucket layouts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LayoutError {
+    /// The capacity of a bucket does not match the configuration.
+    CapacityMismatch {
+        /// The index of the bucket.
+        bucket_index: usize,
+        /// The capacity required by the configuration.
+        expected: usize,
+        /// The actual capacity of the bucket.
+        found: usize,
+    },
+    /// A bucket is not full although it is followed by a non-empty bucket.
+    IncompleteBucket {
+        /// The index of the bucket.
+        bucket_index: usize,
+    },
+    /// The length does not match the number of elements of all buckets.
+    LengthMismatch {
+        /// The given length.
+        expected: usize,
+        /// The number of elements of all buckets.
+        found: usize,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CapacityMismatch {
+                bucket_index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "bucket {} has a capacity of {} but the configuration requires {}",
+                bucket_index, found, expected
+            ),
+            Self::IncompleteBucket { bucket_index } => write!(
+                f,
+                "bucket {} is not full but followed by a non-empty bucket",
+                bucket_index
+            ),
+            Self::LengthMismatch { expected, found } => write!(
+                f,
+                "length {} does not match the {} elements of all buckets",
+                expected, found
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutError {}
+
+impl<T, C> BucketVec<T, C> {
+    /// Returns the elements of every bucket as vectors with the capacity of
+    /// their bucket.
+    ///
+    /// # Note
+    ///
+    /// Reserved buckets are dropped.
+    pub fn into_buckets(self) -> Vec<Vec<T>> {
+        let (buckets, _) = self.into_entry_vector();
+        buckets.into_iter().map(Bucket::into_vec).collect()
+    }
+
+    /// Decomposes the bucket vector into its length and the elements of
+    /// every bucket as vectors with the capacity of their bucket.
+    ///
+    /// Reassemble the bucket vector with [`BucketVec::from_raw_parts`].
+    ///
+    /// # Note
+    ///
+    /// Reserved buckets are returned as trailing empty buckets so that no
+    /// capacity is lost. No element is moved.
+    pub fn into_raw_parts(mut self) -> (usize, Vec<Vec<T>>) {
+        let reserved = core::mem::take(&mut self.reserved);
+        let (buckets, len) = self.into_entry_vector();
+        let buckets = buckets
+            .into_iter()
+            .chain(reserved)
+            .map(Bucket::into_vec)
+            .collect();
+        (len, buckets)
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Creates a bucket vector from the given buckets, e.g. as returned by
+    /// [`BucketVec::into_buckets`].
+    ///
+    /// Trailing empty buckets are kept as reserved buckets.
+    ///
+    /// # Note
+    ///
+    /// Zero-sized elements are always stored within the first bucket which
+    /// is why their buckets are merged without any validation.
+    ///
+    /// # Errors
+    ///
+    /// - If the capacity of any bucket does not match the configuration.
+    /// - If any bucket is not full but followed by a non-empty bucket.
+    pub fn from_buckets(buckets: Vec<Vec<T>>) -> Result<Self, LayoutError> {
+        if core::mem::size_of::<T>() == 0 {
+            return Ok(buckets.into_iter().flatten().collect());
+        }
+        let len_used = buckets
+            .iter()
+            .rposition(|bucket| !bucket.is_empty())
+            .map(|last| last + 1)
+            .unwrap_or(0);
+        for (bucket_index, bucket) in buckets.iter().enumerate() {
+            let expected = config::bucket_capacity::<C>(bucket_index);
+            if bucket.capacity() != expected {
+                return Err(LayoutError::CapacityMismatch {
+                    bucket_index,
+                    expected,
+                    found: bucket.capacity(),
+                });
+            }
+            if bucket_index + 1 < len_used && bucket.len() != expected {
+                return Err(LayoutError::IncompleteBucket { bucket_index });
+            }
+        }
+        let mut vec = Self::new();
+        for (bucket_index, bucket) in buckets.into_iter().enumerate() {
+            if bucket_index < len_used {
+                vec.len += bucket.len();
+                vec.attach_bucket(Bucket::from_vec(bucket));
+            } else {
+                vec.reserved.push_back(Bucket::from_vec(bucket));
+            }
+        }
+        vec.sync_spare();
+        Ok(vec)
+    }
+
+    /// Reassembles a bucket vector from its length and buckets as returned
+    /// by [`BucketVec::into_raw_parts`].
+    ///
+    /// Trailing empty buckets are kept as reserved buckets.
+    ///
+    /// # Errors
+    ///
+    /// - If the length does not match the number of elements of all buckets.
+    /// - If the buckets are invalid for [`BucketVec::from_buckets`].
+    pub fn from_raw_parts(len: usize, buckets: Vec<Vec<T>>) -> Result<Self, LayoutError> {
+        let found = buckets.iter().map(Vec::len).sum();
+        if len != found {
+            return Err(LayoutError::LengthMismatch {
+                expected: len,
+                found,
+            });
+        }
+        Self::from_buckets(buckets)
+    }
+}
@@ -0,0 +1,168 @@
+//! Parallel iteration over bucket vectors using `rayon`.
+//!
+//! Work is split along bucket boundaries first and then further within
+//! large buckets.
+
+use super::{Bucket, BucketVec};
+use rayon::iter::{
+    plumbing::UnindexedConsumer, FlatMap, IntoParallelIterator, IntoParallelRefIterator,
+    IntoParallelRefMutIterator, ParallelIterator,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The parallel iterator over the shared entries of all buckets.
+type FlatEntries<'a, T> = FlatMap<rayon::slice::Iter<'a, Bucket<T>>, fn(&'a Bucket<T>) -> &'a [T]>;
+
+/// The parallel iterator over the exclusive entries of all buckets.
+type FlatEntriesMut<'a, T> =
+    FlatMap<rayon::slice::IterMut<'a, Bucket<T>>, fn(&'a mut Bucket<T>) -> &'a mut [T]>;
+
+/// The parallel iterator over the owned entries of all buckets.
+type FlatIntoEntries<T> = FlatMap<rayon::vec::IntoIter<Bucket<T>>, fn(Bucket<T>) -> Vec<T>>;
+
+/// A parallel iterator yielding shared references to the elements of a bucket vector.
+#[derive(Debug)]
+pub struct ParIter<'a, T>
+where
+    T: Sync,
+{
+    /// The parallel iterator over all entries of all buckets.
+    entries: FlatEntries<'a, T>,
+}
+
+impl<'a, T> ParIter<'a, T>
+where
+    T: Sync,
+{
+    /// Creates a new parallel iterator over the bucket vector.
+    pub(crate) fn new<C>(vec: &'a BucketVec<T, C>) -> Self {
+        Self {
+            entries: vec.buckets.par_iter().flat_map(Bucket::as_slice),
+        }
+    }
+}
+
+impl<'a, T> ParallelIterator for ParIter<'a, T>
+where
+    T: Sync,
+{
+    type Item = &'a T;
+
+    fn drive_unindexed<Cs>(self, consumer: Cs) -> Cs::Result
+    where
+        Cs: UnindexedConsumer<Self::Item>,
+    {
+        self.entries.drive_unindexed(consumer)
+    }
+}
+
+/// A parallel iterator yielding exclusive references to the elements of a bucket vector.
+#[derive(Debug)]
+pub struct ParIterMut<'a, T>
+where
+    T: Send,
+{
+    /// The parallel iterator over all entries of all buckets.
+    entries: FlatEntriesMut<'a, T>,
+}
+
+impl<'a, T> ParIterMut<'a, T>
+where
+    T: Send,
+{
+    /// Creates a new parallel iterator over the bucket vector.
+    pub(crate) fn new<C>(vec: &'a mut BucketVec<T, C>) -> Self {
+        Self {
+            entries: vec.buckets.par_iter_mut().flat_map(Bucket::as_mut_slice),
+        }
+    }
+}
+
+impl<'a, T> ParallelIterator for ParIterMut<'a, T>
+where
+    T: Send,
+{
+    type Item = &'a mut T;
+
+    fn drive_unindexed<Cs>(self, consumer: Cs) -> Cs::Result
+    where
+        Cs: UnindexedConsumer<Self::Item>,
+    {
+        self.entries.drive_unindexed(consumer)
+    }
+}
+
+/// A parallel iterator yielding the elements of a bucket vector by value.
+#[derive(Debug)]
+pub struct IntoParIter<T>
+where
+    T: Send,
+{
+    /// The parallel iterator over all entries of all buckets.
+    entries: FlatIntoEntries<T>,
+}
+
+impl<T> IntoParIter<T>
+where
+    T: Send,
+{
+    /// Creates a new parallel iterator over the bucket vector.
+    pub(crate) fn new<C>(vec: BucketVec<T, C>) -> Self {
+        let (buckets, _) = vec.into_entry_vector();
+        Self {
+            entries: buckets.into_par_iter().flat_map(Bucket::into_vec),
+        }
+    }
+}
+
+impl<T> ParallelIterator for IntoParIter<T>
+where
+    T: Send,
+{
+    type Item = T;
+
+    fn drive_unindexed<Cs>(self, consumer: Cs) -> Cs::Result
+    where
+        Cs: UnindexedConsumer<Self::Item>,
+    {
+        self.entries.drive_unindexed(consumer)
+    }
+}
+
+impl<T, C> IntoParallelIterator for BucketVec<T, C>
+where
+    T: Send,
+{
+    type Iter = IntoParIter<T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        IntoParIter::new(self)
+    }
+}
+
+impl<'a, T, C> IntoParallelIterator for &'a BucketVec<T, C>
+where
+    T: Sync,
+{
+    type Iter = ParIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter::new(self)
+    }
+}
+
+impl<'a, T, C> IntoParallelIterator for &'a mut BucketVec<T, C>
+where
+    T: Send,
+{
+    type Iter = ParIterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterMut::new(self)
+    }
+}
@@ -0,0 +1,319 @@
+//! Reflection of bucket vectors as lists through `bevy_reflect`.
+
+use super::{storage::BucketStorage, BucketVec, BucketVecConfig};
+use bevy_reflect::{
+    utility::{GenericTypeInfoCell, GenericTypePathCell},
+    ApplyError, FromReflect, FromType, Generics, GetTypeRegistration, List, ListInfo, ListIter,
+    MaybeTyped, PartialReflect, Reflect, ReflectCloneError, ReflectFromPtr, ReflectFromReflect,
+    ReflectKind, ReflectMut, ReflectOwned, ReflectRef, TypeInfo, TypeParamInfo, TypePath,
+    TypeRegistration, TypeRegistry, Typed,
+};
+use core::any::{self, Any};
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, boxed::Box, format, string::ToString, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+/// Returns the element of a reflected list as a value of type `T`.
+///
+/// # Panics
+///
+/// If the value is neither of type `T` nor convertible into it.
+fn take_element<T>(value: Box<dyn PartialReflect>) -> T
+where
+    T: FromReflect,
+{
+    T::take_from_reflect(value).unwrap_or_else(|value| {
+        panic!(
+            "Attempted to store invalid value of type {}.",
+            value.reflect_type_path()
+        )
+    })
+}
+
+/// Returns the type name of the configuration without its module path.
+fn short_config_name<C>() -> &'static str
+where
+    C: 'static,
+{
+    let name = any::type_name::<C>();
+    let generics = name.find('<').unwrap_or(name.len());
+    match name[..generics].rfind("::") {
+        Some(module) => &name[module + 2..],
+        None => name,
+    }
+}
+
+impl<T, C> TypePath for BucketVec<T, C>
+where
+    T: TypePath,
+    C: 'static,
+{
+    /// Returns the path of the bucket vector type.
+    ///
+    /// The configuration is named by its `core::any::type_name` so that
+    /// custom configurations do not have to implement `TypePath`.
+    fn type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            format!(
+                "bucket_vec::BucketVec<{}, {}>",
+                T::type_path(),
+                any::type_name::<C>()
+            )
+        })
+    }
+
+    fn short_type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            format!(
+                "BucketVec<{}, {}>",
+                T::short_type_path(),
+                short_config_name::<C>()
+            )
+        })
+    }
+
+    fn type_ident() -> Option<&'static str> {
+        Some("BucketVec")
+    }
+
+    fn crate_name() -> Option<&'static str> {
+        Some("bucket_vec")
+    }
+
+    fn module_path() -> Option<&'static str> {
+        Some("bucket_vec")
+    }
+}
+
+impl<T, C> List for BucketVec<T, C>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+    C: BucketVecConfig + 'static,
+{
+    fn get(&self, index: usize) -> Option<&dyn PartialReflect> {
+        BucketVec::get(self, index).map(|value| value as &dyn PartialReflect)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn PartialReflect> {
+        BucketVec::get_mut(self, index).map(|value| value as &mut dyn PartialReflect)
+    }
+
+    /// Inserts the value at the given index.
+    ///
+    /// # Note
+    ///
+    /// This moves all elements at and after `index`, see
+    /// [`BucketVec::insert_shift`].
+    fn insert(&mut self, index: usize, value: Box<dyn PartialReflect>) {
+        self.insert_shift(index, take_element(value));
+    }
+
+    /// Removes the value at the given index.
+    ///
+    /// # Note
+    ///
+    /// This moves all elements after `index`, see [`BucketVec::remove_shift`].
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn remove(&mut self, index: usize) -> Box<dyn PartialReflect> {
+        let len = self.len();
+        match self.remove_shift(index) {
+            Some(removed) => Box::new(removed),
+            None => panic!("removal index (is {}) should be < len (is {})", index, len),
+        }
+    }
+
+    fn push(&mut self, value: Box<dyn PartialReflect>) {
+        BucketVec::push(self, take_element(value));
+    }
+
+    fn pop(&mut self) -> Option<Box<dyn PartialReflect>> {
+        BucketStorage::pop(self).map(|value| Box::new(value) as Box<dyn PartialReflect>)
+    }
+
+    fn len(&self) -> usize {
+        BucketVec::len(self)
+    }
+
+    fn iter(&self) -> ListIter<'_> {
+        ListIter::new(self)
+    }
+
+    fn drain(&mut self) -> Vec<Box<dyn PartialReflect>> {
+        core::mem::take(self)
+            .into_iter()
+            .map(|value| Box::new(value) as Box<dyn PartialReflect>)
+            .collect()
+    }
+}
+
+impl<T, C> PartialReflect for BucketVec<T, C>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+    C: BucketVecConfig + 'static,
+{
+    fn get_represented_type_info(&self) -> Option<&'static TypeInfo> {
+        Some(<Self as Typed>::type_info())
+    }
+
+    fn into_partial_reflect(self: Box<Self>) -> Box<dyn PartialReflect> {
+        self
+    }
+
+    fn as_partial_reflect(&self) -> &dyn PartialReflect {
+        self
+    }
+
+    fn as_partial_reflect_mut(&mut self) -> &mut dyn PartialReflect {
+        self
+    }
+
+    fn try_into_reflect(self: Box<Self>) -> Result<Box<dyn Reflect>, Box<dyn PartialReflect>> {
+        Ok(self)
+    }
+
+    fn try_as_reflect(&self) -> Option<&dyn Reflect> {
+        Some(self)
+    }
+
+    fn try_as_reflect_mut(&mut self) -> Option<&mut dyn Reflect> {
+        Some(self)
+    }
+
+    fn reflect_kind(&self) -> ReflectKind {
+        ReflectKind::List
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::List(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::List(self)
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::List(self)
+    }
+
+    fn reflect_clone(&self) -> Result<Box<dyn Reflect>, ReflectCloneError> {
+        let cloned = BucketVec::iter(self)
+            .map(|value| {
+                value
+                    .reflect_clone()?
+                    .take()
+                    .map_err(|_| ReflectCloneError::FailedDowncast {
+                        expected: Cow::Borrowed(T::type_path()),
+                        received: Cow::Owned(value.reflect_type_path().to_string()),
+                    })
+            })
+            .collect::<Result<Self, ReflectCloneError>>()?;
+        Ok(Box::new(cloned))
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        bevy_reflect::list_hash(self)
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn PartialReflect) -> Option<bool> {
+        bevy_reflect::list_partial_eq(self, value)
+    }
+
+    fn apply(&mut self, value: &dyn PartialReflect) {
+        bevy_reflect::list_apply(self, value);
+    }
+
+    fn try_apply(&mut self, value: &dyn PartialReflect) -> Result<(), ApplyError> {
+        bevy_reflect::list_try_apply(self, value)
+    }
+}
+
+impl<T, C> Reflect for BucketVec<T, C>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+    C: BucketVecConfig + 'static,
+{
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        self
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = <dyn Reflect>::take(value)?;
+        Ok(())
+    }
+}
+
+impl<T, C> Typed for BucketVec<T, C>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+    C: BucketVecConfig + 'static,
+{
+    fn type_info() -> &'static TypeInfo {
+        static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            TypeInfo::List(
+                ListInfo::new::<Self, T>()
+                    .with_generics(Generics::new().with(TypeParamInfo::new::<T>("T"))),
+            )
+        })
+    }
+}
+
+impl<T, C> GetTypeRegistration for BucketVec<T, C>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+    C: BucketVecConfig + 'static,
+{
+    fn get_type_registration() -> TypeRegistration {
+        let mut registration = TypeRegistration::of::<Self>();
+        registration.insert::<ReflectFromPtr>(FromType::<Self>::from_type());
+        registration.insert::<ReflectFromReflect>(FromType::<Self>::from_type());
+        registration
+    }
+
+    fn register_type_dependencies(registry: &mut TypeRegistry) {
+        registry.register::<T>();
+    }
+}
+
+impl<T, C> FromReflect for BucketVec<T, C>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+    C: BucketVecConfig + 'static,
+{
+    fn from_reflect(reflect: &dyn PartialReflect) -> Option<Self> {
+        let list = reflect.reflect_ref().as_list().ok()?;
+        let mut vec = BucketVec::new();
+        for element in list.iter() {
+            vec.push(T::from_reflect(element)?);
+        }
+        Some(vec)
+    }
+}
@@ -1,28 +1,234 @@
-use super::{BucketVec, BucketVecConfig};
+use super::{
+    config,
+    storage::BucketSchedule,
+    with_layout::{LayoutHeader, WithLayout},
+    BucketVec, BucketVecConfig, CappedBucketVec,
+};
+use core::convert::TryFrom;
+use scale::{CompactLen as _, DecodeLength, EncodeLike, MaxEncodedLen};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 impl<T, C> scale::Encode for BucketVec<T, C>
 where
     T: scale::Encode,
 {
-    fn encode_to<O: scale::Output>(&self, output: &mut O) {
-        output.push(&scale::Compact(self.len() as u64));
+    fn size_hint(&self) -> usize {
+        let len_prefix = <scale::Compact<u64>>::compact_len(&(self.len() as u64));
+        self.iter()
+            .fold(len_prefix, |size, elem| size + elem.size_hint())
+    }
+
+    fn encode_to<O: scale::Output + ?Sized>(&self, output: &mut O) {
+        scale::Compact(self.len() as u64).encode_to(output);
         for elem in self {
-            output.push(elem);
+            elem.encode_to(output);
         }
     }
 }
 
+/// Bucket vectors are encoded exactly like vectors.
+impl<T, U, C> EncodeLike<Vec<U>> for BucketVec<T, C>
+where
+    T: EncodeLike<U>,
+    U: scale::Encode,
+{
+}
+
 impl<T, C> scale::Decode for BucketVec<T, C>
 where
     C: BucketVecConfig,
     T: scale::Decode,
 {
     fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
-        let len = <scale::Compact<u64> as scale::Decode>::decode(input)?.0;
-        let mut vec = Self::new();
-        for _ in 0..len {
-            vec.push(<T as scale::Decode>::decode(input)?);
+        let len = decode_len(input)?;
+        decode_elements(input, len)
+    }
+}
+
+/// Decodes the length prefix of an encoded bucket vector.
+fn decode_len<I: scale::Input>(input: &mut I) -> Result<usize, scale::Error> {
+    let len = <scale::Compact<u64> as scale::Decode>::decode(input)?.0;
+    usize::try_from(len).map_err(|_| "bucket vector length exceeds usize".into())
+}
+
+/// Decodes `len` elements following the length prefix of an encoded bucket vector.
+///
+/// Buckets are allocated one at a time once decoded elements require them
+/// so that the announced length is never trusted. Every bucket allocation is
+/// reported to the input beforehand.
+fn decode_elements<T, C, I>(input: &mut I, len: usize) -> Result<BucketVec<T, C>, scale::Error>
+where
+    C: BucketVecConfig,
+    T: scale::Decode,
+    I: scale::Input,
+{
+    let mut vec = BucketVec::new();
+    let mut remaining = len;
+    while remaining > 0 {
+        if vec.next_push_allocates() {
+            let capacity = config::bucket_capacity::<C>(vec.buckets.len());
+            input.on_before_alloc_mem(capacity.saturating_mul(core::mem::size_of::<T>()))?;
+        }
+        remaining -= vec.try_fill_bucket(remaining, || <T as scale::Decode>::decode(input))?;
+    }
+    Ok(vec)
+}
+
+// `MaxEncodedLen` is only implemented for `CappedBucketVec` since no
+// operation of a plain bucket vector enforces an upper bound on its length.
+impl<T, C> DecodeLength for BucketVec<T, C> {
+    fn len(mut self_encoded: &[u8]) -> Result<usize, scale::Error> {
+        let len = <scale::Compact<u64> as scale::Decode>::decode(&mut self_encoded)?.0;
+        usize::try_from(len).map_err(|_| "bucket vector length exceeds usize".into())
+    }
+}
+
+/// Bucket vectors have the same type information as sequences.
+impl<T, C> scale_info::TypeInfo for BucketVec<T, C>
+where
+    T: scale_info::TypeInfo + 'static,
+{
+    type Identity = [T];
+
+    fn type_info() -> scale_info::Type {
+        <[T] as scale_info::TypeInfo>::type_info()
+    }
+}
+
+impl scale::Encode for LayoutHeader {
+    fn size_hint(&self) -> usize {
+        self.version.size_hint()
+            + self.starting_capacity.size_hint()
+            + self.growth_rate.to_bits().size_hint()
+            + self.max_bucket_capacity.size_hint()
+            + self.rounding.size_hint()
+            + self.rounding_quantum.size_hint()
+            + self.geometric.size_hint()
+    }
+
+    fn encode_to<O: scale::Output + ?Sized>(&self, output: &mut O) {
+        self.version.encode_to(output);
+        self.starting_capacity.encode_to(output);
+        self.growth_rate.to_bits().encode_to(output);
+        self.max_bucket_capacity.encode_to(output);
+        self.rounding.encode_to(output);
+        self.rounding_quantum.encode_to(output);
+        self.geometric.encode_to(output);
+    }
+}
+
+impl scale::Decode for LayoutHeader {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        Ok(Self {
+            version: scale::Decode::decode(input)?,
+            starting_capacity: scale::Decode::decode(input)?,
+            growth_rate: f64::from_bits(scale::Decode::decode(input)?),
+            max_bucket_capacity: scale::Decode::decode(input)?,
+            rounding: scale::Decode::decode(input)?,
+            rounding_quantum: scale::Decode::decode(input)?,
+            geometric: scale::Decode::decode(input)?,
+        })
+    }
+}
+
+impl<T, C> scale::Encode for WithLayout<T, C>
+where
+    T: scale::Encode,
+    C: BucketVecConfig,
+{
+    fn size_hint(&self) -> usize {
+        LayoutHeader::of::<C>().size_hint() + self.0.size_hint()
+    }
+
+    fn encode_to<O: scale::Output + ?Sized>(&self, output: &mut O) {
+        LayoutHeader::of::<C>().encode_to(output);
+        self.0.encode_to(output);
+    }
+}
+
+impl<T, C> EncodeLike for WithLayout<T, C>
+where
+    T: scale::Encode,
+    C: BucketVecConfig,
+{
+}
+
+impl<T, C> scale::Decode for WithLayout<T, C>
+where
+    T: scale::Decode,
+    C: BucketVecConfig,
+{
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let header = <LayoutHeader as scale::Decode>::decode(input)?;
+        header
+            .check::<C>()
+            .map_err(|_| "bucket vector layout does not match its configuration")?;
+        scale::Decode::decode(input).map(WithLayout)
+    }
+}
+
+impl<T, const MAX_LEN: usize, C> scale::Encode for CappedBucketVec<T, MAX_LEN, C>
+where
+    T: scale::Encode,
+{
+    fn size_hint(&self) -> usize {
+        self.as_bucket_vec().size_hint()
+    }
+
+    fn encode_to<O: scale::Output + ?Sized>(&self, output: &mut O) {
+        self.as_bucket_vec().encode_to(output)
+    }
+}
+
+/// Capped bucket vectors are encoded exactly like vectors.
+impl<T, U, const MAX_LEN: usize, C> EncodeLike<Vec<U>> for CappedBucketVec<T, MAX_LEN, C>
+where
+    T: EncodeLike<U>,
+    U: scale::Encode,
+{
+}
+
+impl<T, const MAX_LEN: usize, C> scale::Decode for CappedBucketVec<T, MAX_LEN, C>
+where
+    C: BucketVecConfig,
+    T: scale::Decode,
+{
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let len = decode_len(input)?;
+        if len > MAX_LEN {
+            return Err("capped bucket vector length exceeds its maximum length".into());
         }
-        Ok(vec)
+        let vec = decode_elements(input, len)?;
+        Ok(Self::try_from(vec).unwrap_or_else(|_| unreachable!("checked the length above")))
+    }
+}
+
+impl<T, const MAX_LEN: usize, C> MaxEncodedLen for CappedBucketVec<T, MAX_LEN, C>
+where
+    T: MaxEncodedLen,
+{
+    fn max_encoded_len() -> usize {
+        <scale::Compact<u64>>::compact_len(&(MAX_LEN as u64))
+            .saturating_add(T::max_encoded_len().saturating_mul(MAX_LEN))
+    }
+}
+
+impl<T, const MAX_LEN: usize, C> DecodeLength for CappedBucketVec<T, MAX_LEN, C> {
+    fn len(self_encoded: &[u8]) -> Result<usize, scale::Error> {
+        <BucketVec<T, C> as DecodeLength>::len(self_encoded)
+    }
+}
+
+/// Capped bucket vectors have the same type information as sequences.
+impl<T, const MAX_LEN: usize, C> scale_info::TypeInfo for CappedBucketVec<T, MAX_LEN, C>
+where
+    T: scale_info::TypeInfo + 'static,
+{
+    type Identity = [T];
+
+    fn type_info() -> scale_info::Type {
+        <[T] as scale_info::TypeInfo>::type_info()
     }
 }
@@ -0,0 +1,62 @@
+//! Support for version 1 of `parity-scale-codec`.
+//!
+//! Bucket vectors are encoded exactly like vectors.
+
+use super::{storage::BucketSchedule, BucketVec, BucketVecConfig};
+use core::convert::TryFrom;
+use scale1::{CompactLen as _, DecodeLength, EncodeLike};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+impl<T, C> scale1::Encode for BucketVec<T, C>
+where
+    T: scale1::Encode,
+{
+    fn size_hint(&self) -> usize {
+        let len_prefix = <scale1::Compact<u64>>::compact_len(&(self.len() as u64));
+        self.iter()
+            .fold(len_prefix, |size, elem| size + elem.size_hint())
+    }
+
+    fn encode_to<O: scale1::Output>(&self, output: &mut O) {
+        scale1::Compact(self.len() as u64).encode_to(output);
+        for elem in self {
+            elem.encode_to(output);
+        }
+    }
+}
+
+/// Bucket vectors are encoded exactly like vectors.
+impl<T, U, C> EncodeLike<Vec<U>> for BucketVec<T, C>
+where
+    T: EncodeLike<U>,
+    U: scale1::Encode,
+{
+}
+
+impl<T, C> scale1::Decode for BucketVec<T, C>
+where
+    C: BucketVecConfig,
+    T: scale1::Decode,
+{
+    /// Decodes the elements allocating buckets only once decoded elements
+    /// require them so that the announced length is never trusted.
+    fn decode<I: scale1::Input>(input: &mut I) -> Result<Self, scale1::Error> {
+        let len = <scale1::Compact<u64> as scale1::Decode>::decode(input)?.0;
+        let len = usize::try_from(len).map_err(|_| "bucket vector length exceeds usize")?;
+        let mut vec = BucketVec::new();
+        let mut remaining = len;
+        while remaining > 0 {
+            remaining -= vec.try_fill_bucket(remaining, || <T as scale1::Decode>::decode(input))?;
+        }
+        Ok(vec)
+    }
+}
+
+impl<T, C> DecodeLength for BucketVec<T, C> {
+    fn len(mut self_encoded: &[u8]) -> Result<usize, scale1::Error> {
+        let len = <scale1::Compact<u64> as scale1::Decode>::decode(&mut self_encoded)?.0;
+        usize::try_from(len).map_err(|_| "bucket vector length exceeds usize".into())
+    }
+}
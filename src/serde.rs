@@ -0,0 +1,185 @@
+use super::{
+    with_layout::{LayoutHeader, WithLayout},
+    BucketVec, BucketVecConfig,
+};
+use core::{fmt, marker::PhantomData};
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// The maximum number of bytes pre-allocated upon deserialization.
+///
+/// Guards against malicious inputs announcing huge lengths.
+const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+
+impl<T, C> Serialize for BucketVec<T, C>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self)
+    }
+}
+
+/// Visitor to deserialize a bucket vector from a sequence.
+struct BucketVecVisitor<T, C> {
+    marker: PhantomData<fn() -> BucketVec<T, C>>,
+}
+
+impl<'de, T, C> Visitor<'de> for BucketVecVisitor<T, C>
+where
+    T: Deserialize<'de>,
+    C: BucketVecConfig,
+{
+    type Value = BucketVec<T, C>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut vec = BucketVec::new();
+        if let Some(len) = seq.size_hint() {
+            let max_len = MAX_PREALLOC_BYTES / core::mem::size_of::<T>().max(1);
+            vec.reserve(len.min(max_len));
+        }
+        while let Some(value) = seq.next_element()? {
+            vec.push(value);
+        }
+        Ok(vec)
+    }
+}
+
+impl<'de, T, C> Deserialize<'de> for BucketVec<T, C>
+where
+    T: Deserialize<'de>,
+    C: BucketVecConfig,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(BucketVecVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<T, C> BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Serializes the bucket vector together with the layout parameters of
+    /// its configuration.
+    ///
+    /// Equivalent to serializing a [`WithLayout`] and usable as
+    /// `#[serde(serialize_with = "BucketVec::serialize_with_layout")]`.
+    pub fn serialize_with_layout<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        let header = LayoutHeader::of::<C>();
+        let mut tuple = serializer.serialize_tuple(8)?;
+        tuple.serialize_element(&header.version)?;
+        tuple.serialize_element(&header.starting_capacity)?;
+        tuple.serialize_element(&header.growth_rate)?;
+        tuple.serialize_element(&header.max_bucket_capacity)?;
+        tuple.serialize_element(&header.rounding)?;
+        tuple.serialize_element(&header.rounding_quantum)?;
+        tuple.serialize_element(&header.geometric)?;
+        tuple.serialize_element(self)?;
+        tuple.end()
+    }
+
+    /// Deserializes a bucket vector serialized by [`BucketVec::serialize_with_layout`].
+    ///
+    /// Equivalent to deserializing a [`WithLayout`] and usable as
+    /// `#[serde(deserialize_with = "BucketVec::deserialize_with_layout_check")]`.
+    ///
+    /// # Errors
+    ///
+    /// If the serialized layout parameters do not match the configuration.
+    /// No element is deserialized in this case.
+    pub fn deserialize_with_layout_check<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(
+            8,
+            WithLayoutVisitor {
+                marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<T, C> Serialize for WithLayout<T, C>
+where
+    T: Serialize,
+    C: BucketVecConfig,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize_with_layout(serializer)
+    }
+}
+
+impl<'de, T, C> Deserialize<'de> for WithLayout<T, C>
+where
+    T: Deserialize<'de>,
+    C: BucketVecConfig,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BucketVec::deserialize_with_layout_check(deserializer).map(WithLayout)
+    }
+}
+
+/// Visitor to deserialize a bucket vector preceded by its layout header.
+struct WithLayoutVisitor<T, C> {
+    marker: PhantomData<fn() -> BucketVec<T, C>>,
+}
+
+impl<'de, T, C> Visitor<'de> for WithLayoutVisitor<T, C>
+where
+    T: Deserialize<'de>,
+    C: BucketVecConfig,
+{
+    type Value = BucketVec<T, C>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a layout header followed by a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let missing = |index| de::Error::invalid_length(index, &self);
+        let header = LayoutHeader {
+            version: seq.next_element()?.ok_or_else(|| missing(0))?,
+            starting_capacity: seq.next_element()?.ok_or_else(|| missing(1))?,
+            growth_rate: seq.next_element()?.ok_or_else(|| missing(2))?,
+            max_bucket_capacity: seq.next_element()?.ok_or_else(|| missing(3))?,
+            rounding: seq.next_element()?.ok_or_else(|| missing(4))?,
+            rounding_quantum: seq.next_element()?.ok_or_else(|| missing(5))?,
+            geometric: seq.next_element()?.ok_or_else(|| missing(6))?,
+        };
+        header.check::<C>().map_err(de::Error::custom)?;
+        seq.next_element()?.ok_or_else(|| missing(7))
+    }
+}
@@ -0,0 +1,61 @@
+//! Cheaply cloneable read-only snapshots of bucket vectors.
+
+use super::{BucketVec, DefaultConfig};
+
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// A read-only snapshot of a bucket vector that can be cloned in `O(1)`.
+///
+/// Created by [`BucketVec::freeze`]. Dereferences to the frozen bucket vector
+/// and thus provides its whole read-only API.
+#[derive(Debug)]
+pub struct SharedBucketVec<T, C = DefaultConfig> {
+    /// The shared bucket vector.
+    vec: Arc<BucketVec<T, C>>,
+}
+
+impl<T, C> Clone for SharedBucketVec<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            vec: Arc::clone(&self.vec),
+        }
+    }
+}
+
+impl<T, C> core::ops::Deref for SharedBucketVec<T, C> {
+    type Target = BucketVec<T, C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+
+impl<T, C> From<BucketVec<T, C>> for SharedBucketVec<T, C> {
+    fn from(vec: BucketVec<T, C>) -> Self {
+        Self { vec: Arc::new(vec) }
+    }
+}
+
+impl<T, C> SharedBucketVec<T, C> {
+    /// Returns the bucket vector if this is its only snapshot.
+    ///
+    /// Otherwise returns the snapshot back.
+    pub fn try_unwrap(self) -> Result<BucketVec<T, C>, Self> {
+        Arc::try_unwrap(self.vec).map_err(|vec| Self { vec })
+    }
+}
+
+impl<T, C> BucketVec<T, C> {
+    /// Freezes the bucket vector into a cheaply cloneable read-only snapshot.
+    ///
+    /// # Note
+    ///
+    /// The snapshot can be shared across threads if `T` is `Send` and `Sync`.
+    pub fn freeze(self) -> SharedBucketVec<T, C> {
+        SharedBucketVec::from(self)
+    }
+}
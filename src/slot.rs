@@ -0,0 +1,214 @@
+//! Bucket vector that supports removal of elements through tombstones.
+
+use super::{BucketVec, BucketVecConfig, DefaultConfig, Iter, IterMut};
+
+/// An entry of a slot bucket vector.
+#[derive(Debug, Clone)]
+enum Entry<T> {
+    /// The slot stores an element.
+    Occupied(T),
+    /// The slot has been removed and links to the next vacant slot if any.
+    Vacant(Option<usize>),
+}
+
+/// A bucket vector that supports removal of elements.
+///
+/// Removing an element leaves behind a tombstone that is reused by later
+/// insertions. All other elements keep their indices and addresses.
+#[derive(Debug, Clone)]
+pub struct SlotBucketVec<T, C = DefaultConfig> {
+    /// The occupied and vacant slots.
+    slots: BucketVec<Entry<T>, C>,
+    /// The index of the most recently vacated slot if any.
+    free: Option<usize>,
+    /// The number of occupied slots.
+    len: usize,
+}
+
+impl<T, C> Default for SlotBucketVec<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> SlotBucketVec<T, C> {
+    /// Creates a new empty slot bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            slots: BucketVec::new(),
+            free: None,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements stored in the slot bucket vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the slot bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the indices and shared references to the elements.
+    pub fn iter(&self) -> SlotIter<'_, T> {
+        SlotIter {
+            slots: self.slots.iter().enumerate(),
+        }
+    }
+
+    /// Returns an iterator over the indices and exclusive references to the elements.
+    pub fn iter_mut(&mut self) -> SlotIterMut<'_, T> {
+        SlotIterMut {
+            slots: self.slots.iter_mut().enumerate(),
+        }
+    }
+}
+
+impl<T, C> SlotBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Inserts the element into a vacant slot and returns its index.
+    ///
+    /// # Note
+    ///
+    /// Reuses the most recently vacated slot if any and otherwise pushes a
+    /// new slot. This operation will never move other elements, reallocates
+    /// or otherwise invalidate pointers of elements contained by the slot
+    /// bucket vector.
+    pub fn insert(&mut self, new_value: T) -> usize {
+        self.len += 1;
+        match self.free {
+            Some(index) => {
+                let slot = self
+                    .slots
+                    .get_mut(index)
+                    .expect("encountered out of bounds vacant slot");
+                match core::mem::replace(slot, Entry::Occupied(new_value)) {
+                    Entry::Vacant(next) => self.free = next,
+                    Entry::Occupied(_) => panic!("encountered occupied slot in free list"),
+                }
+                index
+            }
+            None => {
+                let index = self.slots.len();
+                self.slots.push(Entry::Occupied(new_value));
+                index
+            }
+        }
+    }
+
+    /// Removes the element at the given index and returns it if any.
+    ///
+    /// Leaves behind a vacant slot that is reused by later insertions.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let slot = self.slots.get_mut(index)?;
+        if let Entry::Vacant(_) = slot {
+            return None;
+        }
+        let removed = core::mem::replace(slot, Entry::Vacant(self.free));
+        self.free = Some(index);
+        self.len -= 1;
+        match removed {
+            Entry::Occupied(value) => Some(value),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    /// Returns `true` if there is an element at the given index.
+    pub fn contains(&self, index: usize) -> bool {
+        self.get(index).is_some()
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        match self.slots.get(index)? {
+            Entry::Occupied(value) => Some(value),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        match self.slots.get_mut(index)? {
+            Entry::Occupied(value) => Some(value),
+            Entry::Vacant(_) => None,
+        }
+    }
+}
+
+/// An iterator over the indices and shared references to the elements of a
+/// slot bucket vector.
+#[derive(Debug, Clone)]
+pub struct SlotIter<'a, T> {
+    /// The enumerated slots.
+    slots: core::iter::Enumerate<Iter<'a, Entry<T>>>,
+}
+
+impl<'a, T> Iterator for SlotIter<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.by_ref().find_map(|(index, slot)| match slot {
+            Entry::Occupied(value) => Some((index, value)),
+            Entry::Vacant(_) => None,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.slots.size_hint().1)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for SlotIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.slots
+            .by_ref()
+            .rev()
+            .find_map(|(index, slot)| match slot {
+                Entry::Occupied(value) => Some((index, value)),
+                Entry::Vacant(_) => None,
+            })
+    }
+}
+
+/// An iterator over the indices and exclusive references to the elements of
+/// a slot bucket vector.
+#[derive(Debug)]
+pub struct SlotIterMut<'a, T> {
+    /// The enumerated slots.
+    slots: core::iter::Enumerate<IterMut<'a, Entry<T>>>,
+}
+
+impl<'a, T> Iterator for SlotIterMut<'a, T> {
+    type Item = (usize, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.by_ref().find_map(|(index, slot)| match slot {
+            Entry::Occupied(value) => Some((index, value)),
+            Entry::Vacant(_) => None,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.slots.size_hint().1)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for SlotIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.slots
+            .by_ref()
+            .rev()
+            .find_map(|(index, slot)| match slot {
+                Entry::Occupied(value) => Some((index, value)),
+                Entry::Vacant(_) => None,
+            })
+    }
+}
@@ -0,0 +1,64 @@
+//! Debug checks for the address stability of the elements of bucket vectors.
+
+use super::BucketVec;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Records the addresses of all elements of a bucket vector.
+///
+/// Since elements are stored contiguously within their bucket it suffices to
+/// record the address of the storage of every bucket.
+#[derive(Debug, Default)]
+pub struct StabilityChecker {
+    /// The storage address of every bucket in order of the buckets.
+    addresses: Vec<usize>,
+}
+
+impl StabilityChecker {
+    /// Creates a new stability checker that has not recorded any addresses.
+    pub const fn new() -> Self {
+        Self {
+            addresses: Vec::new(),
+        }
+    }
+}
+
+impl<T, C> BucketVec<T, C> {
+    /// Asserts that no element of the bucket vector has moved since it has
+    /// been pushed and records the addresses of all newly pushed buckets.
+    ///
+    /// # Panics
+    ///
+    /// If any element is no longer stored at its recorded address.
+    pub(crate) fn check_stability(&mut self) {
+        let mut addresses = core::mem::take(&mut self.stability.addresses);
+        addresses.truncate(self.buckets.len());
+        for (index, (bucket, &address)) in self.buckets.iter().zip(addresses.iter()).enumerate() {
+            assert_eq!(
+                bucket.as_slice().as_ptr() as usize,
+                address,
+                "element at index {} has been moved",
+                self.offsets[index]
+            );
+        }
+        let recorded = addresses.len();
+        addresses.extend(
+            self.buckets[recorded..]
+                .iter()
+                .map(|bucket| bucket.as_slice().as_ptr() as usize),
+        );
+        self.stability.addresses = addresses;
+    }
+}
+
+impl<T, C> Drop for BucketVec<T, C> {
+    fn drop(&mut self) {
+        // Avoid aborting due to a double panic after a failed check.
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+        self.check_stability();
+    }
+}
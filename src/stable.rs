@@ -0,0 +1,107 @@
+//! Abstraction over containers that never move their elements.
+
+use super::{BucketVec, BucketVecConfig, DynBucketVec, Iter};
+
+/// A growable container whose elements never move once pushed.
+///
+/// References and pointers to elements stay valid across pushes and every
+/// element is identified by the index returned upon its push. This allows
+/// downstream code to be generic over bucket vectors of different
+/// configurations and over other stable-address containers.
+pub trait StableVec {
+    /// The type of the stored elements.
+    type Item;
+
+    /// The iterator over shared references to the elements in index order.
+    type Iter<'a>: Iterator<Item = &'a Self::Item>
+    where
+        Self: 'a;
+
+    /// Returns the number of stored elements.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if no elements are stored.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pushes the value and returns its index.
+    ///
+    /// # Note
+    ///
+    /// This operation must never move other elements or otherwise invalidate
+    /// pointers of elements contained by the container.
+    fn push(&mut self, new_value: Self::Item) -> usize;
+
+    /// Returns a shared reference to the element at the given index if any.
+    fn get(&self, index: usize) -> Option<&Self::Item>;
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    fn get_mut(&mut self, index: usize) -> Option<&mut Self::Item>;
+
+    /// Returns an iterator over shared references to the elements in index order.
+    fn iter(&self) -> Self::Iter<'_>;
+}
+
+impl<T, C> StableVec for BucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    type Item = T;
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        Self: 'a;
+
+    fn len(&self) -> usize {
+        BucketVec::len(self)
+    }
+
+    fn push(&mut self, new_value: T) -> usize {
+        let index = BucketVec::len(self);
+        BucketVec::push(self, new_value);
+        index
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        BucketVec::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        BucketVec::get_mut(self, index)
+    }
+
+    fn iter(&self) -> Iter<'_, T> {
+        BucketVec::iter(self)
+    }
+}
+
+impl<T> StableVec for DynBucketVec<T> {
+    type Item = T;
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        Self: 'a;
+
+    fn len(&self) -> usize {
+        DynBucketVec::len(self)
+    }
+
+    fn push(&mut self, new_value: T) -> usize {
+        let index = DynBucketVec::len(self);
+        DynBucketVec::push(self, new_value);
+        index
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        DynBucketVec::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        DynBucketVec::get_mut(self, index)
+    }
+
+    fn iter(&self) -> Iter<'_, T> {
+        DynBucketVec::iter(self)
+    }
+}
@@ -0,0 +1,116 @@
+//! Memory statistics of bucket vectors.
+
+use super::{Bucket, BucketVec};
+use core::mem::size_of;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The occupancy of a single bucket.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BucketStats {
+    /// The number of elements stored in the bucket.
+    len: usize,
+    /// The capacity of the bucket.
+    capacity: usize,
+}
+
+impl BucketStats {
+    /// Returns the number of elements stored in the bucket.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bucket stores no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the capacity of the bucket.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+/// Memory statistics of a bucket vector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryStats {
+    /// The number of heap bytes used by the bucket vector.
+    heap_bytes: usize,
+    /// The number of elements stored in the bucket vector.
+    len: usize,
+    /// The occupancy of every allocated bucket.
+    buckets: Vec<BucketStats>,
+}
+
+impl MemoryStats {
+    /// Returns the number of heap bytes allocated by the bucket vector.
+    ///
+    /// This includes the storage of all buckets as well as the internal
+    /// bookkeeping of the bucket vector.
+    pub fn heap_bytes(&self) -> usize {
+        self.heap_bytes
+    }
+
+    /// Returns the number of elements stored in the bucket vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bucket vector stores no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the total capacity of all allocated buckets.
+    pub fn capacity(&self) -> usize {
+        self.buckets
+            .iter()
+            .map(BucketStats::capacity)
+            .fold(0, usize::saturating_add)
+    }
+
+    /// Returns the number of vacant entries of all allocated buckets.
+    pub fn wasted(&self) -> usize {
+        self.capacity() - self.len
+    }
+
+    /// Returns the number of allocated buckets.
+    pub fn len_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Returns the occupancy of every allocated bucket in order.
+    ///
+    /// Buckets that have been reserved ahead of time are empty.
+    pub fn buckets(&self) -> &[BucketStats] {
+        &self.buckets
+    }
+}
+
+impl<T, C> BucketVec<T, C> {
+    /// Returns memory statistics of the bucket vector.
+    pub fn memory_stats(&self) -> MemoryStats {
+        let buckets = self
+            .buckets
+            .iter()
+            .chain(self.reserved.iter())
+            .map(|bucket| BucketStats {
+                len: bucket.len(),
+                capacity: bucket.capacity(),
+            })
+            .collect::<Vec<_>>();
+        let entries_bytes = buckets
+            .iter()
+            .map(|bucket| bucket.capacity.saturating_mul(size_of::<T>()))
+            .fold(0, usize::saturating_add);
+        let bookkeeping_bytes = (self.buckets.capacity() + self.reserved.capacity())
+            * size_of::<Bucket<T>>()
+            + self.offsets.capacity() * size_of::<usize>();
+        MemoryStats {
+            heap_bytes: entries_bytes + bookkeeping_bytes,
+            len: self.len(),
+            buckets,
+        }
+    }
+}
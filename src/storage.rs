@@ -0,0 +1,264 @@
+//! Bucket bookkeeping shared by all bucket vectors that push buckets onto an
+//! entry vector.
+//!
+//! The bucket capacities follow either the compile time schedule of a
+//! [`BucketVecConfig`](crate::BucketVecConfig) or a schedule chosen at runtime.
+
+use super::Bucket;
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::VecDeque, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+
+/// Shared access to the parts of a bucket vector.
+pub struct Parts<'a, T> {
+    /// The number of elements stored in the bucket vector.
+    pub len: usize,
+    /// The entry vector.
+    pub buckets: &'a [Bucket<T>],
+    /// Empty buckets that have been allocated ahead of time.
+    pub reserved: &'a VecDeque<Bucket<T>>,
+}
+
+/// Exclusive access to the parts of a bucket vector.
+pub struct PartsMut<'a, T> {
+    /// The number of elements stored in the bucket vector.
+    pub len: &'a mut usize,
+    /// The entry vector.
+    pub buckets: &'a mut Vec<Bucket<T>>,
+    /// The index of the first element of every bucket in the entry vector.
+    pub offsets: &'a mut Vec<usize>,
+    /// Empty buckets that have been allocated ahead of time.
+    pub reserved: &'a mut VecDeque<Bucket<T>>,
+}
+
+/// The bucket bookkeeping that does not depend on the capacity schedule.
+pub trait BucketStorage<T> {
+    /// Returns shared access to the parts of the bucket vector.
+    fn parts(&self) -> Parts<'_, T>;
+
+    /// Returns exclusive access to the parts of the bucket vector.
+    fn parts_mut(&mut self) -> PartsMut<'_, T>;
+
+    /// Called after every structural change to update cached state.
+    fn sync(&mut self);
+
+    /// Returns the number of elements the bucket vector can hold without
+    /// allocating new buckets.
+    ///
+    /// Always returns `usize::MAX` for zero-sized types since they never
+    /// require any allocations.
+    fn capacity(&self) -> usize {
+        if core::mem::size_of::<T>() == 0 {
+            return usize::MAX;
+        }
+        let parts = self.parts();
+        parts
+            .buckets
+            .iter()
+            .chain(parts.reserved.iter())
+            .map(Bucket::capacity)
+            .fold(0, usize::saturating_add)
+    }
+
+    /// Pushes the bucket onto the entry vector and records its offset.
+    fn attach_bucket(&mut self, bucket: Bucket<T>) {
+        let parts = self.parts_mut();
+        let offset = match (parts.offsets.last(), parts.buckets.last()) {
+            (Some(last), Some(last_bucket)) => last + last_bucket.capacity(),
+            _ => 0,
+        };
+        parts.buckets.push(bucket);
+        parts.offsets.push(offset);
+    }
+
+    /// Moves the last bucket into the reserved buckets if it is empty.
+    fn release_empty_bucket(&mut self) {
+        let parts = self.parts_mut();
+        if parts.buckets.last().map(Bucket::is_empty).unwrap_or(false) {
+            let empty = parts
+                .buckets
+                .pop()
+                .expect("encountered missing empty bucket");
+            parts.offsets.pop();
+            parts.reserved.push_front(empty);
+        }
+    }
+
+    /// Removes the last element from the bucket vector and returns it if any.
+    ///
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    fn pop(&mut self) -> Option<T> {
+        let parts = self.parts_mut();
+        let popped = parts.buckets.last_mut()?.pop();
+        if popped.is_some() {
+            *parts.len -= 1;
+        }
+        self.release_empty_bucket();
+        self.sync();
+        popped
+    }
+
+    /// Shortens the bucket vector to the given length dropping all elements beyond it.
+    ///
+    /// Emptied buckets are kept as reserved buckets for later pushes.
+    fn truncate(&mut self, len: usize) {
+        while *self.parts_mut().len > len {
+            let parts = self.parts_mut();
+            let bucket = parts
+                .buckets
+                .last_mut()
+                .expect("encountered missing bucket for remaining elements");
+            let removed = core::cmp::min(bucket.len(), *parts.len - len);
+            bucket.truncate(bucket.len() - removed);
+            *parts.len -= removed;
+            self.release_empty_bucket();
+        }
+        self.sync();
+    }
+}
+
+/// The bucket bookkeeping that allocates buckets following a capacity schedule.
+pub trait BucketSchedule<T>: BucketStorage<T> {
+    /// Returns the capacity of the newly allocated bucket at the given index.
+    fn allocate_bucket(&self, index: usize) -> usize;
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// The reserved buckets are allocated eagerly but only become part of
+    /// the bucket vector once pushed elements require them.
+    fn reserve(&mut self, additional: usize) {
+        let parts = self.parts();
+        let required = parts.len.saturating_add(additional);
+        let mut index = parts.buckets.len() + parts.reserved.len();
+        let mut capacity = self.capacity();
+        while capacity < required {
+            let new_capacity = self.allocate_bucket(index);
+            self.parts_mut()
+                .reserved
+                .push_back(Bucket::new(new_capacity));
+            capacity = capacity.saturating_add(new_capacity);
+            index += 1;
+        }
+        self.sync();
+    }
+
+    /// Returns the next empty bucket to be pushed onto the bucket vector.
+    ///
+    /// Uses the next reserved bucket if any.
+    fn next_bucket(&mut self) -> Bucket<T> {
+        let parts = self.parts_mut();
+        match parts.reserved.pop_front() {
+            Some(reserved) => reserved,
+            None => {
+                let index = parts.buckets.len();
+                Bucket::new(self.allocate_bucket(index))
+            }
+        }
+    }
+
+    /// Makes sure that the last bucket has spare capacity for at least one element.
+    ///
+    /// If a new bucket needs to be pushed it is empty and callers must fill it
+    /// with at least one element before handing back control.
+    fn ensure_spare_bucket(&mut self) {
+        let has_spare = self
+            .parts()
+            .buckets
+            .last()
+            .map(|bucket| bucket.len() < bucket.capacity())
+            .unwrap_or(false);
+        if !has_spare {
+            let new_bucket = self.next_bucket();
+            self.attach_bucket(new_bucket);
+        }
+    }
+
+    /// Pushes up to `max` elements produced by `f` into a single bucket.
+    ///
+    /// Fills the spare capacity of the last bucket or of a new bucket if
+    /// the last bucket is already full.
+    /// Returns the number of pushed elements which is only less than `max`
+    /// if the bucket has been filled to its capacity.
+    ///
+    /// # Errors
+    ///
+    /// Stops at and returns the first error produced by `f`.
+    /// Elements produced before the error remain in the bucket vector.
+    fn try_fill_bucket<F, E>(&mut self, max: usize, mut f: F) -> Result<usize, E>
+    where
+        F: FnMut() -> Result<T, E>,
+    {
+        if max == 0 {
+            return Ok(0);
+        }
+        self.ensure_spare_bucket();
+        let parts = self.parts_mut();
+        let bucket = parts
+            .buckets
+            .last_mut()
+            .expect("encountered missing bucket with spare capacity");
+        let count = core::cmp::min(max, bucket.capacity() - bucket.len());
+        let mut result = Ok(count);
+        for pushed in 0..count {
+            match f() {
+                Ok(new_value) => bucket.push(new_value),
+                Err(error) => {
+                    result = Err(error);
+                    *parts.len += pushed;
+                    break;
+                }
+            }
+        }
+        if let Ok(pushed) = result {
+            *parts.len += pushed;
+        }
+        // Do not leave behind an empty bucket if `f` failed immediately.
+        self.release_empty_bucket();
+        self.sync();
+        result
+    }
+
+    /// Clones as many of the given values as fit into a single bucket.
+    ///
+    /// Fills the spare capacity of the last bucket or of a new bucket if
+    /// the last bucket is already full.
+    /// Returns the number of cloned values.
+    fn fill_bucket_from_slice(&mut self, values: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        if values.is_empty() {
+            return 0;
+        }
+        self.ensure_spare_bucket();
+        let parts = self.parts_mut();
+        let bucket = parts
+            .buckets
+            .last_mut()
+            .expect("encountered missing bucket with spare capacity");
+        let count = core::cmp::min(values.len(), bucket.capacity() - bucket.len());
+        bucket.extend_from_slice(&values[..count]);
+        *parts.len += count;
+        self.sync();
+        count
+    }
+
+    /// Pushes all elements of the iterator onto the bucket vector.
+    ///
+    /// Allocates all buckets required by the lower bound of the size hint up front.
+    fn extend_from_iter<I>(&mut self, iter: I)
+    where
+        I: Iterator<Item = T>,
+    {
+        let (lower, _) = iter.size_hint();
+        self.reserve(lower);
+        // Fill bucket after bucket until the iterator is exhausted.
+        // Fusing makes sure we never poll the iterator again afterwards.
+        let mut iter = iter.fuse().peekable();
+        while iter.peek().is_some() {
+            let _ = self.try_fill_bucket(usize::MAX, || iter.next().ok_or(()));
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Append-only string arena built on top of a byte bucket vector.
+
+use super::{BucketVec, BucketVecConfig, DefaultConfig};
+
+/// An append-only string arena that never moves its contained strings.
+///
+/// Every pushed string is stored contiguously within a single bucket so that
+/// the returned `&str` stays valid for as long as the arena is not dropped.
+#[derive(Debug)]
+pub struct BucketString<C = DefaultConfig> {
+    /// The bytes of all pushed strings.
+    bytes: BucketVec<u8, C>,
+}
+
+/// The location of a string pushed onto a [`BucketString`].
+///
+/// Can be converted back into a `&str` using [`BucketString::get`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StrSpan {
+    /// The byte index of the first byte of the string.
+    start: usize,
+    /// The length of the string in bytes.
+    len: usize,
+}
+
+impl StrSpan {
+    /// Returns the byte index of the first byte of the string.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the length of the string in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<C> Default for BucketString<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> BucketString<C> {
+    /// Creates a new empty string arena.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self {
+            bytes: BucketVec::new(),
+        }
+    }
+
+    /// Returns `true` if no bytes have been pushed onto the string arena.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+impl<C> BucketString<C>
+where
+    C: BucketVecConfig,
+{
+    /// Pushes the string onto the arena and returns its location.
+    ///
+    /// # Panics
+    ///
+    /// If no bucket of the configuration is large enough to hold the string.
+    pub fn push_str_span(&mut self, string: &str) -> StrSpan {
+        let (start, _) = self
+            .bytes
+            .push_slice_padded(string.as_bytes(), &0)
+            .expect("string exceeds the capacity of every bucket");
+        StrSpan {
+            start,
+            len: string.len(),
+        }
+    }
+
+    /// Pushes the string onto the arena and returns the stored string.
+    ///
+    /// # Panics
+    ///
+    /// If no bucket of the configuration is large enough to hold the string.
+    pub fn push_str(&mut self, string: &str) -> &str {
+        let (_, stored) = self
+            .bytes
+            .push_slice_padded(string.as_bytes(), &0)
+            .expect("string exceeds the capacity of every bucket");
+        core::str::from_utf8(stored).expect("encountered invalid UTF-8 in pushed string")
+    }
+
+    /// Returns the string at the given location if any.
+    ///
+    /// Returns `None` if the location does not refer to a string of this arena.
+    pub fn get(&self, span: StrSpan) -> Option<&str> {
+        self.bytes
+            .get_contiguous(span.start, span.len)
+            .and_then(|bytes| core::str::from_utf8(bytes).ok())
+    }
+}
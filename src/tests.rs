@@ -1,5 +1,20 @@
 use super::*;
 
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    vec,
+};
+
+#[cfg(feature = "std")]
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
 /// A configuration for bucket vectors that grows quadratically.
 #[derive(Debug)]
 pub enum QuadraticConfig {}
@@ -69,9 +84,46 @@ impl BucketVecConfig for CrazyPiConfig {
     /// The first bucket has a capacity of approximately PI.
     const STARTING_CAPACITY: usize = 3;
     /// The next bucket is always PI larger.
+    #[allow(clippy::approx_constant)]
     const GROWTH_RATE: f64 = 3.14159265;
 }
 
+/// A config for bucket vectors with a custom schedule whose bucket
+/// capacities stop growing after a few buckets.
+#[derive(Debug)]
+pub enum PlateauConfig {}
+
+impl BucketVecConfig for PlateauConfig {
+    /// The first bucket has a capacity of 1.
+    const STARTING_CAPACITY: usize = 1;
+    /// The next bucket always doubles in capacity until the plateau.
+    const GROWTH_RATE: f64 = 2.0;
+    const GEOMETRIC: bool = false;
+
+    /// Bucket capacities double up until a capacity of 8.
+    fn bucket_capacity(bucket_index: usize) -> usize {
+        1 << core::cmp::min(bucket_index, 3)
+    }
+
+    /// No bucket is larger than 8.
+    fn max_bucket_capacity() -> Option<usize> {
+        Some(8)
+    }
+}
+
+/// A config for bucket vectors whose bucket capacities stop doubling at 64.
+#[derive(Debug)]
+pub enum CappedConfig {}
+
+impl BucketVecConfig for CappedConfig {
+    /// The first bucket has a capacity of 4.
+    const STARTING_CAPACITY: usize = 4;
+    /// The next bucket always doubles in capacity until the cap.
+    const GROWTH_RATE: f64 = 2.0;
+    /// No bucket is larger than 64.
+    const MAX_BUCKET_CAPACITY: Option<usize> = Some(64);
+}
+
 macro_rules! create_test_for_configs {
     ( $test_fn:ident ) => {
         paste::item! {
@@ -134,6 +186,26 @@ macro_rules! create_test_for_configs {
             fn [<$test_fn _crazy_pi_config_big>]() {
                 $test_fn::<CrazyPiConfig>(big_test_values())
             }
+
+            #[test]
+            fn [<$test_fn _capped_config_small>]() {
+                $test_fn::<CappedConfig>(small_test_values())
+            }
+
+            #[test]
+            fn [<$test_fn _capped_config_big>]() {
+                $test_fn::<CappedConfig>(big_test_values())
+            }
+
+            #[test]
+            fn [<$test_fn _plateau_config_small>]() {
+                $test_fn::<PlateauConfig>(small_test_values())
+            }
+
+            #[test]
+            fn [<$test_fn _plateau_config_big>]() {
+                $test_fn::<PlateauConfig>(big_test_values())
+            }
         }
     };
 }
@@ -201,7 +273,7 @@ fn iter_next_works_for<C>(test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     assert_iter_eq(vec.iter(), test_values.iter());
 }
 create_test_for_configs!(iter_next_works_for);
@@ -210,7 +282,7 @@ fn iter_next_back_works<C>(test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     assert_iter_eq(vec.iter().rev(), test_values.iter().rev());
 }
 create_test_for_configs!(iter_next_back_works);
@@ -219,7 +291,7 @@ fn iter_next_meet_middle_works_for<C>(test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     let mut expected = test_values.iter();
     let mut iter = vec.iter();
     // Lock-step get `next` and `next_back` from iterators.
@@ -242,7 +314,7 @@ fn iter_mut_next_works_for<C>(mut test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let mut vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     assert_iter_eq(vec.iter_mut(), test_values.iter_mut());
 }
 create_test_for_configs!(iter_mut_next_works_for);
@@ -251,7 +323,7 @@ fn iter_mut_next_back_works_for<C>(mut test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let mut vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     assert_iter_eq(vec.iter_mut().rev(), test_values.iter_mut().rev());
 }
 create_test_for_configs!(iter_mut_next_back_works_for);
@@ -260,7 +332,7 @@ fn iter_mut_next_meet_middle_works_for<C>(mut test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let mut vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     let mut expected = test_values.iter_mut();
     let mut iter = vec.iter_mut();
     // Lock-step get `next` and `next_back` from iterators.
@@ -279,6 +351,37 @@ where
 }
 create_test_for_configs!(iter_mut_next_meet_middle_works_for);
 
+fn exhausted_iters_stay_exhausted_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    // Polling exhausted iterators repeatedly from both ends must neither
+    // yield elements nor underflow their remaining length.
+    let mut iter = vec.iter();
+    iter.by_ref().for_each(drop);
+    for _ in 0..3 {
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.len(), 0);
+    }
+    let mut iter = vec.iter_mut();
+    iter.by_ref().rev().for_each(drop);
+    for _ in 0..3 {
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.len(), 0);
+    }
+    let mut iter = vec.into_iter();
+    iter.by_ref().for_each(drop);
+    for _ in 0..3 {
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.len(), 0);
+    }
+}
+create_test_for_configs!(exhausted_iters_stay_exhausted_for);
+
 fn access_works_for<C>(test_values: Vec<i32>)
 where
     C: BucketVecConfig,
@@ -292,11 +395,91 @@ where
 }
 create_test_for_configs!(access_works_for);
 
+fn access_deref_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    for (n, value) in test_values.iter().enumerate() {
+        let mut access = vec.push_get(*value);
+        assert_eq!(*access, *value);
+        assert_eq!(access.as_ref(), value);
+        *access += 1;
+        *access.as_mut() += 1;
+        assert_eq!(access.index(), n);
+        assert_eq!(access.position().index(), n);
+        let (bucket_index, entry_index) = (access.bucket_index(), access.entry_index());
+        assert_eq!(
+            config::bucket_entry_indices::<C>(n),
+            (bucket_index, entry_index)
+        );
+        assert_eq!(*access.into_ref(), value + 2);
+    }
+}
+create_test_for_configs!(access_deref_works_for);
+
+fn access_index_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    for (index, value) in test_values.iter().enumerate() {
+        let access = vec.access(index).unwrap();
+        assert_eq!(*access, *value);
+        assert_eq!(access.index(), index);
+        assert_eq!(
+            config::bucket_entry_indices::<C>(index),
+            (access.bucket_index(), access.entry_index())
+        );
+        let mut access = vec.access_mut(index).unwrap();
+        *access += 1;
+        assert_eq!(access.position().index(), index);
+        assert_eq!(access.into_ref(), &(value + 1));
+    }
+    assert!(vec.access(test_values.len()).is_none());
+    assert!(vec.access_mut(test_values.len()).is_none());
+}
+create_test_for_configs!(access_index_works_for);
+
+fn push_with_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<(usize, i32), C>>::new();
+    for value in &test_values {
+        vec.push_with(|index| (index, *value));
+        let access = vec.push_get_with(|index| (index, *value));
+        assert_eq!(access.index(), access.0);
+    }
+    assert_eq!(vec.len(), 2 * test_values.len());
+    for (index, (own_index, value)) in vec.iter().enumerate() {
+        assert_eq!(index, *own_index);
+        assert_eq!(*value, test_values[index / 2]);
+    }
+}
+create_test_for_configs!(push_with_works_for);
+
+fn extend_get_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    let mut start = 0;
+    for chunk in test_values.chunks(5) {
+        let range = vec.extend_get(chunk.iter().copied());
+        assert_eq!(range, start..start + chunk.len());
+        assert!(range.map(|index| vec.get(index).unwrap()).eq(chunk));
+        start += chunk.len();
+    }
+    assert_eq!(vec.extend_get(None), start..start);
+}
+create_test_for_configs!(extend_get_works_for);
+
 fn get_works_for<C>(test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     for (n, expected) in test_values.into_iter().enumerate() {
         assert_eq!(vec.get(n), Some(&expected));
     }
@@ -308,7 +491,7 @@ fn get_mut_works_for<C>(test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let mut vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     for (n, mut expected) in test_values.into_iter().enumerate() {
         assert_eq!(vec.get_mut(n), Some(&mut expected));
     }
@@ -320,7 +503,7 @@ fn last_works_for<C>(mut test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let mut vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     assert_eq!(vec.last(), test_values.last());
     assert_eq!(vec.last_mut(), test_values.last_mut());
 }
@@ -330,8 +513,4831 @@ fn first_works_for<C>(mut test_values: Vec<i32>)
 where
     C: BucketVecConfig,
 {
-    let mut vec = test_values.iter().cloned().collect::<BucketVec<_>>();
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
     assert_eq!(vec.first(), test_values.first());
     assert_eq!(vec.first_mut(), test_values.first_mut());
 }
 create_test_for_configs!(first_works_for);
+
+fn split_first_last_mut_works_for<C>(mut test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    {
+        let (last, rest) = vec.split_last_mut().unwrap();
+        let (expected_last, expected_rest) = test_values.split_last_mut().unwrap();
+        assert_iter_eq(rest, expected_rest.iter_mut());
+        *last += 1;
+        *expected_last += 1;
+    }
+    {
+        let (first, rest) = vec.split_first_mut().unwrap();
+        let (expected_first, expected_rest) = test_values.split_first_mut().unwrap();
+        *first = rest.fold(0, |sum: i32, value| sum.wrapping_add(*value));
+        *expected_first = expected_rest
+            .iter()
+            .fold(0, |sum: i32, value| sum.wrapping_add(*value));
+    }
+    assert_iter_eq(vec.iter(), test_values.iter());
+    let mut empty = <BucketVec<i32, C>>::new();
+    assert!(empty.split_first_mut().is_none());
+    assert!(empty.split_last_mut().is_none());
+}
+create_test_for_configs!(split_first_last_mut_works_for);
+
+fn swap_remove_last_works_for<C>(mut test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    assert_eq!(vec.swap_remove_last(vec.len()), None);
+    let mut index = 0;
+    while !test_values.is_empty() {
+        index = (index + 7) % test_values.len();
+        let first = vec.get(0).map(|value| value as *const i32);
+        assert_eq!(
+            vec.swap_remove_last(index),
+            Some(test_values.swap_remove(index))
+        );
+        if index != 0 && !test_values.is_empty() {
+            // Elements other than the last one are never moved.
+            assert_eq!(vec.get(0).map(|value| value as *const i32), first);
+        }
+        assert_eq!(vec.get(index), test_values.get(index));
+        if test_values.len().is_multiple_of(100) {
+            assert_iter_eq(vec.iter(), test_values.iter());
+        }
+    }
+    assert!(vec.is_empty());
+    assert_eq!(vec.swap_remove_last(0), None);
+}
+create_test_for_configs!(swap_remove_last_works_for);
+
+fn insert_remove_shift_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    let mut expected = Vec::new();
+    for (n, value) in test_values.iter().enumerate() {
+        let index = (n * 7) % (expected.len() + 1);
+        vec.insert_shift(index, *value);
+        expected.insert(index, *value);
+        assert_eq!(vec.get(index), expected.get(index));
+        assert_eq!(vec.len(), expected.len());
+    }
+    assert_iter_eq(vec.iter(), expected.iter());
+    assert_eq!(vec.remove_shift(vec.len()), None);
+    let mut n = 0;
+    while !expected.is_empty() {
+        let index = (n * 13) % expected.len();
+        let first = vec.get(0).map(|value| value as *const i32);
+        assert_eq!(vec.remove_shift(index), Some(expected.remove(index)));
+        if index != 0 && !expected.is_empty() {
+            // Elements before the removed one are never moved.
+            assert_eq!(vec.get(0).map(|value| value as *const i32), first);
+        }
+        if expected.len().is_multiple_of(100) {
+            assert_iter_eq(vec.iter(), expected.iter());
+        }
+        n += 1;
+    }
+    assert!(vec.is_empty());
+}
+create_test_for_configs!(insert_remove_shift_works_for);
+
+fn try_get_works_for<C>(mut test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    for (index, value) in test_values.iter_mut().enumerate() {
+        assert_eq!(vec.try_get(index), Ok(&*value));
+        assert_eq!(vec.try_get_mut(index), Ok(value));
+    }
+    let len = vec.len();
+    let spare = vec.capacity() - len;
+    if spare > 0 {
+        let (bucket_index, entry_index) = config::bucket_entry_indices::<C>(len);
+        let expected = IndexError::MissingEntry {
+            index: len,
+            len,
+            bucket_index,
+            entry_index,
+        };
+        assert_eq!(vec.try_get(len), Err(expected));
+        assert_eq!(vec.try_get_mut(len), Err(expected));
+    }
+    let index = len + spare;
+    let expected = IndexError::MissingBucket { index, len };
+    assert_eq!(vec.try_get(index), Err(expected));
+    assert_eq!(
+        vec.try_get_mut(usize::MAX).map(|_| ()),
+        Err(IndexError::MissingBucket {
+            index: usize::MAX,
+            len
+        })
+    );
+    assert!(expected.to_string().contains(&index.to_string()));
+}
+create_test_for_configs!(try_get_works_for);
+
+fn fallible_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <FallibleBucketVec<i32, C>>::new();
+    let (head, tail) = test_values.split_at(test_values.len() / 2);
+    for (index, value) in head.iter().enumerate() {
+        assert_eq!(vec.try_push(*value), Ok(index));
+    }
+    assert_eq!(vec.try_extend(tail.iter().copied()), Ok(()));
+    assert_iter_eq(vec.iter(), test_values.iter());
+    for (index, value) in test_values.iter().enumerate() {
+        assert_eq!(vec.try_get(index), Ok(value));
+    }
+    assert!(vec.try_get(test_values.len()).is_err());
+    assert_eq!(vec.try_reserve(100), Ok(()));
+    assert!(vec.capacity() >= vec.len() + 100);
+    assert_eq!(vec.capacity(), vec.as_bucket_vec().capacity());
+    assert_eq!(
+        vec.try_reserve(usize::MAX),
+        Err(ReserveError::CapacityOverflow)
+    );
+    let capacity = vec.capacity();
+    for value in 0..100 {
+        vec.try_push(value).unwrap();
+    }
+    assert_eq!(vec.capacity(), capacity);
+    assert_eq!(vec.into_bucket_vec().len(), test_values.len() + 100);
+}
+create_test_for_configs!(fallible_bucket_vec_works_for);
+
+#[test]
+fn fallible_bucket_vec_reports_failed_allocations() {
+    let mut vec = <FallibleBucketVec<u64, config::EqualSizeConfig<{ usize::MAX / 16 }>>>::new();
+    assert_eq!(vec.try_push(1), Err((1, ReserveError::AllocFailed)));
+    assert_eq!(vec.try_extend([2, 3]), Err((2, ReserveError::AllocFailed)));
+    assert!(vec.is_empty());
+}
+
+#[test]
+fn fallible_bucket_vec_ignores_size_hints() {
+    /// Yields a single element but claims to yield `usize::MAX` elements.
+    struct Lying(Option<i32>);
+    impl Iterator for Lying {
+        type Item = i32;
+        fn next(&mut self) -> Option<i32> {
+            self.0.take()
+        }
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (usize::MAX, None)
+        }
+    }
+    let mut vec = <FallibleBucketVec<i32>>::new();
+    assert_eq!(vec.try_extend(Lying(Some(42))), Ok(()));
+    assert_eq!(vec.try_get(0), Ok(&42));
+    assert_eq!(vec.capacity(), DefaultConfig::STARTING_CAPACITY);
+}
+
+fn dedup_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    // Produce runs of equal elements of varying lengths.
+    let values = test_values
+        .iter()
+        .enumerate()
+        .flat_map(|(n, value)| core::iter::repeat_n(*value % 5, n % 4))
+        .collect::<Vec<_>>();
+    let mut vec = values.iter().cloned().collect::<BucketVec<_, C>>();
+    let mut expected = values.clone();
+    vec.dedup();
+    expected.dedup();
+    assert_iter_eq(vec.iter(), expected.iter());
+    let mut vec = values.iter().cloned().collect::<BucketVec<_, C>>();
+    let mut expected = values.clone();
+    vec.dedup_by_key(|value| *value / 2);
+    expected.dedup_by_key(|value| *value / 2);
+    assert_iter_eq(vec.iter(), expected.iter());
+    let mut vec = values.iter().cloned().collect::<BucketVec<_, C>>();
+    let mut expected = values;
+    // Merges runs of ascending elements into their first element.
+    vec.dedup_by(|current, previous| {
+        let merge = *current > *previous;
+        if merge {
+            *previous = previous.wrapping_add(*current);
+        }
+        merge
+    });
+    expected.dedup_by(|current, previous| {
+        let merge = *current > *previous;
+        if merge {
+            *previous = previous.wrapping_add(*current);
+        }
+        merge
+    });
+    assert_iter_eq(vec.iter(), expected.iter());
+    vec.push(1);
+    assert_eq!(vec.last(), Some(&1));
+}
+create_test_for_configs!(dedup_works_for);
+
+#[test]
+#[should_panic(expected = "index 4 is out of bounds for bucket vector length 3")]
+fn insert_shift_panics_for_out_of_bounds_indices() {
+    let mut vec = [1, 2, 3].iter().cloned().collect::<BucketVec<i32>>();
+    vec.insert_shift(4, 4);
+}
+
+fn iter_indexed_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    assert_eq!(vec.iter_indexed().len(), test_values.len());
+    for (position, value) in vec.iter_indexed() {
+        let (bucket_index, entry_index) = config::bucket_entry_indices::<C>(position.index());
+        assert_eq!(position.bucket_index(), bucket_index);
+        assert_eq!(position.entry_index(), entry_index);
+        assert_eq!(value, &test_values[position.index()]);
+    }
+    let mut expected_index = test_values.len();
+    for (position, value) in vec.iter_indexed().rev() {
+        expected_index -= 1;
+        assert_eq!(position.index(), expected_index);
+        assert_eq!(value, &test_values[expected_index]);
+    }
+    assert_eq!(expected_index, 0);
+}
+create_test_for_configs!(iter_indexed_works_for);
+
+fn iter_mut_indexed_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let mut iter = vec.iter_mut_indexed();
+    let mut expected = test_values.iter().enumerate();
+    // Lock-step get `next` and `next_back` from iterators.
+    for step in 0..iter.len() {
+        let (position, value, (expected_index, expected_value)) = if step % 2 == 0 {
+            let (position, value) = iter.next().unwrap();
+            (position, value, expected.next().unwrap())
+        } else {
+            let (position, value) = iter.next_back().unwrap();
+            (position, value, expected.next_back().unwrap())
+        };
+        assert_eq!(position.index(), expected_index);
+        assert_eq!(value, expected_value);
+    }
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+}
+create_test_for_configs!(iter_mut_indexed_works_for);
+
+fn iter_nth_works_for<C>(mut test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    for step in &[0, 1, 2, 3, 7, 42] {
+        assert_iter_eq(
+            vec.iter().step_by(step + 1),
+            test_values.iter().step_by(step + 1),
+        );
+        assert_iter_eq(
+            vec.iter().rev().step_by(step + 1),
+            test_values.iter().rev().step_by(step + 1),
+        );
+        assert_iter_eq(vec.iter().skip(*step), test_values.iter().skip(*step));
+        assert_iter_eq(
+            vec.iter_mut().step_by(step + 1),
+            test_values.iter_mut().step_by(step + 1),
+        );
+        assert_iter_eq(
+            vec.clone().into_iter().rev().step_by(step + 1),
+            test_values.clone().into_iter().rev().step_by(step + 1),
+        );
+    }
+    // Alternate between `nth` and `nth_back` until both iterators meet.
+    let mut iter = vec.iter();
+    let mut expected = test_values.iter();
+    for step in 0.. {
+        let (actual, wanted) = if step % 2 == 0 {
+            (iter.nth(step), expected.nth(step))
+        } else {
+            (iter.nth_back(step), expected.nth_back(step))
+        };
+        assert_eq!(actual, wanted);
+        assert_eq!(iter.len(), expected.len());
+        if actual.is_none() {
+            break;
+        }
+    }
+    assert_eq!(vec.iter().nth(vec.len()), None);
+    assert_eq!(vec.into_iter().nth_back(usize::MAX), None);
+}
+create_test_for_configs!(iter_nth_works_for);
+
+fn iter_nth_jumps_to_any_element_for<C>(mut test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let len = test_values.len();
+    let steps = (0..=len)
+        .step_by(len / 64 + 1)
+        .chain(len.saturating_sub(3)..=len + 1);
+    for n in steps {
+        assert_eq!(vec.iter().nth(n), test_values.get(n));
+        assert_eq!(vec.iter().nth_back(n), test_values.iter().nth_back(n));
+        assert_eq!(vec.iter_mut().nth(n), test_values.get_mut(n));
+        assert_eq!(
+            vec.iter_mut().nth_back(n),
+            test_values.iter_mut().nth_back(n)
+        );
+        // Jump from within partially consumed buckets on both ends.
+        let mut iter = vec.clone().into_iter();
+        let mut expected = test_values.clone().into_iter();
+        assert_eq!(iter.next(), expected.next());
+        assert_eq!(iter.next_back(), expected.next_back());
+        assert_eq!(iter.nth(n / 2), expected.nth(n / 2));
+        assert_eq!(iter.nth_back(n / 3), expected.nth_back(n / 3));
+        assert_eq!(iter.len(), expected.len());
+        assert!(iter.eq(expected));
+    }
+    // Iterators over chunks only cover a range of the buckets.
+    for chunk_size in [3, 17] {
+        for (chunk, expected) in vec.chunks(chunk_size).zip(test_values.chunks(chunk_size)) {
+            for n in 0..=chunk_size {
+                assert_eq!(chunk.clone().nth(n), expected.get(n));
+                assert_eq!(chunk.clone().nth_back(n), expected.iter().nth_back(n));
+            }
+        }
+    }
+}
+create_test_for_configs!(iter_nth_jumps_to_any_element_for);
+
+fn iter_fold_works_for<C>(mut test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let push = |mut acc: Vec<i32>, value: i32| {
+        acc.push(value);
+        acc
+    };
+    let expected = test_values.clone();
+    let expected_rev = test_values.iter().rev().cloned().collect::<Vec<_>>();
+    assert_eq!(vec.iter().copied().fold(Vec::new(), push), expected);
+    assert_eq!(vec.iter().copied().rfold(Vec::new(), push), expected_rev);
+    assert_eq!(vec.iter_mut().map(|v| *v).fold(Vec::new(), push), expected);
+    assert_eq!(
+        vec.iter_mut().map(|v| *v).rfold(Vec::new(), push),
+        expected_rev
+    );
+    assert_eq!(vec.clone().into_iter().fold(Vec::new(), push), expected);
+    assert_eq!(
+        vec.clone().into_iter().rfold(Vec::new(), push),
+        expected_rev
+    );
+    // Folding a partially consumed iterator from both ends.
+    let mut iter = vec.iter().copied();
+    let mut expected_iter = test_values.iter().copied();
+    assert_eq!(iter.next(), expected_iter.next());
+    assert_eq!(iter.next_back(), expected_iter.next_back());
+    assert_eq!(
+        iter.fold(Vec::new(), push),
+        expected_iter.fold(Vec::new(), push)
+    );
+    // `for_each` is implemented in terms of `fold`.
+    vec.iter_mut()
+        .for_each(|value| *value = value.wrapping_add(1));
+    test_values
+        .iter_mut()
+        .for_each(|value| *value = value.wrapping_add(1));
+    assert_iter_eq(vec.iter(), test_values.iter());
+}
+create_test_for_configs!(iter_fold_works_for);
+
+fn iter_count_last_works_for<C>(mut test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    assert_eq!(vec.iter().count(), test_values.len());
+    assert_eq!(vec.iter_mut().count(), test_values.len());
+    assert_eq!(vec.iter_indexed().count(), test_values.len());
+    assert_eq!(vec.clone().into_iter().count(), test_values.len());
+    assert_eq!(vec.iter().last(), test_values.iter().last());
+    assert_eq!(vec.iter_mut().last(), test_values.iter_mut().last());
+    assert_eq!(
+        vec.clone().into_iter().last(),
+        test_values.clone().into_iter().last()
+    );
+    let mut iter = vec.iter().skip(1);
+    let mut expected = test_values.iter().skip(1);
+    assert_eq!(iter.next(), expected.next());
+    assert_eq!(iter.count(), expected.count());
+    // Exhausted iterators keep on yielding `None`.
+    let mut iter = vec.iter();
+    iter.by_ref().for_each(drop);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+}
+create_test_for_configs!(iter_count_last_works_for);
+
+fn iter_remaining_buckets_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let skip = test_values.len() / 3;
+    let mut iter = vec.iter();
+    iter.nth(skip);
+    iter.next_back();
+    let expected = test_values.iter().skip(skip + 1).rev().skip(1).rev();
+    assert!(iter.remaining_buckets().all(|entries| !entries.is_empty()));
+    assert!(iter.remaining_buckets().flatten().eq(expected.clone()));
+    assert_eq!(iter.next(), expected.clone().next());
+    let mut iter_mut = vec.iter_mut();
+    iter_mut.nth(skip);
+    assert!(iter_mut
+        .remaining_buckets()
+        .flatten()
+        .eq(test_values.iter().skip(skip + 1)));
+    for entries in iter_mut.into_remaining_buckets() {
+        entries.iter_mut().for_each(|value| *value = 0);
+    }
+    assert!(vec.iter().eq(test_values
+        .iter()
+        .take(skip + 1)
+        .chain(core::iter::repeat_n(
+            &0,
+            test_values.len().saturating_sub(skip + 1)
+        ))));
+}
+create_test_for_configs!(iter_remaining_buckets_works_for);
+
+fn stable_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    fn push_all<V>(vec: &mut V, test_values: &[i32])
+    where
+        V: StableVec<Item = i32>,
+    {
+        for (index, &value) in test_values.iter().enumerate() {
+            assert_eq!(vec.push(value), index);
+        }
+        assert_eq!(vec.len(), test_values.len());
+        assert_eq!(vec.is_empty(), test_values.is_empty());
+        assert!(vec.iter().eq(test_values.iter()));
+        if let Some(first) = vec.get_mut(0) {
+            *first = first.wrapping_add(1);
+        }
+        assert_eq!(
+            vec.get(0).copied(),
+            test_values.first().map(|value| value.wrapping_add(1))
+        );
+        assert_eq!(vec.get(test_values.len()), None);
+    }
+    push_all(&mut <BucketVec<i32, C>>::new(), &test_values);
+    push_all(
+        &mut DynBucketVec::from_config::<DefaultConfig>(),
+        &test_values,
+    );
+}
+create_test_for_configs!(stable_vec_works_for);
+
+#[test]
+fn default_iterators_are_empty() {
+    assert_eq!(<Iter<i32>>::default().next(), None);
+    assert_eq!(<IterMut<i32>>::default().next_back(), None);
+    assert_eq!(<IntoIter<i32>>::default().len(), 0);
+    assert_eq!(<IterIndexed<i32>>::default().next(), None);
+    assert_eq!(<IterIndexedMut<i32>>::default().next_back(), None);
+}
+
+fn into_iter_clone_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let mut iter = vec.into_iter();
+    let mut expected = test_values.into_iter();
+    assert_eq!(iter.next(), expected.next());
+    assert_eq!(iter.next_back(), expected.next_back());
+    assert_iter_eq(iter.clone(), expected.clone());
+    assert_iter_eq(iter, expected);
+}
+create_test_for_configs!(into_iter_clone_works_for);
+
+/// An element that counts how often elements sharing its counter have been dropped.
+#[derive(Debug)]
+struct DropCounted {
+    value: i32,
+    drops: Rc<core::cell::Cell<usize>>,
+}
+
+impl Drop for DropCounted {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+fn into_iter_alternating_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    for finish_back in [false, true] {
+        let drops = Rc::new(core::cell::Cell::new(0));
+        let vec = test_values
+            .iter()
+            .map(|&value| DropCounted {
+                value,
+                drops: drops.clone(),
+            })
+            .collect::<BucketVec<_, C>>();
+        let mut iter = vec.into_iter();
+        let mut expected = test_values.iter().copied();
+        for step in 0..expected.len() {
+            if step.is_multiple_of(3) {
+                assert_eq!(iter.next_back().map(|e| e.value), expected.next_back());
+            } else {
+                assert_eq!(iter.next().map(|e| e.value), expected.next());
+            }
+            assert_eq!(iter.len(), expected.len());
+            assert_eq!(drops.get(), step + 1);
+        }
+        // Polling the exhausted iterator from either end must free the buffers
+        // of the buckets that were drained last on both ends.
+        if finish_back {
+            assert!(iter.next_back().is_none());
+        } else {
+            assert!(iter.next().is_none());
+        }
+        let debug = format!("{:?}", iter);
+        assert!(debug.contains("front_iter: None"), "{}", debug);
+        assert!(debug.contains("back_iter: None"), "{}", debug);
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+        assert_eq!(drops.get(), test_values.len());
+    }
+}
+create_test_for_configs!(into_iter_alternating_works_for);
+
+#[cfg(feature = "rayon")]
+fn par_iter_works_for<C>(mut test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use ::rayon::prelude::*;
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    assert_eq!(
+        vec.par_iter().map(|&value| i64::from(value)).sum::<i64>(),
+        test_values
+            .iter()
+            .map(|&value| i64::from(value))
+            .sum::<i64>(),
+    );
+    assert_eq!(
+        vec.par_iter().collect::<Vec<_>>(),
+        test_values.iter().collect::<Vec<_>>()
+    );
+    vec.par_iter_mut()
+        .for_each(|value| *value = value.wrapping_mul(2));
+    test_values
+        .iter_mut()
+        .for_each(|value| *value = value.wrapping_mul(2));
+    assert_eq!(vec.into_par_iter().collect::<Vec<_>>(), test_values);
+}
+#[cfg(feature = "rayon")]
+create_test_for_configs!(par_iter_works_for);
+
+fn reserve_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    assert_eq!(vec.capacity(), 0);
+    vec.reserve(test_values.len());
+    let capacity = vec.capacity();
+    assert!(capacity >= test_values.len());
+    vec.extend(test_values.iter().cloned());
+    assert_eq!(vec.capacity(), capacity);
+    for (n, expected) in test_values.iter().enumerate() {
+        assert_eq!(vec.get(n), Some(expected));
+    }
+    assert_iter_eq(vec.iter(), test_values.iter());
+}
+create_test_for_configs!(reserve_works_for);
+
+#[cfg(feature = "serde")]
+fn serde_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let json = serde_json::to_string(&vec).unwrap();
+    assert_eq!(json, serde_json::to_string(&test_values).unwrap());
+    let decoded = serde_json::from_str::<BucketVec<i32, C>>(&json).unwrap();
+    assert_iter_eq(decoded.iter(), test_values.iter());
+}
+#[cfg(feature = "serde")]
+create_test_for_configs!(serde_works_for);
+
+#[cfg(feature = "defmt")]
+#[test]
+fn defmt_format_is_implemented() {
+    fn assert_format<T: ::defmt::Format>() {}
+    assert_format::<BucketVec<i32>>();
+    assert_format::<BucketVec<BucketVec<u8>, WastefulConfig>>();
+}
+
+#[cfg(feature = "bevy_reflect")]
+fn bevy_reflect_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig + 'static,
+{
+    use bevy_reflect::{FromReflect, List, PartialReflect, ReflectRef};
+    let mut vec = test_values
+        .as_slice()
+        .iter()
+        .copied()
+        .collect::<BucketVec<i32, C>>();
+    let list = match vec.reflect_ref() {
+        ReflectRef::List(list) => list,
+        _ => panic!("expected bucket vector to be reflected as a list"),
+    };
+    assert_eq!(list.len(), test_values.len());
+    for (element, value) in list.iter().zip(&test_values) {
+        assert_eq!(element.try_downcast_ref::<i32>(), Some(value));
+    }
+    List::push(&mut vec, Box::new(42));
+    List::insert(&mut vec, 0, Box::new(7));
+    assert_eq!(vec.first(), Some(&7));
+    assert_eq!(vec.last(), Some(&42));
+    let removed = List::remove(&mut vec, 0);
+    assert_eq!(removed.try_downcast_ref::<i32>(), Some(&7));
+    let cloned = <BucketVec<i32, C>>::from_reflect(&vec).unwrap();
+    assert_eq!(vec.reflect_partial_eq(&cloned), Some(true));
+    assert_eq!(cloned, vec);
+    let from_vec = <BucketVec<i32, C>>::from_reflect(&test_values).unwrap();
+    assert_eq!(from_vec, test_values);
+}
+#[cfg(feature = "bevy_reflect")]
+create_test_for_configs!(bevy_reflect_works_for);
+
+#[cfg(feature = "borsh")]
+fn borsh_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let encoded = ::borsh::to_vec(&vec).unwrap();
+    assert_eq!(encoded, ::borsh::to_vec(&test_values).unwrap());
+    let decoded = ::borsh::from_slice::<BucketVec<i32, C>>(&encoded).unwrap();
+    assert_iter_eq(decoded.iter(), test_values.iter());
+}
+#[cfg(feature = "borsh")]
+create_test_for_configs!(borsh_works_for);
+
+#[cfg(feature = "bincode")]
+fn bincode_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let config = ::bincode::config::standard();
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let encoded = ::bincode::encode_to_vec(&vec, config).unwrap();
+    assert_eq!(
+        encoded,
+        ::bincode::encode_to_vec(&test_values, config).unwrap()
+    );
+    let (decoded, read) =
+        ::bincode::decode_from_slice::<BucketVec<i32, C>, _>(&encoded, config).unwrap();
+    assert_eq!(read, encoded.len());
+    assert_iter_eq(decoded.iter(), test_values.iter());
+    // Borrow decoding of elements referencing the encoded buffer.
+    let strings = test_values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>();
+    let encoded = ::bincode::encode_to_vec(&strings, config).unwrap();
+    let (decoded, _) =
+        ::bincode::borrow_decode_from_slice::<BucketVec<&str, C>, _>(&encoded, config).unwrap();
+    assert_iter_eq(decoded.iter().copied(), strings.iter().map(String::as_str));
+}
+#[cfg(feature = "bincode")]
+create_test_for_configs!(bincode_works_for);
+
+#[cfg(feature = "arbitrary")]
+fn arbitrary_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use ::arbitrary::{Arbitrary, Unstructured};
+    let bytes = test_values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect::<Vec<u8>>();
+    let vec = BucketVec::<i32, C>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+    let expected = Vec::<i32>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+    assert_eq!(vec, expected);
+    let vec = BucketVec::<i32, C>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+    let expected = Vec::<i32>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+    assert_eq!(vec, expected);
+}
+#[cfg(feature = "arbitrary")]
+create_test_for_configs!(arbitrary_works_for);
+
+#[cfg(feature = "proptest")]
+fn proptest_strategy_works_for<C>(_test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use ::proptest::{
+        prelude::any,
+        strategy::{Strategy, ValueTree},
+        test_runner::TestRunner,
+    };
+    let boundary = C::total_capacity_for(1);
+    let mut boundary_hit = false;
+    let mut runner = TestRunner::deterministic();
+    let strategy = crate::bucket_vec_strategy::<_, C>(any::<i32>(), 1..=2 * boundary + 1);
+    for _ in 0..100 {
+        let vec = strategy.new_tree(&mut runner).unwrap().current();
+        assert!((1..=2 * boundary + 1).contains(&vec.len()));
+        assert_iter_eq(vec.iter(), (0..vec.len()).map(|i| vec.get(i).unwrap()));
+        boundary_hit |= vec.len() == boundary;
+    }
+    assert!(boundary_hit);
+}
+#[cfg(feature = "proptest")]
+create_test_for_configs!(proptest_strategy_works_for);
+
+#[cfg(feature = "scale-3")]
+fn scale_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use ::scale::{Decode as _, DecodeLength, Encode as _};
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let encoded = vec.encode();
+    assert_eq!(encoded, test_values.encode());
+    assert_eq!(vec.size_hint(), encoded.len());
+    assert_eq!(
+        <BucketVec<i32, C> as DecodeLength>::len(&encoded),
+        Ok(test_values.len())
+    );
+    let decoded = <BucketVec<i32, C>>::decode(&mut &encoded[..]).unwrap();
+    assert_iter_eq(decoded.iter(), test_values.iter());
+}
+#[cfg(feature = "scale-3")]
+create_test_for_configs!(scale_works_for);
+
+#[cfg(feature = "scale-3")]
+#[test]
+fn scale_type_info_works() {
+    use scale_info::TypeInfo as _;
+    assert_eq!(<BucketVec<i32>>::type_info(), <Vec<i32>>::type_info());
+}
+
+#[cfg(feature = "scale-3")]
+#[test]
+fn scale_decode_rejects_excessive_length() {
+    use ::scale::{Compact, Decode as _, Encode as _};
+    let mut encoded = Compact(u64::MAX).encode();
+    encoded.extend_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0]);
+    assert!(<BucketVec<i32>>::decode(&mut &encoded[..]).is_err());
+    let mut encoded = Compact(3_u64).encode();
+    encoded.extend_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0]);
+    assert!(<BucketVec<i32>>::decode(&mut &encoded[..]).is_err());
+}
+
+#[cfg(feature = "scale-3")]
+fn scale_decode_reports_every_bucket_allocation_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use ::scale::{Decode as _, Encode as _};
+
+    /// Input that sums up the memory announced to be allocated.
+    struct CountingInput<'a> {
+        bytes: &'a [u8],
+        allocated: usize,
+    }
+
+    impl ::scale::Input for CountingInput<'_> {
+        fn remaining_len(&mut self) -> Result<Option<usize>, ::scale::Error> {
+            self.bytes.remaining_len()
+        }
+
+        fn read(&mut self, into: &mut [u8]) -> Result<(), ::scale::Error> {
+            self.bytes.read(into)
+        }
+
+        fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), ::scale::Error> {
+            self.allocated += size;
+            Ok(())
+        }
+    }
+
+    let encoded = test_values.encode();
+    let mut input = CountingInput {
+        bytes: &encoded,
+        allocated: 0,
+    };
+    let decoded = <BucketVec<i32, C>>::decode(&mut input).unwrap();
+    assert_iter_eq(decoded.iter(), test_values.iter());
+    assert_eq!(
+        input.allocated,
+        decoded.capacity() * core::mem::size_of::<i32>()
+    );
+}
+#[cfg(feature = "scale-3")]
+create_test_for_configs!(scale_decode_reports_every_bucket_allocation_for);
+
+#[cfg(feature = "scale-1")]
+fn scale_1_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use ::scale1::{Decode as _, DecodeLength, Encode as _};
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let encoded = ::scale1::Encode::encode(&vec);
+    assert_eq!(encoded, test_values.encode());
+    assert_eq!(::scale1::Encode::size_hint(&vec), encoded.len());
+    assert_eq!(
+        <BucketVec<i32, C> as DecodeLength>::len(&encoded).unwrap(),
+        test_values.len()
+    );
+    let decoded = <BucketVec<i32, C>>::decode(&mut &encoded[..]).unwrap();
+    assert_iter_eq(decoded.iter(), test_values.iter());
+    // Lengths that the input cannot back up are rejected.
+    let mut truncated = ::scale1::Compact(u64::MAX).encode();
+    truncated.extend_from_slice(&encoded[1..]);
+    assert!(<BucketVec<i32, C>>::decode(&mut &truncated[..]).is_err());
+}
+#[cfg(feature = "scale-1")]
+create_test_for_configs!(scale_1_works_for);
+
+#[cfg(feature = "scale-3")]
+#[test]
+fn scale_capped_bucket_vec_works() {
+    use ::scale::{Decode as _, Encode as _, MaxEncodedLen as _};
+    let mut capped = <CappedBucketVec<u32, 3>>::new();
+    assert_eq!(<CappedBucketVec<u32, 3>>::max_encoded_len(), 1 + 3 * 4);
+    assert_eq!(capped.encode().len(), 1);
+    for value in 1..=3 {
+        capped.try_push(value).unwrap();
+    }
+    let encoded = capped.encode();
+    assert_eq!(encoded, vec![1_u32, 2, 3].encode());
+    assert_eq!(encoded.len(), <CappedBucketVec<u32, 3>>::max_encoded_len());
+    assert_eq!(
+        <CappedBucketVec<u32, 3>>::decode(&mut &encoded[..]),
+        Ok(capped)
+    );
+    // Lengths beyond the maximum length are rejected before decoding any element.
+    assert!(<CappedBucketVec<u32, 2>>::decode(&mut &encoded[..]).is_err());
+}
+
+#[cfg(feature = "std")]
+fn io_write_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use std::io::Write as _;
+    let mut vec = <BucketVec<u8, C>>::new();
+    let mut expected = Vec::new();
+    for value in &test_values {
+        write!(vec, "{},", value).unwrap();
+        write!(expected, "{},", value).unwrap();
+    }
+    vec.write_all(&[]).unwrap();
+    vec.flush().unwrap();
+    assert_iter_eq(vec.iter(), expected.iter());
+    for (n, expected) in expected.iter().enumerate() {
+        assert_eq!(vec.get(n), Some(expected));
+    }
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(io_write_works_for);
+
+fn extend_from_slice_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    let mut expected = Vec::new();
+    for chunk in test_values.chunks(7) {
+        vec.extend_from_slice(chunk);
+        expected.extend_from_slice(chunk);
+        assert_eq!(vec.len(), expected.len());
+        assert_eq!(vec.last(), expected.last());
+    }
+    for (n, expected) in expected.iter().enumerate() {
+        assert_eq!(vec.get(n), Some(expected));
+    }
+    assert_iter_eq(vec.iter().rev(), expected.iter().rev());
+}
+create_test_for_configs!(extend_from_slice_works_for);
+
+fn push_repeated_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    let mut expected = Vec::new();
+    for (count, &value) in test_values.iter().enumerate().take(50) {
+        vec.push_repeated(count, value);
+        expected.extend(core::iter::repeat_n(value, count));
+        assert_eq!(vec.len(), expected.len());
+    }
+    assert_iter_eq(vec.iter(), expected.iter());
+    vec.push(42);
+    assert_eq!(vec.last(), Some(&42));
+}
+create_test_for_configs!(push_repeated_works_for);
+
+fn alloc_slice_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let max_len = config::max_bucket_capacity::<C>().unwrap_or(usize::MAX);
+    let mut vec = <BucketVec<u8, C>>::new();
+    let mut allocated = Vec::new();
+    for value in &test_values {
+        let bytes = value.to_string().into_bytes();
+        if bytes.len() > max_len {
+            continue;
+        }
+        let stored = vec.alloc_slice(&bytes);
+        assert_eq!(stored, &bytes[..]);
+        allocated.push((stored.as_ptr() as usize, bytes));
+    }
+    // Previously allocated slices have not been moved.
+    let mut all = vec.iter_indexed();
+    for (address, bytes) in &allocated {
+        let (position, first) = all
+            .by_ref()
+            .find(|(_, first)| *first as *const u8 as usize == *address)
+            .unwrap();
+        assert_eq!(*first, bytes[0]);
+        for (offset, byte) in bytes.iter().enumerate() {
+            assert_eq!(vec.get(position.index() + offset), Some(byte));
+        }
+    }
+}
+create_test_for_configs!(alloc_slice_works_for);
+
+#[test]
+#[should_panic]
+fn alloc_slice_panics_for_oversized_slices() {
+    let mut vec = <BucketVec<u8, EqualSizeConfig>>::new();
+    vec.alloc_slice(&[1, 2, 3, 4, 5]);
+}
+
+fn bucket_string_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let max_len = config::max_bucket_capacity::<C>().unwrap_or(usize::MAX);
+    let mut arena = <BucketString<C>>::new();
+    assert!(arena.is_empty());
+    let mut spans = Vec::new();
+    for value in &test_values {
+        let string = format!("ä{}", value);
+        if string.len() > max_len {
+            continue;
+        }
+        assert_eq!(arena.push_str(&string), string);
+        let span = arena.push_str_span(&string);
+        assert_eq!(span.len(), string.len());
+        spans.push((span, string));
+    }
+    for (span, string) in &spans {
+        assert_eq!(arena.get(*span), Some(string.as_str()));
+    }
+}
+create_test_for_configs!(bucket_string_works_for);
+
+fn push_slice_padded_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    for chunk in test_values.chunks(3) {
+        let len_before = vec.len();
+        let (index, stored) = match vec.push_slice_padded(chunk, &0) {
+            Some(pushed) => pushed,
+            None => {
+                assert_eq!(vec.len(), len_before);
+                continue;
+            }
+        };
+        assert!(index >= len_before);
+        assert_eq!(stored, chunk);
+        // All values in front of the pushed values are padding.
+        for padding in len_before..index {
+            assert_eq!(vec.get(padding), Some(&0));
+        }
+        for (offset, value) in chunk.iter().enumerate() {
+            assert_eq!(vec.get(index + offset), Some(value));
+        }
+        assert_eq!(vec.len(), index + chunk.len());
+    }
+}
+create_test_for_configs!(push_slice_padded_works_for);
+
+/// A custom schedule whose bucket capacities stop growing at 8 without
+/// overriding `max_bucket_capacity`.
+#[derive(Debug)]
+enum UnboundedPlateauConfig {}
+
+impl BucketVecConfig for UnboundedPlateauConfig {
+    const STARTING_CAPACITY: usize = 1;
+    const GROWTH_RATE: f64 = 2.0;
+    const GEOMETRIC: bool = false;
+
+    fn bucket_capacity(bucket_index: usize) -> usize {
+        1 << core::cmp::min(bucket_index, 3)
+    }
+}
+
+#[test]
+fn push_slice_padded_pads_for_custom_schedules() {
+    let mut vec = <BucketVec<i32, UnboundedPlateauConfig>>::new();
+    vec.push(1);
+    // The second bucket only holds 2 values so it is padded and the values
+    // are stored in the third bucket holding 4 values.
+    let (index, stored) = vec.push_slice_padded(&[2, 3, 4], &0).unwrap();
+    assert_eq!(index, 3);
+    assert_eq!(stored, [2, 3, 4]);
+    assert_eq!(vec, [1, 0, 0, 2, 3, 4]);
+    // Bucket capacities plateau at 8 so no bucket holds 9 values.
+    assert!(vec.push_slice_padded(&[5; 9], &0).is_none());
+    assert_eq!(vec.len(), 6);
+    let (index, _) = vec.push_slice_padded(&[5; 8], &0).unwrap();
+    assert_eq!(index, 7);
+    assert_eq!(vec.get(6), Some(&0));
+    assert_eq!(vec.len(), 15);
+}
+
+/// A custom schedule whose bucket capacities grow by one with every bucket.
+#[derive(Debug)]
+enum LinearConfig {}
+
+impl BucketVecConfig for LinearConfig {
+    const STARTING_CAPACITY: usize = 1;
+    const GROWTH_RATE: f64 = 1.0;
+    const GEOMETRIC: bool = false;
+
+    fn bucket_capacity(bucket_index: usize) -> usize {
+        bucket_index + 1
+    }
+}
+
+#[test]
+fn push_slice_padded_bounds_padding_for_slowly_growing_schedules() {
+    let mut vec = <BucketVec<i32, LinearConfig>>::new();
+    vec.push(1);
+    // Reaching a bucket of 1000 values would require padding of half a million.
+    assert!(vec.push_slice_padded(&[2; 1000], &0).is_none());
+    assert_eq!(vec.len(), 1);
+    // Buckets of 2 and 3 values are skipped with a padding of 5.
+    let (index, _) = vec.push_slice_padded(&[2; 4], &0).unwrap();
+    assert_eq!(index, 6);
+    assert_eq!(vec.len(), 10);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn push_slice_padded_counts_padding_pushed_before_a_panic() {
+    #[derive(Debug, PartialEq)]
+    struct Fragile(i32);
+
+    impl Clone for Fragile {
+        fn clone(&self) -> Self {
+            if self.0 < 0 {
+                panic!("cannot clone negative values")
+            }
+            Fragile(self.0)
+        }
+    }
+
+    let mut vec = <BucketVec<Fragile, UnboundedPlateauConfig>>::new();
+    vec.push(Fragile(1));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        vec.push_slice_padded(&[Fragile(2), Fragile(3), Fragile(-1)], &Fragile(0));
+    }));
+    assert!(result.is_err());
+    // The padding and the values cloned before the panic are accounted for.
+    assert_eq!(
+        vec,
+        [Fragile(1), Fragile(0), Fragile(0), Fragile(2), Fragile(3)]
+    );
+    vec.push(Fragile(4));
+    assert_eq!(vec.len(), 6);
+    assert_eq!(vec.get(5), Some(&Fragile(4)));
+}
+
+fn try_push_slice_contiguous_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    for chunk in test_values.chunks(3) {
+        let len_before = vec.len();
+        let capacity = match vec.spare {
+            0 => config::bucket_capacity::<C>(vec.buckets.len()),
+            spare => spare,
+        };
+        match vec.try_push_slice_contiguous(chunk) {
+            Some((index, stored)) => {
+                assert!(chunk.len() <= capacity);
+                // The values are never preceded by padding.
+                assert_eq!(index, len_before);
+                assert_eq!(stored, chunk);
+            }
+            None => {
+                assert!(chunk.len() > capacity);
+                assert_eq!(vec.len(), len_before);
+                vec.extend_from_slice(chunk);
+            }
+        }
+        for (offset, value) in chunk.iter().enumerate() {
+            assert_eq!(vec.get(len_before + offset), Some(value));
+        }
+        assert_eq!(vec.len(), len_before + chunk.len());
+    }
+}
+create_test_for_configs!(try_push_slice_contiguous_works_for);
+
+fn get_cursor_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let len = vec.len();
+    let mut cursor = vec.get_cursor();
+    for index in (0..len).chain((0..len).rev()) {
+        assert_eq!(cursor.get(index), vec.get(index));
+    }
+    for index in (0..len).step_by(3).chain(core::iter::once(len)) {
+        assert_eq!(cursor.get(index), vec.get(index));
+        assert_eq!(cursor.get(index + 1), vec.get(index + 1));
+    }
+    let zsts = core::iter::repeat_n((), len).collect::<BucketVec<(), C>>();
+    let mut cursor = zsts.get_cursor();
+    for index in 0..=len {
+        assert_eq!(cursor.get(index), zsts.get(index));
+    }
+}
+create_test_for_configs!(get_cursor_works_for);
+
+fn select_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let len = test_values.len();
+    let indices = (0..len)
+        .step_by(3)
+        .chain((0..len).rev().step_by(7))
+        .chain((0..len).map(|index| index * 31 % len.max(1)))
+        .collect::<Vec<_>>();
+    assert!(vec
+        .select(indices.iter().copied())
+        .eq(indices.iter().map(|&index| &test_values[index])));
+    assert!(vec.try_select(vec![0, len, 1]).eq(vec![
+        test_values.first(),
+        None,
+        test_values.get(1)
+    ]));
+}
+create_test_for_configs!(select_works_for);
+
+#[test]
+#[should_panic(expected = "index 3 is out of bounds for bucket vector length 3")]
+fn select_panics_for_out_of_bounds_indices() {
+    let vec = [1, 2, 3].iter().cloned().collect::<BucketVec<i32>>();
+    vec.select(vec![0, 3]).for_each(drop);
+}
+
+fn index_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    for (index, value) in test_values.iter().enumerate() {
+        assert_eq!(vec[index], *value);
+        vec[index] += 1;
+        assert_eq!(vec[index], *value + 1);
+    }
+}
+create_test_for_configs!(index_works_for);
+
+#[test]
+#[should_panic(expected = "index 3 is out of bounds for bucket vector length 3")]
+fn index_panics_for_out_of_bounds_indices() {
+    let mut vec = [1, 2, 3].iter().cloned().collect::<BucketVec<i32>>();
+    vec[3] += 1;
+}
+
+fn layout_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    for (position, _) in vec.iter_indexed() {
+        assert_eq!(
+            Layout::<C>::bucket_entry_indices(position.index()),
+            (position.bucket_index(), position.entry_index())
+        );
+    }
+    let stats = vec.memory_stats();
+    let mut total = 0;
+    for (index, bucket) in stats.buckets().iter().enumerate() {
+        assert_eq!(Layout::<C>::bucket_capacity(index), bucket.capacity());
+        total += bucket.capacity();
+        assert_eq!(Layout::<C>::total_capacity(index + 1), total);
+        assert_eq!(Layout::<C>::checked_total_capacity(index + 1), Some(total));
+    }
+    if let Some(max) = Layout::<C>::max_bucket_capacity() {
+        assert!(stats
+            .buckets()
+            .iter()
+            .all(|bucket| bucket.capacity() <= max));
+    }
+}
+create_test_for_configs!(layout_works_for);
+
+fn push_key_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    let keys = test_values
+        .iter()
+        .map(|value| vec.push_key(*value))
+        .collect::<Vec<_>>();
+    for (index, (key, value)) in keys.iter().zip(&test_values).enumerate() {
+        assert_eq!(key.index(), index);
+        assert_eq!(vec.get_key(*key), Some(value));
+        *vec.get_key_mut(*key).unwrap() += 1;
+        assert_eq!(vec.get_key(*key), Some(&(value + 1)));
+    }
+    assert_eq!(vec.get_key(Key::new(test_values.len())), None);
+    assert!(keys.windows(2).all(|window| window[0] < window[1]));
+}
+create_test_for_configs!(push_key_works_for);
+
+fn get_mut_or_grow_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    for (index, value) in test_values.iter().enumerate().rev().step_by(2) {
+        *vec.get_mut_or_grow(index, || -1) = *value;
+    }
+    assert_eq!(vec.len(), test_values.len());
+    let expected = test_values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            if (test_values.len() - 1 - index).is_multiple_of(2) {
+                *value
+            } else {
+                -1
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_iter_eq(vec.iter(), expected.iter());
+    let first = vec.get(0).map(|value| value as *const i32);
+    *vec.get_mut_or_grow(0, || unreachable!()) += 1;
+    assert_eq!(vec.len(), test_values.len());
+    assert_eq!(vec.get(0).map(|value| value as *const i32), first);
+}
+create_test_for_configs!(get_mut_or_grow_works_for);
+
+fn write_many_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let mut expected = test_values.clone();
+    let len = test_values.len();
+    let updates = (0..len)
+        .map(|index| index * 17 % len.max(1))
+        .chain((0..len).step_by(5))
+        .enumerate()
+        .map(|(value, index)| (index, value as i32))
+        .collect::<Vec<_>>();
+    for &(index, value) in &updates {
+        expected[index] = value;
+    }
+    vec.write_many(updates);
+    assert_iter_eq(vec.iter(), expected.iter());
+    vec.write_many(core::iter::empty());
+    assert_iter_eq(vec.iter(), expected.iter());
+}
+create_test_for_configs!(write_many_works_for);
+
+#[test]
+#[cfg(feature = "std")]
+fn write_many_writes_nothing_for_out_of_bounds_indices() {
+    let mut vec = [1, 2, 3].iter().cloned().collect::<BucketVec<i32>>();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        vec.write_many(vec![(0, 10), (3, 30)]);
+    }));
+    assert!(result.is_err());
+    assert_iter_eq(vec.iter(), [1, 2, 3].iter());
+}
+
+fn pop_while_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let is_odd = |value: &i32| value % 2 != 0;
+    let tail = test_values
+        .iter()
+        .rev()
+        .take_while(|value| is_odd(value))
+        .count();
+    let kept = test_values.len() - tail;
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let popped = vec.pop_while(is_odd);
+    assert!(popped.iter().eq(test_values[kept..].iter().rev()));
+    assert_iter_eq(vec.iter(), test_values[..kept].iter());
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let first = vec.first().map(|value| value as *const i32);
+    vec.truncate_while(is_odd);
+    assert_iter_eq(vec.iter(), test_values[..kept].iter());
+    vec.truncate_while(|_| true);
+    assert!(vec.is_empty());
+    assert!(vec.pop_while(|_| true).is_empty());
+    vec.extend(test_values.iter().cloned());
+    assert_eq!(vec.first().map(|value| value as *const i32), first);
+    assert_iter_eq(vec.iter(), test_values.iter());
+}
+create_test_for_configs!(pop_while_works_for);
+
+fn checkpoint_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let (head, tail) = test_values.split_at(test_values.len() / 2);
+    let mut vec = head.iter().cloned().collect::<BucketVec<_, C>>();
+    let addresses = vec
+        .iter()
+        .map(|value| value as *const i32)
+        .collect::<Vec<_>>();
+    let outer = vec.checkpoint();
+    assert_eq!(outer.len(), head.len());
+    vec.extend(tail.iter().cloned());
+    let inner = vec.checkpoint();
+    vec.extend(head.iter().cloned());
+    vec.rollback_to(inner);
+    assert_iter_eq(vec.iter(), test_values.iter());
+    vec.rollback_to(outer);
+    assert_iter_eq(vec.iter(), head.iter());
+    assert!(vec
+        .iter()
+        .map(|value| value as *const i32)
+        .eq(addresses.iter().cloned()));
+    vec.rollback_to(outer);
+    assert_eq!(vec.len(), head.len());
+}
+create_test_for_configs!(checkpoint_works_for);
+
+fn try_extend_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    let appended = vec.try_extend(test_values.iter().map(|value| Ok::<_, ()>(*value)));
+    assert_eq!(appended, Ok(test_values.len()));
+    assert_iter_eq(vec.iter(), test_values.iter());
+    let failing = test_values.len() / 2;
+    let values = test_values.iter().enumerate().map(|(index, value)| {
+        if index == failing {
+            Err(index)
+        } else {
+            Ok(*value)
+        }
+    });
+    assert_eq!(vec.try_extend(values), Err(failing));
+    assert_eq!(vec.len(), test_values.len() + failing);
+    assert!(vec
+        .iter()
+        .skip(test_values.len())
+        .eq(test_values[..failing].iter()));
+    assert_eq!(
+        vec.try_extend(core::iter::empty::<Result<i32, ()>>()),
+        Ok(0)
+    );
+}
+create_test_for_configs!(try_extend_works_for);
+
+fn concat_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let parts = test_values
+        .chunks(7)
+        .map(|chunk| chunk.iter().cloned().collect::<BucketVec<_, C>>())
+        .collect::<Vec<_>>();
+    let first = parts
+        .first()
+        .and_then(|part| part.first())
+        .map(|value| value as *const i32);
+    let concat = BucketVec::concat(
+        core::iter::once(BucketVec::new())
+            .chain(parts)
+            .chain(core::iter::once(BucketVec::new())),
+    );
+    assert_iter_eq(concat.iter(), test_values.iter());
+    assert_eq!(concat.first().map(|value| value as *const i32), first);
+    assert!(BucketVec::<i32, C>::concat(core::iter::empty()).is_empty());
+}
+create_test_for_configs!(concat_works_for);
+
+#[test]
+fn concat_adopts_aligned_buckets() {
+    let first = (0..4).collect::<BucketVec<i32, EqualSizeConfig>>();
+    let second = (4..14).collect::<BucketVec<i32, EqualSizeConfig>>();
+    let third = (14..17).collect::<BucketVec<i32, EqualSizeConfig>>();
+    let fourth = (17..20).collect::<BucketVec<i32, EqualSizeConfig>>();
+    let addresses = |vec: &BucketVec<i32, EqualSizeConfig>| {
+        vec.iter()
+            .map(|value| value as *const i32)
+            .collect::<Vec<_>>()
+    };
+    let aligned = [&first, &second]
+        .iter()
+        .flat_map(|part| addresses(part))
+        .collect::<Vec<_>>();
+    let unaligned = addresses(&fourth);
+    let concat = BucketVec::concat(vec![first, second, third, fourth]);
+    assert!(concat.iter().copied().eq(0..20));
+    // The first part ends on a bucket boundary so that all buckets of the
+    // second part are adopted.
+    assert_eq!(&addresses(&concat)[..14], &aligned[..]);
+    // The second part does not end on a bucket boundary so that the
+    // elements of all following parts are moved.
+    assert!(addresses(&concat)[17..]
+        .iter()
+        .all(|address| !unaligned.contains(address)));
+}
+
+fn cells_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let cells = vec.cells();
+    assert_eq!(cells.len(), test_values.len());
+    assert!(cells
+        .iter()
+        .map(core::cell::Cell::get)
+        .eq(test_values.iter().cloned()));
+    // Two closures mutating the same elements through shared references.
+    let increment = |index| {
+        cells
+            .get(index)
+            .map(|cell| cell.set(cell.get().wrapping_add(1)))
+    };
+    let double = |index| {
+        cells
+            .get(index)
+            .map(|cell| cell.set(cell.get().wrapping_mul(2)))
+    };
+    for index in 0..test_values.len() {
+        increment(index);
+        double(index);
+    }
+    assert_eq!(cells.get(test_values.len()), None);
+    assert!(vec.iter().copied().eq(test_values
+        .iter()
+        .map(|value| value.wrapping_add(1).wrapping_mul(2))));
+    if let Some(cell) = vec.get_cell(0) {
+        cell.set(42);
+        assert_eq!(vec.first(), Some(&42));
+    }
+}
+create_test_for_configs!(cells_works_for);
+
+fn try_slice_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    let mut records = Vec::new();
+    for record in test_values.chunks(3) {
+        if let Some((index, _)) = vec.push_slice_padded(record, &0) {
+            records.push((index, record));
+        }
+    }
+    for &(index, record) in &records {
+        assert_eq!(vec.try_slice(index..index + record.len()), Some(record));
+        assert_eq!(
+            vec.try_slice_mut(index..=index + record.len() - 1)
+                .map(|entries| &*entries),
+            Some(record)
+        );
+    }
+    assert_eq!(vec.try_slice(vec.len()..), Some(&[][..]));
+    assert_eq!(vec.try_slice(..vec.len() + 1), None);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = vec.try_slice(1..0);
+    assert_eq!(reversed, None);
+    // A range spanning multiple buckets is never contiguous.
+    if vec.buckets.len() > 1 {
+        assert_eq!(vec.try_slice(..), None);
+    }
+}
+create_test_for_configs!(try_slice_works_for);
+
+fn persistent_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    // Checking every version takes quadratic time.
+    let test_values = &test_values[..test_values.len().min(1_000)];
+    let mut versions = vec![<PersistentBucketVec<i32, C>>::new()];
+    for &value in test_values {
+        let pushed = versions.last().unwrap().push(value);
+        versions.push(pushed);
+    }
+    for (len, version) in versions.iter().enumerate() {
+        assert_eq!(version.len(), len);
+        assert!(version.iter().eq(test_values[..len].iter()));
+        assert_eq!(version.get(len), None);
+        if let Some(last) = len.checked_sub(1) {
+            assert_eq!(version.get(last), Some(&test_values[last]));
+        }
+    }
+    // Elements of full buckets are shared between all later versions.
+    let latest = versions.last().unwrap();
+    if test_values.len() > config::bucket_capacity::<C>(0) {
+        let first = versions[config::bucket_capacity::<C>(0)].get(0).unwrap();
+        assert!(core::ptr::eq(first, latest.get(0).unwrap()));
+    }
+    let mut extended = latest.clone();
+    extended.push_mut(42);
+    assert_eq!(latest.len(), test_values.len());
+    assert_eq!(extended.get(test_values.len()), Some(&42));
+}
+create_test_for_configs!(persistent_bucket_vec_works_for);
+
+fn leak_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig + 'static,
+{
+    let leaked: &'static mut BucketVec<i32, C> = test_values
+        .iter()
+        .cloned()
+        .collect::<BucketVec<_, C>>()
+        .leak();
+    let first = leaked.first().map(|value| value as *const i32);
+    leaked.push(42);
+    assert_eq!(leaked.first().map(|value| value as *const i32), first);
+    let table: &'static BucketVec<i32, C> = leaked;
+    assert!(table.iter().eq(test_values.iter().chain(Some(&42))));
+}
+create_test_for_configs!(leak_works_for);
+
+fn bucket_pool_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut pool = BucketPool::new();
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let len_buckets = vec.memory_stats().len_buckets();
+    let capacity = vec.capacity();
+    vec.clear_into(&mut pool);
+    assert!(vec.is_empty());
+    assert_eq!(vec.capacity(), 0);
+    assert_eq!(pool.len(), len_buckets);
+    assert_eq!(pool.capacity(), capacity);
+    let mut recycled = <BucketVec<i32, C>>::new();
+    recycled.reserve_from_pool(test_values.len(), &mut pool);
+    // The same buckets are required again so all of them are drawn from the pool.
+    assert!(pool.is_empty());
+    assert_eq!(recycled.memory_stats().len_buckets(), len_buckets);
+    recycled.extend(test_values.iter().copied());
+    assert_iter_eq(recycled.iter(), test_values.iter());
+    vec.extend(test_values.iter().copied());
+    assert_iter_eq(vec.iter(), test_values.iter());
+    recycled.recycle(&mut pool);
+    assert_eq!(pool.len(), len_buckets);
+    pool.clear();
+    assert!(pool.is_empty());
+}
+create_test_for_configs!(bucket_pool_works_for);
+
+#[test]
+fn bucket_pool_works_for_zero_sized_elements() {
+    let mut pool = BucketPool::new();
+    for _ in 0..2 {
+        let mut vec = <BucketVec<()>>::new();
+        vec.extend(core::iter::repeat_n((), 10));
+        vec.recycle(&mut pool);
+    }
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.capacity(), usize::MAX);
+    let mut recycled = <BucketVec<()>>::new();
+    recycled.reserve_from_pool(10, &mut pool);
+    recycled.extend(core::iter::repeat_n((), 10));
+    assert_eq!(recycled.len(), 10);
+    assert_eq!(recycled.get(9), Some(&()));
+}
+
+#[test]
+#[should_panic(expected = "exceeds bucket vector length")]
+fn rollback_to_panics_for_stale_checkpoints() {
+    let mut vec = <BucketVec<i32>>::new();
+    let outer = vec.checkpoint();
+    vec.push(1);
+    let inner = vec.checkpoint();
+    vec.rollback_to(outer);
+    vec.rollback_to(inner);
+}
+
+fn try_push_key_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    for (index, value) in test_values.iter().enumerate() {
+        let key = vec.try_push_key::<u32>(*value).unwrap();
+        assert_eq!(key.index(), index);
+        assert_eq!(vec.get_key(key), Some(value));
+    }
+    assert_eq!(core::mem::size_of::<Key<i32, u32>>(), 4);
+    assert_eq!(
+        vec.get_key(Key::<i32, u32>::try_new(vec.len()).unwrap()),
+        None
+    );
+}
+create_test_for_configs!(try_push_key_works_for);
+
+#[test]
+fn try_push_key_rejects_overflowing_indices() {
+    let mut vec = <BucketVec<u8>>::new();
+    vec.extend(core::iter::repeat_n(0, usize::from(u16::MAX) + 1));
+    assert_eq!(vec.try_push_key::<u16>(42), Err(42));
+    assert_eq!(vec.len(), usize::from(u16::MAX) + 1);
+    assert!(vec.try_push_key::<u32>(42).is_ok());
+    assert_eq!(Key::<u8, u16>::try_new(usize::from(u16::MAX) + 1), None);
+}
+
+fn gen_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <GenBucketVec<i32, C>>::new();
+    let keys = test_values
+        .iter()
+        .map(|value| vec.push(*value))
+        .collect::<Vec<_>>();
+    assert_eq!(vec.len(), test_values.len());
+    for (key, value) in keys.iter().zip(&test_values) {
+        assert_eq!(vec.get_gen(*key), Some(value));
+    }
+    let half = test_values.len() / 2;
+    vec.truncate(half);
+    assert_eq!(vec.len(), half);
+    // Re-push elements at the removed indices.
+    let new_keys = test_values[half..]
+        .iter()
+        .map(|value| vec.push(value + 1))
+        .collect::<Vec<_>>();
+    for (key, value) in keys.iter().zip(&test_values) {
+        if key.index() < half {
+            assert_eq!(vec.get_gen(*key), Some(value));
+        } else {
+            assert_eq!(vec.get_gen(*key), None);
+            assert_eq!(vec.get(key.index()), Some(&(value + 1)));
+        }
+    }
+    for (key, value) in new_keys.iter().zip(&test_values[half..]) {
+        assert_eq!(vec.get_gen(*key), Some(&(value + 1)));
+    }
+    if let Some(last) = new_keys.last() {
+        assert_eq!(vec.pop(), test_values.last().map(|value| value + 1));
+        assert_eq!(vec.get_gen(*last), None);
+        vec.push(0);
+        assert_eq!(vec.get_gen(*last), None);
+    }
+}
+create_test_for_configs!(gen_bucket_vec_works_for);
+
+fn truncate_keeps_buckets_reserved_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let capacity = vec.capacity();
+    vec.truncate(test_values.len() / 3);
+    assert_eq!(vec.capacity(), capacity);
+    assert!(vec.iter().eq(&test_values[..test_values.len() / 3]));
+    vec.extend(test_values[test_values.len() / 3..].iter().copied());
+    assert!(vec.iter().eq(&test_values));
+    assert_eq!(vec.capacity(), capacity);
+    while vec.pop().is_some() {}
+    assert!(vec.is_empty());
+    assert_eq!(vec.capacity(), capacity);
+    vec.extend(test_values.iter().copied());
+    assert!(vec.iter().eq(&test_values));
+}
+create_test_for_configs!(truncate_keeps_buckets_reserved_for);
+
+fn slot_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <SlotBucketVec<i32, C>>::new();
+    let indices = test_values
+        .iter()
+        .map(|value| vec.insert(*value))
+        .collect::<Vec<_>>();
+    assert_eq!(vec.len(), test_values.len());
+    // Remove every other element.
+    for index in indices.iter().step_by(2) {
+        assert_eq!(vec.remove(*index), Some(test_values[*index]));
+        assert_eq!(vec.remove(*index), None);
+        assert!(!vec.contains(*index));
+    }
+    assert_eq!(vec.len(), test_values.len() / 2);
+    assert!(vec
+        .iter()
+        .eq(test_values.iter().enumerate().skip(1).step_by(2)));
+    assert!(vec
+        .iter()
+        .rev()
+        .eq(test_values.iter().enumerate().skip(1).step_by(2).rev()));
+    let survivors = vec
+        .iter()
+        .map(|(index, value)| (index, value as *const i32 as usize))
+        .collect::<Vec<_>>();
+    // Insertions reuse the vacated slots.
+    let mut reused = indices
+        .iter()
+        .step_by(2)
+        .map(|_| vec.insert(0))
+        .collect::<Vec<_>>();
+    reused.sort_unstable();
+    assert!(reused
+        .iter()
+        .copied()
+        .eq(indices.iter().copied().step_by(2)));
+    assert_eq!(vec.len(), test_values.len());
+    for (index, address) in survivors {
+        assert_eq!(
+            vec.get(index).map(|value| value as *const i32 as usize),
+            Some(address)
+        );
+    }
+    for (_, value) in vec.iter_mut() {
+        *value += 1;
+    }
+    assert_eq!(vec.insert(0), test_values.len());
+}
+create_test_for_configs!(slot_bucket_vec_works_for);
+
+fn bucket_vec_deque_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut deque = <BucketVecDeque<i32, C>>::new();
+    let mut expected = VecDeque::new();
+    for (n, value) in test_values.iter().enumerate() {
+        if n % 3 == 0 {
+            deque.push_front(*value);
+            expected.push_front(*value);
+        } else {
+            deque.push_back(*value);
+            expected.push_back(*value);
+        }
+    }
+    assert_eq!(deque.len(), expected.len());
+    assert!(deque.iter().eq(expected.iter()));
+    assert!(deque.iter().rev().eq(expected.iter().rev()));
+    assert_eq!(deque.front(), expected.front());
+    assert_eq!(deque.back(), expected.back());
+    for index in 0..expected.len() {
+        assert_eq!(deque.get(index), expected.get(index));
+        assert_eq!(deque.get_mut(index), expected.get_mut(index));
+    }
+    let addresses = deque
+        .iter()
+        .map(|value| value as *const i32 as usize)
+        .collect::<Vec<_>>();
+    // Use the bucket queue as FIFO and check that elements never move.
+    for (n, value) in test_values.iter().enumerate() {
+        assert_eq!(deque.pop_front(), expected.pop_front());
+        if let Some(address) = addresses.get(n + 1) {
+            assert_eq!(
+                deque.front().map(|value| value as *const i32 as usize),
+                Some(*address)
+            );
+        }
+        deque.push_back(*value);
+        expected.push_back(*value);
+    }
+    assert!(deque.iter().eq(expected.iter()));
+    while let Some(back) = expected.pop_back() {
+        assert_eq!(deque.pop_back(), Some(back));
+    }
+    assert!(deque.is_empty());
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+}
+create_test_for_configs!(bucket_vec_deque_works_for);
+
+fn frozen_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <FrozenBucketVec<i32, C>>::new();
+    // Keep references to all elements while pushing through `&self`.
+    let pushed = test_values
+        .iter()
+        .map(|value| vec.push(*value))
+        .collect::<Vec<_>>();
+    assert_eq!(vec.len(), test_values.len());
+    assert!(pushed.iter().copied().eq(&test_values));
+    for (index, (reference, value)) in pushed.iter().zip(&test_values).enumerate() {
+        assert!(core::ptr::eq(vec.get(index).unwrap(), *reference));
+        assert_eq!(*reference, value);
+    }
+    assert_eq!(vec.get(test_values.len()), None);
+    assert!(vec.iter().eq(&test_values));
+    assert!(vec.iter().rev().eq(test_values.iter().rev()));
+    for index in 0..test_values.len() {
+        *vec.get_mut(index).unwrap() += 1;
+    }
+    assert!(vec.iter().eq(test_values
+        .iter()
+        .map(|value| value + 1)
+        .collect::<Vec<_>>()
+        .iter()));
+}
+create_test_for_configs!(frozen_bucket_vec_works_for);
+
+fn lazy_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <LazyBucketVec<i32, C>>::new();
+    assert_eq!(vec.get(0), None);
+    // Initialize every other slot in reverse while keeping references.
+    let initialized = test_values
+        .iter()
+        .enumerate()
+        .rev()
+        .step_by(2)
+        .map(|(index, &value)| (index, vec.get_or_init_at(index, || value)))
+        .collect::<Vec<_>>();
+    assert_eq!(vec.len(), test_values.len());
+    for &(index, reference) in &initialized {
+        assert!(core::ptr::eq(vec.get(index).unwrap(), reference));
+        // Initialized slots keep their value.
+        assert!(core::ptr::eq(vec.get_or_init_at(index, || 0), reference));
+    }
+    assert!(vec.iter().eq(test_values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| ((test_values.len() - index) % 2 == 1).then_some(value))));
+    if let Some(value) = vec.get_mut(test_values.len().wrapping_sub(1)) {
+        *value = 42;
+        assert_eq!(vec.get(test_values.len() - 1), Some(&42));
+    }
+}
+create_test_for_configs!(lazy_bucket_vec_works_for);
+
+#[test]
+fn lazy_bucket_vec_memoizes_recursion() {
+    fn fibonacci(memo: &LazyBucketVec<u64>, n: usize) -> u64 {
+        *memo.get_or_init_at(n, || match n {
+            0 | 1 => n as u64,
+            _ => fibonacci(memo, n - 1) + fibonacci(memo, n - 2),
+        })
+    }
+    let memo = LazyBucketVec::new();
+    assert_eq!(fibonacci(&memo, 90), 2_880_067_194_370_816_120);
+    assert_eq!(memo.len(), 91);
+    assert!(memo.iter().all(|value| value.is_some()));
+}
+
+#[cfg(feature = "std")]
+fn concurrent_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = <ConcurrentBucketVec<i32, C>>::new();
+    let len_threads = 4;
+    std::thread::scope(|scope| {
+        for thread in 0..len_threads {
+            let vec = &vec;
+            let test_values = &test_values;
+            scope.spawn(move || {
+                for value in test_values.iter().skip(thread).step_by(len_threads) {
+                    let (index, pushed) = vec.push(*value);
+                    assert_eq!(pushed, value);
+                    assert_eq!(vec.get(index), Some(value));
+                }
+            });
+        }
+    });
+    assert_eq!(vec.len(), test_values.len());
+    let mut pushed = vec.iter().copied().collect::<Vec<_>>();
+    let mut expected = test_values.clone();
+    pushed.sort_unstable();
+    expected.sort_unstable();
+    assert_eq!(pushed, expected);
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(concurrent_bucket_vec_works_for);
+
+/// Whether `FailingAllocConfig` panics upon allocating its second bucket.
+#[cfg(feature = "std")]
+static FAIL_SECOND_BUCKET: core::sync::atomic::AtomicBool =
+    core::sync::atomic::AtomicBool::new(true);
+
+/// A configuration whose second bucket allocation panics while `FAIL_SECOND_BUCKET` is set.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+enum FailingAllocConfig {}
+
+#[cfg(feature = "std")]
+impl BucketVecConfig for FailingAllocConfig {
+    const STARTING_CAPACITY: usize = 2;
+    const GROWTH_RATE: f64 = 2.0;
+
+    fn on_bucket_allocated(bucket_index: usize, _capacity: usize) {
+        if bucket_index == 1 && FAIL_SECOND_BUCKET.load(core::sync::atomic::Ordering::SeqCst) {
+            panic!("failed to allocate bucket")
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn concurrent_bucket_vec_push_panicking_on_allocation_reserves_nothing() {
+    let vec = <ConcurrentBucketVec<i32, FailingAllocConfig>>::new();
+    vec.push(1);
+    vec.push(2);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        vec.push(3);
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec.len(), 2);
+    assert_eq!(vec.get(2), None);
+    FAIL_SECOND_BUCKET.store(false, core::sync::atomic::Ordering::SeqCst);
+    assert_eq!(vec.push(4), (2, &4));
+    vec.push(5);
+    assert_eq!(vec.len(), 4);
+    assert!(vec.iter().copied().eq([1, 2, 4, 5]));
+}
+
+#[cfg(feature = "std")]
+fn freeze_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let shared = vec.freeze();
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            let shared = shared.clone();
+            let test_values = &test_values;
+            scope.spawn(move || {
+                assert_eq!(shared.len(), test_values.len());
+                assert!(shared.iter().eq(test_values));
+            });
+        }
+    });
+    let other = shared.clone();
+    assert!(core::ptr::eq::<BucketVec<i32, C>>(&*shared, &*other));
+    let shared = match shared.try_unwrap() {
+        Ok(_) => panic!("unwrapped snapshot that is still shared"),
+        Err(shared) => shared,
+    };
+    drop(other);
+    match shared.try_unwrap() {
+        Ok(vec) => assert!(vec.iter().eq(&test_values)),
+        Err(_) => panic!("failed to unwrap the only snapshot"),
+    }
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(freeze_works_for);
+
+fn get_ptr_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let table = vec.ptr_table();
+    assert_eq!(table.len(), test_values.len());
+    for (index, ptr) in table.iter().enumerate() {
+        assert_eq!(vec.get_ptr(index), Some(*ptr));
+        assert_eq!(vec.get_ptr_mut(index), Some(*ptr));
+        assert!(core::ptr::eq(vec.get(index).unwrap(), ptr.as_ptr()));
+    }
+    assert_eq!(vec.get_ptr(test_values.len()), None);
+    assert_eq!(vec.get_ptr_mut(test_values.len()), None);
+    // Pushing more elements does not move the existing ones.
+    vec.extend(test_values.iter().copied());
+    assert!(table.iter().eq(vec.ptr_table()[..test_values.len()].iter()));
+}
+create_test_for_configs!(get_ptr_works_for);
+
+fn index_of_ref_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    for (index, element) in vec.iter().enumerate() {
+        assert_eq!(vec.index_of_ref(element), Some(index));
+    }
+    let outside = 0;
+    assert_eq!(vec.index_of_ref(&outside), None);
+    for value in &test_values {
+        assert_eq!(vec.index_of_ref(value), None);
+    }
+    let units = test_values.iter().map(|_| ()).collect::<BucketVec<(), C>>();
+    assert_eq!(
+        units.first().and_then(|unit| units.index_of_ref(unit)),
+        None
+    );
+}
+create_test_for_configs!(index_of_ref_works_for);
+
+#[cfg(feature = "bumpalo")]
+fn bump_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let bump = bumpalo::Bump::new();
+    let mut vec = <BumpBucketVec<i32, C>>::new_in(&bump);
+    let mut addresses = Vec::new();
+    for value in &test_values {
+        vec.push(*value);
+        addresses.push(vec.get(vec.len() - 1).unwrap() as *const i32 as usize);
+    }
+    assert_eq!(vec.len(), test_values.len());
+    assert!(vec.iter().eq(&test_values));
+    assert!(vec.iter().rev().eq(test_values.iter().rev()));
+    for (index, address) in addresses.iter().enumerate() {
+        assert_eq!(vec.get(index).unwrap() as *const i32 as usize, *address);
+        *vec.get_mut(index).unwrap() += 1;
+    }
+    assert_eq!(vec.get(test_values.len()), None);
+    assert!(vec.iter().eq(test_values
+        .iter()
+        .map(|value| value + 1)
+        .collect::<Vec<_>>()
+        .iter()));
+}
+#[cfg(feature = "bumpalo")]
+create_test_for_configs!(bump_bucket_vec_works_for);
+
+#[cfg(feature = "bumpalo")]
+fn bump_bucket_vec_zero_sized_works_for<C>(_test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let bump = bumpalo::Bump::new();
+    let mut vec = <BumpBucketVec<(), C>>::new_in(&bump);
+    for _ in 0..10 {
+        vec.push(());
+    }
+    assert_eq!(vec.len(), 10);
+    for index in 0..10 {
+        assert_eq!(vec.get(index), Some(&()));
+        assert_eq!(vec.get_mut(index), Some(&mut ()));
+    }
+    assert_eq!(vec.get(10), None);
+    assert_eq!(vec.iter().count(), 10);
+}
+#[cfg(feature = "bumpalo")]
+create_test_for_configs!(bump_bucket_vec_zero_sized_works_for);
+
+#[test]
+fn static_bucket_vec_works() {
+    let test_values = small_test_values();
+    let mut vec = <StaticBucketVec<i32, 8>>::new();
+    assert!(vec.is_empty());
+    assert_eq!(vec.capacity(), 8);
+    let mut addresses = Vec::new();
+    for value in test_values.iter().take(8) {
+        assert_eq!(vec.push(*value), Ok(()));
+        addresses.push(vec.get(vec.len() - 1).unwrap() as *const i32 as usize);
+        assert_eq!(vec.iter().len(), vec.len());
+    }
+    assert!(vec.is_full());
+    assert_eq!(vec.push(42), Err(42));
+    assert!(vec.iter().eq(test_values.iter().take(8)));
+    assert!(vec.iter().rev().eq(test_values.iter().take(8).rev()));
+    assert_eq!(vec.iter().nth(5), test_values.get(5));
+    assert_eq!(vec.iter().nth_back(5), test_values.get(2));
+    for (index, address) in addresses.iter().enumerate() {
+        assert_eq!(vec.get(index).unwrap() as *const i32 as usize, *address);
+    }
+    for value in vec.iter_mut() {
+        *value += 1;
+    }
+    *vec.get_mut(0).unwrap() -= 1;
+    assert_eq!(vec.get(0), test_values.first());
+    assert_eq!(vec.get(1), Some(&(test_values[1] + 1)));
+    assert_eq!(vec.get(8), None);
+    assert_eq!(format!("{:?}", <StaticBucketVec<i32, 4>>::new()), "[]");
+}
+
+#[test]
+fn static_bucket_vec_works_for_non_default_elements() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct NoDefault(i32);
+
+    let mut vec = <StaticBucketVec<NoDefault, 6>>::default();
+    for value in 0..5 {
+        assert_eq!(vec.push(NoDefault(value)), Ok(()));
+    }
+    assert_eq!(vec.get(4), Some(&NoDefault(4)));
+    assert_eq!(vec.get(5), None);
+    for value in vec.iter_mut().rev().step_by(2) {
+        value.0 += 10;
+    }
+    assert!(vec
+        .iter()
+        .map(|value| value.0)
+        .eq([10, 1, 12, 3, 14].iter().copied()));
+    assert_eq!(vec.push(NoDefault(5)), Ok(()));
+    assert_eq!(vec.push(NoDefault(6)), Err(NoDefault(6)));
+    assert_eq!(vec.clone().iter().len(), 6);
+}
+
+#[test]
+fn static_bucket_vec_pop_and_clear_work() {
+    const EMPTY: StaticBucketVec<DropCounted, 4> = StaticBucketVec::new();
+    let drops = Rc::new(core::cell::Cell::new(0));
+    let mut vec = EMPTY;
+    for value in 0..4 {
+        let element = DropCounted {
+            value,
+            drops: drops.clone(),
+        };
+        assert!(vec.push(element).is_ok());
+    }
+    let first = vec.get(0).unwrap() as *const DropCounted;
+    assert_eq!(vec.pop().map(|element| element.value), Some(3));
+    assert_eq!(drops.get(), 1);
+    assert_eq!(vec.len(), 3);
+    assert_eq!(vec.get(3).map(|element| element.value), None);
+    assert_eq!(vec.get(0).unwrap() as *const DropCounted, first);
+    vec.clear();
+    assert!(vec.is_empty());
+    assert_eq!(drops.get(), 4);
+    assert!(vec.pop().is_none());
+    assert_eq!(vec.capacity(), 4);
+    let element = DropCounted {
+        value: 5,
+        drops: drops.clone(),
+    };
+    assert!(vec.push(element).is_ok());
+    assert_eq!(vec.get(0).unwrap() as *const DropCounted, first);
+}
+
+fn bucket_entry_indices_are_exact_for<C>()
+where
+    C: BucketVecConfig,
+{
+    let mut bucket_index = 0;
+    let mut entry_index = 0;
+    for index in 0..200_000 {
+        if entry_index == config::bucket_capacity::<C>(bucket_index) {
+            bucket_index += 1;
+            entry_index = 0;
+        }
+        assert_eq!(
+            config::bucket_entry_indices::<C>(index),
+            (bucket_index, entry_index)
+        );
+        entry_index += 1;
+    }
+}
+
+#[test]
+fn bucket_entry_indices_are_exact() {
+    bucket_entry_indices_are_exact_for::<DefaultConfig>();
+    bucket_entry_indices_are_exact_for::<QuadraticConfig>();
+    bucket_entry_indices_are_exact_for::<CubicConfig>();
+    bucket_entry_indices_are_exact_for::<EqualSizeConfig>();
+    bucket_entry_indices_are_exact_for::<WastefulConfig>();
+    bucket_entry_indices_are_exact_for::<PowerOfTwoConfig>();
+    bucket_entry_indices_are_exact_for::<ConstConfig<4, 2, 1>>();
+    bucket_entry_indices_are_exact_for::<ConstConfig<5, 6, 2>>();
+    bucket_entry_indices_are_exact_for::<ConstConfig<3, 11, 10>>();
+    bucket_entry_indices_are_exact_for::<PlateauConfig>();
+    bucket_entry_indices_are_exact_for::<CappedConfig>();
+    bucket_entry_indices_are_exact_for::<CappedFractionalConfig>();
+    bucket_entry_indices_are_exact_for::<CappedBelowStartConfig>();
+    bucket_entry_indices_are_exact_for::<config::EqualSizeConfig<16>>();
+    bucket_entry_indices_are_exact_for::<FibonacciConfig>();
+    bucket_entry_indices_are_exact_for::<CappedDoublingConfig<100>>();
+    bucket_entry_indices_are_exact_for::<TinyStartConfig>();
+}
+
+/// A configuration with a power of two starting capacity that doubles.
+#[derive(Debug)]
+enum PowerOfTwoConfig {}
+
+impl BucketVecConfig for PowerOfTwoConfig {
+    const STARTING_CAPACITY: usize = 16;
+    const GROWTH_RATE: f64 = 2.0;
+}
+
+fn access_positions_match_iteration_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    vec.extend(test_values.iter().copied());
+    for (position, value) in vec.iter_indexed() {
+        let access = vec.access(position.index()).unwrap();
+        assert_eq!(access.position(), position);
+        assert_eq!(*access, *value);
+    }
+}
+create_test_for_configs!(access_positions_match_iteration_for);
+
+fn push_after_bulk_operations_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    let mut expected = Vec::new();
+    for chunk in test_values.chunks(7) {
+        vec.extend_from_slice(chunk);
+        expected.extend_from_slice(chunk);
+        vec.push(chunk[0]);
+        expected.push(chunk[0]);
+        vec.extend(chunk.iter().copied());
+        expected.extend(chunk.iter().copied());
+        vec.push(chunk[0]);
+        expected.push(chunk[0]);
+        vec.pop();
+        expected.pop();
+        vec.push(chunk[0]);
+        expected.push(chunk[0]);
+    }
+    assert!(vec.iter().eq(&expected));
+    for (index, value) in expected.iter().enumerate() {
+        assert_eq!(vec.get(index), Some(value));
+    }
+}
+create_test_for_configs!(push_after_bulk_operations_works_for);
+
+/// A configuration whose bucket capacities overflow after a few buckets.
+#[derive(Debug)]
+enum HugeGrowthConfig {}
+
+impl BucketVecConfig for HugeGrowthConfig {
+    const STARTING_CAPACITY: usize = 3;
+    const GROWTH_RATE: f64 = 1000.0;
+}
+
+/// A configuration with a huge fractional growth rate.
+#[derive(Debug)]
+enum HugeFractionalGrowthConfig {}
+
+impl BucketVecConfig for HugeFractionalGrowthConfig {
+    const STARTING_CAPACITY: usize = 3;
+    const GROWTH_RATE: f64 = 1000.5;
+}
+
+fn capacity_math_near_overflow_works_for<C>()
+where
+    C: BucketVecConfig,
+{
+    // Find the first bucket whose total capacity overflows.
+    let overflow = (0..)
+        .find(|&index| config::checked_total_capacity::<C>(index).is_none())
+        .unwrap();
+    assert!(overflow > 0);
+    let last_total = config::total_capacity::<C>(overflow - 1);
+    assert!(config::checked_total_capacity::<C>(overflow + 1).is_none());
+    assert!(config::checked_total_capacity::<C>(usize::MAX).is_none());
+    // Index math stays well-defined up to the largest possible index.
+    for &index in &[last_total - 1, last_total, usize::MAX - 1, usize::MAX] {
+        let (x, y) = config::bucket_entry_indices::<C>(index);
+        assert_eq!(config::total_capacity::<C>(x) + y, index);
+        match config::checked_total_capacity::<C>(x + 1) {
+            Some(next_total) => assert!(index < next_total),
+            None => assert_eq!(x + 1, overflow),
+        }
+    }
+}
+
+#[test]
+fn capacity_math_near_overflow_works() {
+    capacity_math_near_overflow_works_for::<DefaultConfig>();
+    capacity_math_near_overflow_works_for::<QuadraticConfig>();
+    capacity_math_near_overflow_works_for::<CubicConfig>();
+    capacity_math_near_overflow_works_for::<HugeGrowthConfig>();
+    capacity_math_near_overflow_works_for::<FibonacciConfig>();
+    capacity_math_near_overflow_works_for::<ConstConfig<3, 11, 10>>();
+}
+
+/// Asserts that the index math of the configuration agrees with the buckets
+/// that have actually been allocated for millions of elements.
+fn index_math_agrees_with_allocations_for<C>()
+where
+    C: BucketVecConfig,
+{
+    const LEN: u32 = 2_000_000;
+    let vec = (0..LEN).collect::<BucketVec<u32, C>>();
+    for (position, value) in vec.iter_indexed() {
+        let index = position.index();
+        assert_eq!(index, *value as usize);
+        assert_eq!(
+            config::bucket_entry_indices::<C>(index),
+            (position.bucket_index(), position.entry_index())
+        );
+    }
+    let frozen = <FrozenBucketVec<u32, C>>::new();
+    for value in 0..LEN {
+        frozen.push(value);
+    }
+    for value in (0..LEN).step_by(7) {
+        assert_eq!(frozen.get(value as usize), Some(&value));
+        assert_eq!(vec.get(value as usize), Some(&value));
+    }
+}
+
+#[test]
+fn index_math_agrees_with_allocations_at_millions() {
+    index_math_agrees_with_allocations_for::<CrazyPiConfig>();
+    index_math_agrees_with_allocations_for::<C3G1x5Config>();
+    index_math_agrees_with_allocations_for::<FibonacciConfig>();
+    index_math_agrees_with_allocations_for::<CappedFractionalConfig>();
+    index_math_agrees_with_allocations_for::<ConstConfig<3, 11, 10>>();
+    index_math_agrees_with_allocations_for::<ConstConfig<2, 101, 100>>();
+}
+
+#[test]
+fn tabulated_capacities_match_formula() {
+    fn assert_matches_formula<C>()
+    where
+        C: BucketVecConfig,
+    {
+        for index in 0..1000 {
+            assert_eq!(
+                config::checked_total_capacity::<C>(index),
+                config::checked_total_capacity_of(C::STARTING_CAPACITY, C::GROWTH_RATE, index),
+            );
+        }
+    }
+    // Tabulated at compile time.
+    assert_matches_formula::<FibonacciConfig>();
+    assert_matches_formula::<ConstConfig<1, 3, 2>>();
+    // Too slow growth to be tabulated.
+    assert_matches_formula::<ConstConfig<3, 11, 10>>();
+}
+
+#[test]
+fn fractional_capacity_math_near_overflow_works() {
+    let overflow = (0..)
+        .find(|&index| {
+            config::checked_total_capacity::<HugeFractionalGrowthConfig>(index).is_none()
+        })
+        .unwrap();
+    assert!(overflow > 0);
+    assert!(config::checked_total_capacity::<HugeFractionalGrowthConfig>(usize::MAX).is_none());
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn bucket_capacity_panics_on_overflow() {
+    config::bucket_capacity::<HugeGrowthConfig>(7);
+}
+
+#[test]
+#[should_panic(expected = "`bucket_capacity` is overridden but `GEOMETRIC` is not set to `false`")]
+fn custom_schedule_without_opting_out_of_geometric_panics() {
+    /// Overrides the schedule but forgets to set `GEOMETRIC` to `false`.
+    enum ForgetfulConfig {}
+    impl BucketVecConfig for ForgetfulConfig {
+        const STARTING_CAPACITY: usize = 1;
+        const GROWTH_RATE: f64 = 2.0;
+
+        fn bucket_capacity(bucket_index: usize) -> usize {
+            1 << core::cmp::min(bucket_index, 3)
+        }
+    }
+    let mut vec = BucketVec::<i32, ForgetfulConfig>::new();
+    vec.extend(0..100);
+}
+
+#[test]
+fn const_config_matches_equivalent_configs() {
+    fn assert_same_layout<C1, C2>()
+    where
+        C1: BucketVecConfig,
+        C2: BucketVecConfig,
+    {
+        for index in 0..20 {
+            assert_eq!(
+                config::bucket_capacity::<C1>(index),
+                config::bucket_capacity::<C2>(index)
+            );
+        }
+        for index in 0..10_000 {
+            assert_eq!(
+                config::bucket_entry_indices::<C1>(index),
+                config::bucket_entry_indices::<C2>(index)
+            );
+        }
+    }
+    assert_same_layout::<ConstConfig<4, 2, 1>, DefaultConfig>();
+    assert_same_layout::<ConstConfig<1, 3, 1>, CubicConfig>();
+    assert_same_layout::<ConstConfig<4, 1, 1>, EqualSizeConfig>();
+    assert_same_layout::<ConstConfig<3, 3, 2>, C3G1x5Config>();
+    let mut vec = BucketVec::<i32, ConstConfig<3, 3, 2>>::new();
+    vec.extend(0..1000);
+    assert!(vec.iter().copied().eq(0..1000));
+}
+
+fn dyn_bucket_vec_matches_static_config_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    if !<C as BucketVecConfig>::GEOMETRIC || <C as BucketVecConfig>::MAX_BUCKET_CAPACITY.is_some() {
+        return;
+    }
+    let mut vec = DynBucketVec::from_config::<C>();
+    let mut expected = BucketVec::<i32, C>::new();
+    for &value in &test_values {
+        assert_eq!(*vec.push_get(value), value);
+        expected.push(value);
+    }
+    assert_eq!(vec.len(), expected.len());
+    assert_eq!(vec.capacity(), expected.capacity());
+    assert!(vec.iter().eq(expected.iter()));
+    for index in 0..=test_values.len() {
+        assert_eq!(vec.get(index), expected.get(index));
+    }
+    assert_eq!(vec.last(), test_values.last());
+    for value in &mut vec {
+        *value += 1;
+    }
+    assert!(vec
+        .into_iter()
+        .eq(test_values.iter().map(|value| value + 1)));
+}
+create_test_for_configs!(dyn_bucket_vec_matches_static_config_for);
+
+fn dyn_bucket_vec_mutations_work_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    if !<C as BucketVecConfig>::GEOMETRIC || <C as BucketVecConfig>::MAX_BUCKET_CAPACITY.is_some() {
+        return;
+    }
+    let len = test_values.len();
+    let mut vec = DynBucketVec::from_config::<C>();
+    vec.reserve(len);
+    let capacity = vec.capacity();
+    assert!(capacity >= len);
+    vec.extend(test_values.iter().copied());
+    assert_eq!(vec.capacity(), capacity);
+    let clone = vec.clone();
+    assert_eq!(clone, vec);
+    assert_eq!(clone.capacity(), vec.capacity());
+    for (index, value) in test_values.iter().enumerate() {
+        assert_eq!(vec[index], *value);
+        vec[index] += 1;
+    }
+    assert_ne!(clone, vec);
+    let addresses = vec
+        .iter()
+        .map(|value| value as *const i32)
+        .collect::<Vec<_>>();
+    assert_eq!(vec.pop(), test_values.last().map(|value| value + 1));
+    vec.truncate(len / 2);
+    assert_eq!(vec.len(), len / 2);
+    assert_eq!(vec.capacity(), capacity);
+    assert!(vec
+        .iter()
+        .map(|value| value as *const i32)
+        .eq(addresses[..len / 2].iter().copied()));
+    vec.extend(test_values[len / 2..].iter().map(|value| value + 1));
+    assert_eq!(vec.capacity(), capacity);
+    assert!(vec.iter().eq(test_values
+        .iter()
+        .map(|value| value + 1)
+        .collect::<Vec<_>>()
+        .iter()));
+    vec.clear();
+    assert!(vec.is_empty());
+    assert_eq!(vec.pop(), None);
+    assert_eq!(vec.capacity(), capacity);
+    assert_eq!(vec.get(0), None);
+}
+create_test_for_configs!(dyn_bucket_vec_mutations_work_for);
+
+fn dyn_bucket_vec_matches_bucket_vec_api_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    if !<C as BucketVecConfig>::GEOMETRIC || <C as BucketVecConfig>::MAX_BUCKET_CAPACITY.is_some() {
+        return;
+    }
+    let expected = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let mut vec = DynBucketVec::from_config::<C>();
+    vec.extend_from_slice(&test_values);
+    assert_eq!(vec.capacity(), expected.capacity());
+    assert!(vec.iter().eq(expected.iter()));
+    let collected = test_values.iter().copied().collect::<DynBucketVec<i32>>();
+    assert_eq!(collected, vec);
+    assert_eq!(format!("{:?}", collected), format!("{:?}", test_values));
+    let mut longer = vec.clone();
+    longer.extend(&[i32::MIN]);
+    assert!(vec < longer);
+    assert_eq!(vec.cmp(&longer), core::cmp::Ordering::Less);
+    assert_eq!(
+        vec.partial_cmp(&vec.clone()),
+        Some(core::cmp::Ordering::Equal)
+    );
+    #[cfg(feature = "std")]
+    {
+        fn hash_of<T: core::hash::Hash + ?Sized>(value: &T) -> u64 {
+            use core::hash::Hasher as _;
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+        assert_eq!(hash_of(&vec), hash_of(&expected));
+    }
+}
+create_test_for_configs!(dyn_bucket_vec_matches_bucket_vec_api_for);
+
+#[test]
+fn dyn_bucket_vec_extend_reserves_from_size_hint() {
+    let mut vec = DynBucketVec::new(2, 2.0);
+    vec.extend(0..100);
+    // The buckets for all 100 elements are allocated up front: 2 + 4 + .. + 64.
+    assert_eq!(vec.capacity(), 126);
+    assert!(vec.iter().copied().eq(0..100));
+    let mut zero_sized = DynBucketVec::new(2, 2.0);
+    zero_sized.extend((0..10).map(|_| ()));
+    assert_eq!(zero_sized.len(), 10);
+    assert_eq!(zero_sized.capacity(), usize::MAX);
+}
+
+#[test]
+#[should_panic(expected = "index 1 is out of bounds for bucket vector length 1")]
+fn dyn_bucket_vec_index_panics_out_of_bounds() {
+    let mut vec = DynBucketVec::new(4, 2.0);
+    vec.push(1);
+    let _ = vec[1];
+}
+
+#[test]
+#[should_panic(expected = "growth rate must be finite and at least 1")]
+fn dyn_bucket_vec_rejects_shrinking_growth_rate() {
+    let _ = DynBucketVec::<i32>::new(4, 0.5);
+}
+
+#[test]
+#[should_panic(expected = "growth rate must be finite and at least 1")]
+fn dyn_bucket_vec_rejects_infinite_growth_rate() {
+    let _ = DynBucketVec::<i32>::new(4, f64::INFINITY);
+}
+
+#[test]
+#[should_panic(expected = "growth rate must be finite and at least 1")]
+fn dyn_bucket_vec_rejects_nan_growth_rate() {
+    let _ = DynBucketVec::<i32>::new(4, f64::NAN);
+}
+
+/// A fractional configuration whose bucket capacities stop growing at 20.
+#[derive(Debug)]
+enum CappedFractionalConfig {}
+
+impl BucketVecConfig for CappedFractionalConfig {
+    const STARTING_CAPACITY: usize = 3;
+    const GROWTH_RATE: f64 = 1.5;
+    const MAX_BUCKET_CAPACITY: Option<usize> = Some(20);
+}
+
+/// A configuration whose cap is smaller than its starting capacity.
+#[derive(Debug)]
+enum CappedBelowStartConfig {}
+
+impl BucketVecConfig for CappedBelowStartConfig {
+    const STARTING_CAPACITY: usize = 8;
+    const GROWTH_RATE: f64 = 2.0;
+    const MAX_BUCKET_CAPACITY: Option<usize> = Some(5);
+}
+
+#[test]
+fn max_bucket_capacity_caps_growth() {
+    let capacities = (0..8)
+        .map(config::bucket_capacity::<CappedConfig>)
+        .collect::<Vec<_>>();
+    assert_eq!(capacities, vec![4, 8, 16, 32, 64, 64, 64, 64]);
+    assert_eq!(config::max_bucket_capacity::<CappedConfig>(), Some(64));
+    assert_eq!(
+        config::total_capacity::<CappedConfig>(6),
+        4 + 8 + 16 + 32 + 64 + 64
+    );
+    assert_eq!(config::bucket_capacity::<CappedBelowStartConfig>(0), 5);
+    assert_eq!(config::bucket_capacity::<CappedFractionalConfig>(100), 20);
+    // Capped bucket capacities never overflow no matter the bucket index.
+    assert_eq!(config::bucket_capacity::<CappedConfig>(usize::MAX / 2), 64);
+    let mut vec = BucketVec::<i32, CappedConfig>::new();
+    vec.extend(0..1000);
+    assert!(vec.capacity() - vec.len() < 64);
+}
+
+fn builtin_config_works_for<C>()
+where
+    C: BucketVecConfig,
+{
+    let mut vec = BucketVec::<usize, C>::new();
+    vec.extend(0..10_000);
+    for index in 0..10_000 {
+        assert_eq!(vec.get(index), Some(&index));
+    }
+    assert_eq!(vec.get(10_000), None);
+}
+
+#[test]
+fn builtin_configs_work() {
+    builtin_config_works_for::<config::EqualSizeConfig<16>>();
+    builtin_config_works_for::<FibonacciConfig>();
+    builtin_config_works_for::<CappedDoublingConfig<100>>();
+    builtin_config_works_for::<TinyStartConfig>();
+    assert_eq!(
+        config::max_bucket_capacity::<config::EqualSizeConfig<16>>(),
+        Some(16)
+    );
+    assert_eq!(
+        config::max_bucket_capacity::<CappedDoublingConfig<100>>(),
+        Some(100)
+    );
+    let fibonacci = (0..6)
+        .map(config::bucket_capacity::<FibonacciConfig>)
+        .collect::<Vec<_>>();
+    assert_eq!(fibonacci, vec![4, 6, 10, 17, 28, 44]);
+}
+
+fn capacity_planning_matches_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    assert_eq!(C::buckets_needed(0), 0);
+    assert_eq!(C::wasted_for(0), 0);
+    let mut vec = BucketVec::<i32, C>::new();
+    for (n, value) in test_values.into_iter().enumerate() {
+        vec.push(value);
+        if n % 97 == 0 {
+            let len = n + 1;
+            let len_buckets = C::buckets_needed(len);
+            assert_eq!(vec.capacity(), C::total_capacity_for(len_buckets));
+            assert_eq!(vec.capacity() - len, C::wasted_for(len));
+        }
+    }
+}
+create_test_for_configs!(capacity_planning_matches_for);
+
+#[test]
+fn capacity_planning_works() {
+    assert_eq!(DefaultConfig::buckets_needed(4), 1);
+    assert_eq!(DefaultConfig::buckets_needed(5), 2);
+    assert_eq!(DefaultConfig::total_capacity_for(3), 4 + 8 + 16);
+    assert_eq!(DefaultConfig::wasted_for(13), 4 + 8 + 16 - 13);
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// The bucket allocations observed by `ObservedConfig` on this thread.
+    static OBSERVED_ALLOCATIONS: core::cell::RefCell<Vec<(usize, usize)>> = Default::default();
+}
+
+/// A configuration that records all bucket allocations.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+enum ObservedConfig {}
+
+#[cfg(feature = "std")]
+impl BucketVecConfig for ObservedConfig {
+    const STARTING_CAPACITY: usize = 2;
+    const GROWTH_RATE: f64 = 2.0;
+
+    fn on_bucket_allocated(bucket_index: usize, capacity: usize) {
+        OBSERVED_ALLOCATIONS
+            .with(|allocations| allocations.borrow_mut().push((bucket_index, capacity)));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn on_bucket_allocated_observes_allocations() {
+    let observed = || OBSERVED_ALLOCATIONS.with(|allocations| allocations.borrow().clone());
+    let mut vec = BucketVec::<i32, ObservedConfig>::new();
+    assert_eq!(observed(), vec![]);
+    vec.extend(0..7);
+    assert_eq!(observed(), vec![(0, 2), (1, 4), (2, 8)]);
+    vec.reserve(10);
+    assert_eq!(observed(), vec![(0, 2), (1, 4), (2, 8), (3, 16)]);
+    vec.extend(7..30);
+    assert_eq!(observed().len(), 4);
+    vec.push(30);
+    assert_eq!(observed().last(), Some(&(4, 32)));
+}
+
+fn memory_stats_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    vec.reserve(10);
+    let stats = vec.memory_stats();
+    assert_eq!(stats.len(), vec.len());
+    assert_eq!(stats.capacity(), vec.capacity());
+    assert_eq!(stats.wasted(), vec.capacity() - vec.len());
+    assert_eq!(
+        stats.buckets().iter().map(BucketStats::len).sum::<usize>(),
+        vec.len()
+    );
+    assert!(stats.len_buckets() >= 1);
+    assert!(stats.heap_bytes() >= stats.capacity() * core::mem::size_of::<i32>());
+}
+create_test_for_configs!(memory_stats_works_for);
+
+#[test]
+fn memory_stats_of_empty_vec_works() {
+    let stats = BucketVec::<i32>::new().memory_stats();
+    assert!(stats.is_empty());
+    assert_eq!(stats.len_buckets(), 0);
+    assert_eq!(stats.heap_bytes(), 0);
+}
+
+#[test]
+fn debug_formats_elements_and_buckets() {
+    let mut vec = BucketVec::<i32>::new();
+    assert_eq!(format!("{:?}", vec), "[]");
+    vec.extend(0..5);
+    assert_eq!(format!("{:?}", vec), "[0, 1, 2, 3, 4]");
+    assert_eq!(
+        format!("{:#?}", vec),
+        "[\n    [0, 1, 2, 3],\n    [4, _, _, _, _, _, _, _],\n]"
+    );
+    vec.extend(5..13);
+    assert_eq!(
+        format!("{:#?}", vec),
+        "[\n    [0, 1, 2, 3],\n    [4, 5, 6, 7, 8, 9, 10, 11],\n    [12, 15 x _],\n]"
+    );
+}
+
+fn prealloc_next_bucket_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = BucketVec::<i32, C>::new();
+    assert_eq!(vec.spare_capacity(), 0);
+    for value in test_values {
+        if vec.spare_capacity() == 0 {
+            vec.prealloc_next_bucket();
+            let capacity = vec.capacity();
+            vec.prealloc_next_bucket();
+            assert_eq!(vec.capacity(), capacity);
+            assert!(vec.spare_capacity() >= 1);
+        }
+        assert!(!vec.next_push_allocates());
+        let capacity = vec.capacity();
+        vec.push(value);
+        // Pushing never allocated another bucket.
+        assert_eq!(vec.capacity(), capacity);
+    }
+}
+create_test_for_configs!(prealloc_next_bucket_works_for);
+
+fn next_push_allocates_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = BucketVec::<i32, C>::new();
+    for value in test_values {
+        let allocates = vec.next_push_allocates();
+        assert_eq!(allocates, vec.spare_capacity() == 0);
+        let len_buckets = vec.memory_stats().len_buckets();
+        vec.push(value);
+        assert_eq!(
+            vec.memory_stats().len_buckets(),
+            len_buckets + allocates as usize
+        );
+    }
+}
+create_test_for_configs!(next_push_allocates_works_for);
+
+fn zero_sized_elements_work_for<C>(_test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = BucketVec::<(), C>::new();
+    for _ in 0..10_000 {
+        vec.push(());
+    }
+    assert_eq!(vec.len(), 10_000);
+    // All zero-sized elements share a single bucket.
+    assert_eq!(vec.memory_stats().len_buckets(), 1);
+    for index in 0..10_000 {
+        assert_eq!(vec.get(index), Some(&()));
+        assert_eq!(vec.access(index).unwrap().position().bucket_index(), 0);
+    }
+    assert_eq!(vec.get(10_000), None);
+    assert_eq!(vec.iter().count(), 10_000);
+    vec.truncate(10);
+    assert_eq!(vec.pop(), Some(()));
+    vec.extend(core::iter::repeat_n((), 100));
+    assert_eq!(vec.len(), 109);
+    assert_eq!(vec.get(108), Some(&()));
+    assert!(!vec.next_push_allocates());
+}
+create_test_for_configs!(zero_sized_elements_work_for);
+
+fn zero_sized_capacity_saturates_for<C>(_test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = BucketVec::<(), C>::new();
+    assert_eq!(vec.capacity(), usize::MAX);
+    vec.extend(core::iter::repeat_n((), 100));
+    assert_eq!(vec.capacity(), usize::MAX);
+    assert_eq!(vec.spare_capacity(), usize::MAX - 100);
+    // Zero-sized elements never queue reserved buckets.
+    vec.reserve(1000);
+    vec.prealloc_next_bucket();
+    assert_eq!(vec.spare_capacity(), usize::MAX - 100);
+    let stats = vec.memory_stats();
+    assert_eq!(stats.len_buckets(), 1);
+    assert_eq!(stats.capacity(), usize::MAX);
+    assert_eq!(stats.wasted(), usize::MAX - 100);
+    vec.extend(core::iter::repeat_n((), 100));
+    assert_eq!(vec.len(), 200);
+    assert_eq!(vec.memory_stats().len_buckets(), 1);
+}
+create_test_for_configs!(zero_sized_capacity_saturates_for);
+
+fn eq_respects_length_and_layout_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let mut prefix = vec.clone();
+    prefix.pop();
+    assert_ne!(vec, prefix);
+    assert_ne!(prefix, vec);
+    // Cloned bucket vectors may be split into buckets differently.
+    let mut clone = prefix.clone();
+    clone.push(*test_values.last().unwrap());
+    assert_eq!(vec, clone);
+    assert_eq!(clone, vec);
+    let mut other = vec.clone();
+    *other.last_mut().unwrap() += 1;
+    assert_ne!(vec, other);
+    assert_eq!(BucketVec::<i32, C>::new(), BucketVec::<i32, C>::new());
+}
+create_test_for_configs!(eq_respects_length_and_layout_for);
+
+fn eq_against_slices_and_configs_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    assert_eq!(vec, test_values);
+    assert_eq!(vec, test_values[..]);
+    assert_eq!(vec, &test_values[..]);
+    assert_ne!(vec, test_values[1..]);
+    assert_ne!(vec, test_values[..test_values.len() - 1]);
+    let other = test_values.iter().copied().collect::<BucketVec<i32>>();
+    assert_eq!(vec, other);
+    assert_eq!(other, vec);
+    let mut shorter = other;
+    shorter.pop();
+    assert_ne!(vec, shorter);
+}
+create_test_for_configs!(eq_against_slices_and_configs_works_for);
+
+#[test]
+fn eq_against_arrays_works() {
+    let vec = (1..=3).collect::<BucketVec<i32, WastefulConfig>>();
+    assert_eq!(vec, [1, 2, 3]);
+    assert_ne!(vec, [1, 2]);
+    assert_ne!(vec, [1, 2, 4]);
+    assert_eq!(BucketVec::<i32>::new(), [0; 0]);
+}
+
+fn ord_matches_slices_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let len = test_values.len();
+    let mut modified = test_values.clone();
+    modified[len / 2] += 1;
+    let slices = [
+        &test_values[..],
+        &test_values[..len - 1],
+        &test_values[1..],
+        &modified[..],
+        &[],
+    ];
+    for lhs in &slices {
+        // Cloning and pushing splits the elements into buckets differently.
+        let mut lhs_vec = lhs.iter().copied().collect::<BucketVec<i32, C>>();
+        if let Some(last) = lhs_vec.pop() {
+            lhs_vec = lhs_vec.clone();
+            lhs_vec.push(last);
+        }
+        for rhs in &slices {
+            let rhs_vec = rhs.iter().copied().collect::<BucketVec<i32, C>>();
+            assert_eq!(lhs_vec.cmp(&rhs_vec), lhs.cmp(rhs));
+            assert_eq!(lhs_vec.partial_cmp(&rhs_vec), lhs.partial_cmp(rhs));
+        }
+    }
+}
+create_test_for_configs!(ord_matches_slices_for);
+
+#[test]
+fn partial_ord_with_nan_works() {
+    let lhs = vec![1.0, f64::NAN, 3.0]
+        .into_iter()
+        .collect::<BucketVec<f64>>();
+    let rhs = vec![1.0, 2.0].into_iter().collect::<BucketVec<f64>>();
+    assert_eq!(lhs.partial_cmp(&rhs), None);
+    let rhs = vec![0.0, 2.0].into_iter().collect::<BucketVec<f64>>();
+    assert_eq!(lhs.partial_cmp(&rhs), Some(core::cmp::Ordering::Greater));
+}
+
+#[cfg(feature = "std")]
+fn hash_matches_slices_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    fn hash_of<T: core::hash::Hash + ?Sized>(value: &T) -> u64 {
+        use core::hash::Hasher as _;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    assert_eq!(hash_of(&vec), hash_of(&test_values[..]));
+    assert_eq!(hash_of(&vec), hash_of(&test_values));
+    let strings = test_values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let vec = strings.iter().cloned().collect::<BucketVec<String, C>>();
+    assert_eq!(hash_of(&vec), hash_of(&strings[..]));
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(hash_matches_slices_for);
+
+fn default_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::default();
+    assert!(vec.is_empty());
+    vec.extend(test_values.iter().copied());
+    assert_eq!(vec, test_values);
+    assert!(<SlotBucketVec<i32, C>>::default().is_empty());
+    assert!(<BucketVecDeque<i32, C>>::default().is_empty());
+    assert!(<GenBucketVec<i32, C>>::default().is_empty());
+    assert!(<FrozenBucketVec<i32, C>>::default().is_empty());
+    #[cfg(feature = "std")]
+    assert!(<ConcurrentBucketVec<i32, C>>::default().is_empty());
+    assert!(<BucketString<C>>::default().is_empty());
+}
+create_test_for_configs!(default_works_for);
+
+#[test]
+fn default_can_be_derived_for_custom_configs() {
+    #[derive(Default)]
+    struct Holder {
+        vec: BucketVec<i32, CubicConfig>,
+    }
+    assert!(Holder::default().vec.is_empty());
+}
+
+fn clone_preserves_layout_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let mut clone = vec.clone();
+    assert_eq!(clone.capacity(), vec.capacity());
+    clone.extend(test_values.iter().copied());
+    for (index, value) in test_values.iter().chain(&test_values).enumerate() {
+        assert_eq!(clone.get(index), Some(value));
+    }
+}
+create_test_for_configs!(clone_preserves_layout_for);
+
+fn clone_from_reuses_buckets_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let len = test_values.len();
+    let source = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    // Shrinking clone keeps the elements in place.
+    let mut target = (0..len as i32 * 2).collect::<BucketVec<i32, C>>();
+    let addresses = target
+        .iter()
+        .map(|value| value as *const i32)
+        .collect::<Vec<_>>();
+    let capacity = target.capacity();
+    target.clone_from(&source);
+    assert_eq!(target, source);
+    assert_eq!(target.capacity(), capacity);
+    assert!(target
+        .iter()
+        .map(|value| value as *const i32)
+        .eq(addresses[..len].iter().copied()));
+    // Growing clone keeps the elements in place and stays usable.
+    let mut target = (0..len as i32 / 2).collect::<BucketVec<i32, C>>();
+    let addresses = target
+        .iter()
+        .map(|value| value as *const i32)
+        .collect::<Vec<_>>();
+    target.clone_from(&source);
+    assert_eq!(target, source);
+    assert!(target
+        .iter()
+        .map(|value| value as *const i32)
+        .take(addresses.len())
+        .eq(addresses.iter().copied()));
+    target.push(42);
+    for (index, value) in test_values.iter().chain(&[42]).enumerate() {
+        assert_eq!(target.get(index), Some(value));
+    }
+    // Cloning into a bucket vector with reserved buckets reuses them.
+    let mut target = BucketVec::<i32, C>::new();
+    target.reserve(len);
+    let capacity = target.capacity();
+    target.clone_from(&source);
+    assert_eq!(target, source);
+    assert_eq!(target.capacity(), capacity);
+    // Reserved buckets not required by `source` are kept.
+    let mut target = BucketVec::<i32, C>::new();
+    target.reserve(len * 4);
+    let capacity = target.capacity();
+    target.clone_from(&source);
+    assert_eq!(target, source);
+    assert_eq!(target.capacity(), capacity);
+    target.extend(0..len as i32);
+    assert_eq!(target.capacity(), capacity);
+    assert!(target.iter().skip(len).copied().eq(0..len as i32));
+}
+create_test_for_configs!(clone_from_reuses_buckets_for);
+
+fn extend_by_reference_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = BucketVec::<i32, C>::new();
+    vec.extend(test_values.iter());
+    vec.extend(&test_values);
+    assert_eq!(vec.len(), 2 * test_values.len());
+    assert!(vec.iter().eq(test_values.iter().chain(&test_values)));
+}
+create_test_for_configs!(extend_by_reference_works_for);
+
+fn extend_preallocates_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let len = test_values.len();
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    assert_eq!(vec, test_values);
+    assert_eq!(
+        vec.capacity(),
+        C::total_capacity_for(C::buckets_needed(len))
+    );
+    // Iterators without a useful lower bound still work.
+    let mut vec = test_values
+        .iter()
+        .copied()
+        .filter(|_| true)
+        .collect::<BucketVec<i32, C>>();
+    assert_eq!(vec, test_values);
+    vec.extend(test_values.iter().copied());
+    assert_eq!(vec.len(), 2 * len);
+    assert_eq!(
+        vec.capacity(),
+        C::total_capacity_for(C::buckets_needed(2 * len))
+    );
+}
+create_test_for_configs!(extend_preallocates_for);
+
+#[test]
+#[cfg(feature = "debug-stability-checks")]
+#[should_panic(expected = "element at index 0 has been moved")]
+fn stability_checks_detect_moved_elements() {
+    let mut vec = <BucketVec<i32>>::new();
+    vec.extend([1, 2, 3]);
+    // Simulate a regression that reallocates the first bucket.
+    vec.buckets[0] = vec.buckets[0].clone();
+    vec.push(4);
+}
+
+/// Alternates between yielding an element and yielding `None`, starting with an element.
+struct UnfusedIter {
+    exhausted: bool,
+}
+
+impl Iterator for UnfusedIter {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        self.exhausted = !self.exhausted;
+        if self.exhausted {
+            Some(1)
+        } else {
+            None
+        }
+    }
+}
+
+fn extend_stops_at_first_none_for<C>(_test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = BucketVec::<i32, C>::new();
+    for len in 1..=C::total_capacity_for(2) {
+        vec.extend(UnfusedIter { exhausted: false });
+        assert_eq!(vec.len(), len);
+    }
+}
+create_test_for_configs!(extend_stops_at_first_none_for);
+
+fn map_preserves_bucket_structure_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = BucketVec::<i32, C>::new();
+    let positions = test_values
+        .iter()
+        .map(|&value| vec.push_get(value).position())
+        .collect::<Vec<_>>();
+    let layout = vec
+        .buckets
+        .iter()
+        .map(|bucket| (bucket.len(), bucket.capacity()))
+        .collect::<Vec<_>>();
+    let mapped = vec.map(i64::from);
+    assert_iter_eq(
+        mapped.iter().copied(),
+        test_values.iter().map(|&v| i64::from(v)),
+    );
+    let mapped_layout = mapped
+        .buckets
+        .iter()
+        .map(|bucket| (bucket.len(), bucket.capacity()))
+        .collect::<Vec<_>>();
+    assert_eq!(mapped_layout, layout);
+    for (position, &value) in positions.iter().zip(test_values.iter()) {
+        let (x, y) = (position.bucket_index(), position.entry_index());
+        assert_eq!(mapped.buckets[x][y], i64::from(value));
+    }
+    // Zero-sized elements in either direction.
+    let units = test_values
+        .iter()
+        .collect::<BucketVec<&i32, C>>()
+        .map(|_| ());
+    assert_eq!(units.len(), test_values.len());
+    let ones = units.map(|()| 1);
+    assert!(ones.iter().all(|&one| one == 1));
+    assert_eq!(ones.len(), test_values.len());
+}
+create_test_for_configs!(map_preserves_bucket_structure_for);
+
+fn try_map_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let mapped = vec.clone().try_map(|value| Ok::<_, ()>(i64::from(value)));
+    assert_eq!(mapped.unwrap(), vec.clone().map(i64::from));
+    if let Some(&last) = test_values.last() {
+        let failed = vec.try_map(|value| if value == last { Err(value) } else { Ok(value) });
+        assert_eq!(failed, Err(last));
+    }
+    // Collecting fallible elements stops at the first error.
+    let collected = test_values
+        .iter()
+        .map(|&value| Ok::<_, ()>(value))
+        .collect::<Result<BucketVec<i32, C>, _>>()
+        .unwrap();
+    assert_eq!(collected, test_values);
+    let failed = test_values
+        .iter()
+        .map(|&value| Ok(value))
+        .chain(core::iter::once(Err("error")))
+        .collect::<Result<BucketVec<i32, C>, _>>();
+    assert_eq!(failed, Err("error"));
+}
+create_test_for_configs!(try_map_works_for);
+
+fn into_from_buckets_roundtrip_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    vec.prealloc_next_bucket();
+    let capacity = vec.capacity();
+    let buckets = vec.clone().into_buckets();
+    assert_eq!(buckets.len(), vec.buckets.len());
+    assert!(buckets.iter().flatten().eq(test_values.iter()));
+    let restored = BucketVec::<i32, C>::from_buckets(buckets).unwrap();
+    assert_eq!(restored, test_values);
+    assert_eq!(restored.offsets, vec.offsets);
+    // Trailing empty buckets are kept as reserved buckets.
+    let mut buckets = vec.into_buckets();
+    buckets.push(Vec::with_capacity(config::bucket_capacity::<C>(
+        buckets.len(),
+    )));
+    let restored = BucketVec::<i32, C>::from_buckets(buckets).unwrap();
+    assert_eq!(restored, test_values);
+    assert_eq!(restored.capacity(), capacity);
+}
+create_test_for_configs!(into_from_buckets_roundtrip_for);
+
+fn raw_parts_roundtrip_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    vec.prealloc_next_bucket();
+    let capacity = vec.capacity();
+    let first = vec.first().map(|value| value as *const i32);
+    let (len, buckets) = vec.into_raw_parts();
+    assert_eq!(len, test_values.len());
+    assert!(buckets.iter().flatten().eq(test_values.iter()));
+    assert_eq!(
+        BucketVec::<i32, C>::from_raw_parts(len + 1, buckets.clone()),
+        Err(LayoutError::LengthMismatch {
+            expected: len + 1,
+            found: len,
+        })
+    );
+    let restored = BucketVec::<i32, C>::from_raw_parts(len, buckets).unwrap();
+    assert_eq!(restored, test_values);
+    // Neither elements nor reserved capacity are lost.
+    assert_eq!(restored.first().map(|value| value as *const i32), first);
+    assert_eq!(restored.capacity(), capacity);
+}
+create_test_for_configs!(raw_parts_roundtrip_for);
+
+#[test]
+fn from_buckets_rejects_invalid_layouts() {
+    let with = |capacity: usize, values: &[i32]| {
+        let mut bucket = Vec::with_capacity(capacity);
+        bucket.extend_from_slice(values);
+        bucket
+    };
+    assert_eq!(
+        <BucketVec<i32>>::from_buckets(vec![with(5, &[1])]),
+        Err(LayoutError::CapacityMismatch {
+            bucket_index: 0,
+            expected: 4,
+            found: 5,
+        })
+    );
+    assert_eq!(
+        <BucketVec<i32>>::from_buckets(vec![with(4, &[1, 2, 3]), with(8, &[4])]),
+        Err(LayoutError::IncompleteBucket { bucket_index: 0 })
+    );
+    let vec = <BucketVec<i32>>::from_buckets(vec![with(4, &[1, 2]), with(8, &[])]).unwrap();
+    assert_eq!(vec, [1, 2]);
+    assert_eq!(vec.capacity(), 12);
+    let units = <BucketVec<()>>::from_buckets(vec![vec![(); 3], vec![(); 2]]).unwrap();
+    assert_eq!(units.len(), 5);
+}
+
+fn rebalance_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let rebalanced = vec.rebalance::<EqualSizeConfig>();
+    assert_eq!(rebalanced, test_values);
+    let len = test_values.len();
+    assert_eq!(
+        rebalanced.capacity(),
+        EqualSizeConfig::total_capacity_for(EqualSizeConfig::buckets_needed(len))
+    );
+    let restored = rebalanced.rebalance::<C>();
+    assert_eq!(restored, test_values);
+}
+create_test_for_configs!(rebalance_works_for);
+
+fn to_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let cloned = vec.to_vec();
+    assert_eq!(cloned, test_values);
+    assert_eq!(cloned.capacity(), test_values.len());
+    assert_eq!(vec.to_boxed_slice(), test_values.into_boxed_slice());
+}
+create_test_for_configs!(to_vec_works_for);
+
+fn fill_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let pointers = vec.ptr_table();
+    vec.fill(42);
+    assert!(vec.iter().all(|&value| value == 42));
+    let mut counter = 0;
+    vec.fill_with(|| {
+        counter += 1;
+        counter
+    });
+    assert_iter_eq(vec.iter().copied(), 1..test_values.len() as i32 + 1);
+    assert_eq!(vec.ptr_table(), pointers);
+}
+create_test_for_configs!(fill_works_for);
+
+fn reverse_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let addresses = vec
+        .iter()
+        .map(|value| value as *const i32)
+        .collect::<Vec<_>>();
+    vec.reverse();
+    assert_iter_eq(vec.iter(), test_values.iter().rev());
+    assert!(vec
+        .iter()
+        .map(|value| value as *const i32)
+        .eq(addresses.iter().cloned()));
+    vec.reverse();
+    assert_iter_eq(vec.iter(), test_values.iter());
+}
+create_test_for_configs!(reverse_works_for);
+
+fn zip_mut_with_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let wide = test_values
+        .iter()
+        .cloned()
+        .map(i64::from)
+        .collect::<Vec<_>>();
+    let mut lhs = wide.iter().cloned().collect::<BucketVec<_, C>>();
+    let rhs = test_values
+        .iter()
+        .cloned()
+        .collect::<BucketVec<_, EqualSizeConfig>>();
+    lhs.zip_mut_with(&rhs, |lhs, rhs| *lhs += i64::from(*rhs) * 2);
+    let expected = wide.iter().map(|value| value * 3).collect::<Vec<_>>();
+    assert_iter_eq(lhs.iter(), expected.iter());
+    // Only the common prefix of both bucket vectors is combined.
+    let half = test_values[..test_values.len() / 2]
+        .iter()
+        .cloned()
+        .collect::<BucketVec<_, TinyStartConfig>>();
+    lhs.zip_mut_with(&half, |lhs, rhs| *lhs -= i64::from(*rhs) * 3);
+    let expected = wide
+        .iter()
+        .enumerate()
+        .map(|(index, value)| if index < half.len() { 0 } else { value * 3 })
+        .collect::<Vec<_>>();
+    assert_iter_eq(lhs.iter(), expected.iter());
+    let mut empty = <BucketVec<i64, C>>::new();
+    empty.zip_mut_with(&half, |_, _| {
+        panic!("encountered element in empty bucket vector")
+    });
+}
+create_test_for_configs!(zip_mut_with_works_for);
+
+fn zip_buckets_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut keys = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let mut payloads = test_values
+        .iter()
+        .map(|&value| i64::from(value) * 2)
+        .collect::<BucketVec<_, C>>();
+    let mut len = 0;
+    for (bucket_index, (lhs, rhs)) in keys.zip_buckets(&payloads).enumerate() {
+        // Identical configurations pair whole buckets.
+        assert_eq!(lhs.len(), rhs.len());
+        assert_eq!(
+            lhs.len(),
+            config::bucket_capacity::<C>(bucket_index).min(keys.len() - len)
+        );
+        assert!(lhs
+            .iter()
+            .zip(rhs)
+            .all(|(&key, &payload)| i64::from(key) * 2 == payload));
+        len += lhs.len();
+    }
+    assert_eq!(len, test_values.len());
+    for (lhs, rhs) in keys.zip_buckets_mut(&mut payloads) {
+        lhs.fill(1);
+        rhs.fill(2);
+    }
+    assert!(keys.iter().all(|&key| key == 1));
+    assert!(payloads.iter().all(|&payload| payload == 2));
+    // Only the common prefix of both bucket vectors is paired.
+    payloads.push_repeated(10, 3);
+    let paired = keys
+        .zip_buckets(&payloads)
+        .map(|(lhs, _)| lhs.len())
+        .sum::<usize>();
+    assert_eq!(paired, test_values.len());
+    // Zero-sized elements are stored in a single bucket.
+    let units = core::iter::repeat_n((), test_values.len()).collect::<BucketVec<_, C>>();
+    let mut paired = keys.iter();
+    for (lhs, rhs) in keys.zip_buckets(&units) {
+        assert_eq!(lhs.len(), rhs.len());
+        assert!(lhs.iter().eq(paired.by_ref().take(lhs.len())));
+    }
+    assert_eq!(paired.next(), None);
+}
+create_test_for_configs!(zip_buckets_works_for);
+
+fn iter_step_by_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let len = test_values.len();
+    for &(start, step) in &[(0, 1), (0, 3), (1, 2), (5, 7), (2, 100), (0, len), (len, 1)] {
+        let expected = || test_values.iter().skip(start).step_by(step);
+        assert_iter_eq(vec.iter_step_by(start, step), expected());
+        assert!(vec.iter_step_by(start, step).rev().eq(expected().rev()));
+        for n in 0..5 {
+            let mut iter = vec.iter_step_by(start, step);
+            let mut expected = expected();
+            assert_eq!(iter.nth(n), expected.nth(n));
+            assert_eq!(iter.len(), expected.len());
+            assert_eq!(iter.next_back(), expected.next_back());
+            assert_eq!(iter.next(), expected.next());
+        }
+    }
+    assert_eq!(vec.iter_step_by(len + 1, 1).next(), None);
+    // Columns of interleaved data.
+    let columns = (0..3)
+        .map(|column| vec.iter_step_by(column, 3).copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    for (index, value) in test_values.iter().enumerate() {
+        assert_eq!(&columns[index % 3][index / 3], value);
+    }
+}
+create_test_for_configs!(iter_step_by_works_for);
+
+#[test]
+#[should_panic(expected = "step must be non-zero")]
+fn iter_step_by_panics_for_zero_step() {
+    let vec = [1, 2, 3].iter().cloned().collect::<BucketVec<i32>>();
+    let _ = vec.iter_step_by(0, 0);
+}
+
+fn sort_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let addresses = vec
+        .iter()
+        .map(|value| value as *const i32)
+        .collect::<Vec<_>>();
+    let mut expected = test_values.clone();
+    expected.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+    vec.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+    assert_iter_eq(vec.iter(), expected.iter());
+    assert!(vec
+        .iter()
+        .map(|value| value as *const i32)
+        .eq(addresses.iter().cloned()));
+    // Sorting by key is stable so equal keys keep their relative order.
+    let mut pairs = test_values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| (value % 3, index))
+        .collect::<BucketVec<_, C>>();
+    let mut expected = pairs.to_vec();
+    expected.sort_by_key(|(key, _)| *key);
+    pairs.sort_by_key(|(key, _)| *key);
+    assert_iter_eq(pairs.iter(), expected.iter());
+}
+create_test_for_configs!(sort_works_for);
+
+fn retain_invalidating_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let mut expected = test_values.clone();
+    vec.retain_invalidating(|value| value % 3 != 0);
+    expected.retain(|value| value % 3 != 0);
+    assert_eq!(vec, expected);
+    assert_eq!(
+        vec.capacity(),
+        C::total_capacity_for(C::buckets_needed(expected.len()))
+    );
+    // Pushing continues right after the retained elements.
+    vec.push(1);
+    expected.push(1);
+    assert_eq!(vec, expected);
+    vec.retain_invalidating(|_| false);
+    assert!(vec.is_empty());
+    assert_eq!(vec.capacity(), 0);
+}
+create_test_for_configs!(retain_invalidating_works_for);
+
+fn chunk_by_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    fn assert_runs<F>(vec: &BucketVec<i32, impl BucketVecConfig>, values: &[i32], pred: F)
+    where
+        F: Fn(&i32, &i32) -> bool,
+    {
+        let mut expected = values.chunk_by(&pred);
+        for run in vec.chunk_by(&pred) {
+            let expected = expected.next().unwrap();
+            assert_eq!(run.len(), expected.len());
+            assert!(run.clone().eq(expected.iter()));
+            assert!(run.rev().eq(expected.iter().rev()));
+        }
+        assert!(expected.next().is_none());
+    }
+    assert_runs(&vec, &test_values, |a, b| a % 2 == b % 2);
+    assert_runs(&vec, &test_values, |a, b| a <= b);
+    assert_runs(&vec, &test_values, |_, _| true);
+    assert_runs(&vec, &test_values, |_, _| false);
+}
+create_test_for_configs!(chunk_by_works_for);
+
+fn array_windows_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let pairs = vec.array_windows::<2>();
+    assert_eq!(pairs.len(), test_values.len().saturating_sub(1));
+    assert!(pairs
+        .map(|[lhs, rhs]| (*lhs, *rhs))
+        .eq(test_values.windows(2).map(|window| (window[0], window[1]))));
+    let mut triples = vec.array_windows::<3>();
+    for (index, window) in test_values.windows(3).enumerate() {
+        assert_eq!(triples.len(), test_values.len() - 2 - index);
+        assert!(triples.next().unwrap().iter().cloned().eq(window.iter()));
+    }
+    assert_eq!(triples.next(), None);
+    assert_eq!(triples.len(), 0);
+    let mut wide = vec.array_windows::<64>();
+    assert_eq!(wide.len(), test_values.len().saturating_sub(63));
+    assert_eq!(wide.next().is_some(), test_values.len() >= 64);
+}
+create_test_for_configs!(array_windows_works_for);
+
+fn chunks_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    for chunk_size in [1, 3, 7, 64] {
+        let chunks = vec.chunks(chunk_size);
+        assert_eq!(chunks.len(), test_values.chunks(chunk_size).len());
+        for (chunk, expected) in chunks.zip(test_values.chunks(chunk_size)) {
+            assert_iter_eq(chunk, expected.iter());
+        }
+    }
+    let zsts = core::iter::repeat_n((), test_values.len()).collect::<BucketVec<(), C>>();
+    assert_eq!(
+        zsts.chunks(5).map(|chunk| chunk.len()).sum::<usize>(),
+        test_values.len()
+    );
+}
+create_test_for_configs!(chunks_works_for);
+
+#[test]
+#[should_panic(expected = "window size must be non-zero")]
+fn array_windows_panics_for_empty_windows() {
+    let vec = [1, 2, 3].iter().cloned().collect::<BucketVec<i32>>();
+    let _ = vec.array_windows::<0>();
+}
+
+#[cfg(feature = "std")]
+fn extend_from_reader_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use std::io;
+    let bytes = test_values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect::<Vec<u8>>();
+    let mut vec = BucketVec::<u8, C>::new();
+    vec.push(42);
+    let first = vec.first().unwrap() as *const u8;
+    assert_eq!(vec.extend_from_reader(&bytes[..]).unwrap(), bytes.len());
+    assert_eq!(vec.first().unwrap() as *const u8, first);
+    assert!(vec.iter().eq(core::iter::once(&42).chain(bytes.iter())));
+    /// Yields an interruption and then fails after a single byte.
+    struct FailingReader(u8);
+    impl io::Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.0 += 1;
+            match self.0 {
+                1 => Err(io::ErrorKind::Interrupted.into()),
+                2 => {
+                    buf[0] = 7;
+                    Ok(1)
+                }
+                _ => Err(io::ErrorKind::Other.into()),
+            }
+        }
+    }
+    let mut vec = BucketVec::<u8, C>::new();
+    let error = vec.extend_from_reader(FailingReader(0)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::Other);
+    assert_eq!(vec, [7]);
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(extend_from_reader_works_for);
+
+#[cfg(feature = "std")]
+fn bucket_cursor_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use std::io::{self, BufRead as _, Read as _, Seek as _, Write as _};
+    let bytes = test_values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect::<Vec<u8>>();
+    let mut cursor = BucketCursor::<C>::default();
+    let mut expected = io::Cursor::new(Vec::new());
+    cursor.write_all(&bytes).unwrap();
+    expected.write_all(&bytes).unwrap();
+    let first = cursor.get_ref().first().map(|byte| byte as *const u8);
+    // Overwrite in place across bucket boundaries and append past the end.
+    for &(seek, len) in &[
+        (io::SeekFrom::Start(1), 9),
+        (io::SeekFrom::End(-3), 20),
+        (io::SeekFrom::Current(5), 3),
+        (io::SeekFrom::Start(bytes.len() as u64 / 2), bytes.len()),
+    ] {
+        assert_eq!(cursor.seek(seek).unwrap(), expected.seek(seek).unwrap());
+        let written = (0..len).map(|byte| byte as u8).collect::<Vec<_>>();
+        cursor.write_all(&written).unwrap();
+        expected.write_all(&written).unwrap();
+        assert_eq!(cursor.position(), expected.position());
+    }
+    assert_eq!(
+        cursor.get_ref().first().map(|byte| byte as *const u8),
+        first
+    );
+    assert!(cursor.get_ref().iter().eq(expected.get_ref().iter()));
+    // Read in odd sized chunks.
+    cursor.rewind().unwrap();
+    let mut read = Vec::new();
+    let mut chunk = [0x00; 7];
+    loop {
+        match cursor.read(&mut chunk).unwrap() {
+            0 => break,
+            len => read.extend_from_slice(&chunk[..len]),
+        }
+    }
+    assert_eq!(&read, expected.get_ref());
+    // Read bucket by bucket without copying.
+    cursor.set_position(0);
+    let mut read = Vec::new();
+    loop {
+        let buf = cursor.fill_buf().unwrap();
+        if buf.is_empty() {
+            break;
+        }
+        let len = buf.len();
+        read.extend_from_slice(buf);
+        cursor.consume(len);
+    }
+    assert_eq!(&read, expected.get_ref());
+    assert!(cursor.seek(io::SeekFrom::Current(-1)).is_ok());
+    assert!(cursor
+        .seek(io::SeekFrom::End(-(read.len() as i64) - 1))
+        .is_err());
+    assert_eq!(cursor.into_inner().len(), read.len());
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(bucket_cursor_works_for);
+
+#[test]
+#[cfg(feature = "std")]
+fn bucket_cursor_pads_gaps_with_zeros() {
+    use std::io::{Seek as _, SeekFrom, Write as _};
+    let mut cursor = BucketCursor::<DefaultConfig>::new([1, 2].iter().cloned().collect());
+    cursor.seek(SeekFrom::End(3)).unwrap();
+    cursor.write_all(&[9]).unwrap();
+    assert_eq!(cursor.get_ref(), &[1, 2, 0, 0, 0, 9]);
+    assert_eq!(cursor.position(), 6);
+}
+
+fn bulk_queries_work_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    for value in test_values.iter().step_by(7) {
+        assert!(vec.contains(value));
+    }
+    assert_eq!(vec.contains(&i32::MIN), test_values.contains(&i32::MIN));
+    assert_eq!(vec.iter().min(), test_values.iter().min());
+    assert_eq!(vec.iter().max(), test_values.iter().max());
+    let predicates: [fn(&i32) -> bool; 3] = [|v| v % 5 == 0, |v| v % 2 == 0, |_| false];
+    for &predicate in &predicates {
+        // Searches resume where the previous search stopped.
+        let mut actual = vec.iter();
+        let mut expected = test_values.iter();
+        actual.next_back();
+        expected.next_back();
+        loop {
+            let found = actual.position(predicate);
+            assert_eq!(found, expected.position(predicate));
+            assert_eq!(actual.len(), expected.len());
+            assert_eq!(
+                actual.clone().find(|v| predicate(v)),
+                expected.clone().find(|v| predicate(v))
+            );
+            assert_eq!(
+                actual.clone().any(predicate),
+                expected.clone().any(predicate)
+            );
+            assert_eq!(
+                actual.clone().all(predicate),
+                expected.clone().all(predicate)
+            );
+            if found.is_none() {
+                break;
+            }
+        }
+    }
+}
+create_test_for_configs!(bulk_queries_work_for);
+
+#[cfg(feature = "bytemuck")]
+fn bytemuck_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = test_values.iter().copied().collect::<BucketVec<i32, C>>();
+    let bytes = vec.bucket_bytes().flatten().copied().collect::<Vec<u8>>();
+    assert_eq!(bytes, ::bytemuck::cast_slice::<i32, u8>(&test_values));
+    // Loading from unaligned bytes.
+    let mut restored = BucketVec::<i32, C>::new();
+    restored.push(42);
+    let mut unaligned = vec![0u8];
+    unaligned.extend_from_slice(&bytes);
+    restored.extend_from_bytes(&unaligned[1..]);
+    assert!(restored
+        .iter()
+        .eq(core::iter::once(&42).chain(test_values.iter())));
+    for bytes in restored.bucket_bytes_mut() {
+        bytes.fill(0);
+    }
+    assert!(restored.iter().all(|&value| value == 0));
+}
+#[cfg(feature = "bytemuck")]
+create_test_for_configs!(bytemuck_works_for);
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn push_zeroed_works() {
+    let mut vec = <BucketVec<u64>>::new();
+    vec.push(1);
+    vec.push_zeroed(1_000_000);
+    assert_eq!(vec.len(), 1_000_001);
+    assert_eq!(vec.first(), Some(&1));
+    assert!(vec.iter().skip(1).all(|&value| value == 0));
+    vec.push(2);
+    assert_eq!(vec.get(1_000_001), Some(&2));
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+#[should_panic(expected = "number of bytes must be a multiple of the element size")]
+fn extend_from_bytes_panics_for_partial_elements() {
+    <BucketVec<u32>>::new().extend_from_bytes(&[1, 2, 3]);
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn extend_from_bytes_works_for_zero_sized_elements() {
+    let mut vec = <BucketVec<()>>::new();
+    vec.push(());
+    vec.extend_from_bytes(&[]);
+    assert_eq!(vec.len(), 1);
+    assert_eq!(vec.bucket_bytes().flatten().count(), 0);
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+#[should_panic(expected = "number of bytes must be a multiple of the element size")]
+fn extend_from_bytes_panics_for_zero_sized_elements_and_non_empty_bytes() {
+    <BucketVec<()>>::new().extend_from_bytes(&[1]);
+}
+
+#[cfg(feature = "std")]
+fn bucket_map_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut map = BucketMap::<String, i32, C>::new();
+    let mut expected = std::collections::HashMap::new();
+    let mut order = Vec::new();
+    let mut pointers = Vec::new();
+    for &value in &test_values {
+        let key = (value % 100).to_string();
+        let (index, old) = map.insert(key.clone(), value);
+        assert_eq!(old, expected.insert(key.clone(), value));
+        if old.is_none() {
+            assert_eq!(index, order.len());
+            order.push(key.clone());
+            pointers.push(map.get(&key).unwrap() as *const i32);
+        }
+        assert_eq!(map.get_index_of(key.as_str()), Some(index));
+    }
+    assert_eq!(map.len(), expected.len());
+    for (index, (key, value)) in map.iter().enumerate() {
+        assert_eq!(key, &order[index]);
+        assert_eq!(value, &expected[key]);
+        assert_eq!(value as *const i32, pointers[index]);
+    }
+    assert!(!map.contains_key("missing"));
+    *map.get_or_insert_with("missing".to_string(), || 0) += 1;
+    assert_eq!(map.get("missing"), Some(&1));
+    assert_eq!(
+        map.get_index(map.len() - 1),
+        Some((&"missing".to_string(), &1))
+    );
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(bucket_map_works_for);
+
+#[cfg(feature = "std")]
+fn intern_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut interned = InternBucketVec::<String, C>::new();
+    let mut expected = Vec::<String>::new();
+    for value in &test_values {
+        let value = (value % 50).to_string();
+        let index = interned.push_unique(value.clone());
+        match expected.iter().position(|stored| stored == &value) {
+            Some(position) => assert_eq!(index, position),
+            None => {
+                assert_eq!(index, expected.len());
+                expected.push(value.clone());
+            }
+        }
+        assert_eq!(interned.index_of(value.as_str()), Some(index));
+        assert_eq!(interned.get(index), Some(&value));
+    }
+    assert_eq!(interned.len(), expected.len());
+    assert!(interned.iter().eq(expected.iter()));
+    assert_eq!(interned.index_of("missing"), None);
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(intern_bucket_vec_works_for);
+
+/// Hashes all values to the same hash to provoke collisions.
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct CollidingHasher;
+
+#[cfg(feature = "std")]
+impl core::hash::Hasher for CollidingHasher {
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, _bytes: &[u8]) {}
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn intern_bucket_vec_resolves_hash_collisions() {
+    type Colliding = core::hash::BuildHasherDefault<CollidingHasher>;
+    let mut interned = <InternBucketVec<i32, DefaultConfig, Colliding>>::default();
+    for value in 0..20 {
+        assert_eq!(interned.push_unique(value), value as usize);
+    }
+    for value in (0..20).rev() {
+        assert_eq!(interned.push_unique(value), value as usize);
+        assert_eq!(interned.index_of(&value), Some(value as usize));
+    }
+    assert_eq!(interned.len(), 20);
+    assert_eq!(interned.index_of(&20), None);
+}
+
+fn bucket_heap_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut heap = <BucketHeap<core::cmp::Reverse<i32>, C>>::new();
+    assert_eq!(heap.peek(), None);
+    assert_eq!(heap.pop(), None);
+    for (index, &value) in test_values.iter().enumerate() {
+        assert_eq!(heap.push(core::cmp::Reverse(value)), index);
+    }
+    let min = heap.peek().unwrap() as *const _;
+    heap.push(core::cmp::Reverse(i32::MAX));
+    assert_eq!(heap.peek().unwrap() as *const _, min);
+    let mut expected = test_values.clone();
+    expected.push(i32::MAX);
+    expected.sort_unstable();
+    let mut popped = Vec::new();
+    while let Some(index) = heap.peek_index() {
+        let value = heap.pop().unwrap();
+        assert_eq!(heap.get(index), None);
+        popped.push(value.0);
+    }
+    assert_eq!(popped, expected);
+    assert!(heap.is_empty());
+    // Popped slots are reused instead of growing the heap.
+    let reused = heap.push(core::cmp::Reverse(0));
+    assert!(reused <= test_values.len());
+    assert_eq!(heap.get(reused), Some(&core::cmp::Reverse(0)));
+    assert_eq!(heap.pop(), Some(core::cmp::Reverse(0)));
+}
+create_test_for_configs!(bucket_heap_works_for);
+
+fn bucket_any_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig + 'static,
+{
+    let mut arena = <BucketAny<C>>::new();
+    let mut keys = Vec::new();
+    for &value in &test_values {
+        let number = arena.insert(value);
+        let name = arena.insert(value.to_string());
+        keys.push((number, name));
+    }
+    let unit = arena.insert(());
+    let first = keys.first().map(|&(number, name)| {
+        (
+            arena.get(number).unwrap() as *const i32,
+            arena.get(name).unwrap() as *const String,
+        )
+    });
+    assert_eq!(arena.len(), 2 * test_values.len() + 1);
+    assert_eq!(arena.get(unit), Some(&()));
+    for (&(number, name), value) in keys.iter().zip(&test_values) {
+        assert_eq!(arena.get(number), Some(value));
+        assert_eq!(arena.get(name), Some(&value.to_string()));
+    }
+    if let Some(&(number, name)) = keys.first() {
+        *arena.get_mut(number).unwrap() += 1;
+        arena.get_mut(name).unwrap().push('!');
+        assert_eq!(
+            first,
+            Some((
+                arena.get(number).unwrap() as *const i32,
+                arena.get(name).unwrap() as *const String
+            ))
+        );
+    }
+    // Keys of another arena never resolve even if the index is in bounds.
+    let mut other = <BucketAny<C>>::new();
+    let foreign = other.insert(0_u8);
+    arena.insert(1_u8);
+    assert_eq!(arena.get(foreign), None);
+    assert_eq!(arena.get_mut(foreign), None);
+    assert_eq!(other.get(foreign), Some(&0));
+    assert_eq!(
+        arena.of_type::<String>().map(BucketVec::len),
+        Some(test_values.len()).filter(|&len| len > 0)
+    );
+}
+create_test_for_configs!(bucket_any_works_for);
+
+fn ordered_index_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut vec = <BucketVec<i32, C>>::new();
+    let mut index = <OrderedIndex<i32>>::new();
+    let mut expected = BTreeMap::new();
+    for &value in &test_values {
+        let pushed = index.push(&mut vec, value, value);
+        expected.insert(value, pushed);
+    }
+    assert_eq!(index.len(), expected.len());
+    assert_eq!(index, OrderedIndex::build(&vec, |value| *value));
+    for (&key, &pushed) in &expected {
+        assert_eq!(index.index_of(&key), Some(pushed));
+        assert!(core::ptr::eq(
+            index.get_by_key(&vec, &key).unwrap(),
+            vec.get(pushed).unwrap()
+        ));
+    }
+    assert_eq!(index.get_by_key(&vec, &i32::MIN), None);
+    assert!(index
+        .iter(&vec)
+        .map(|(key, value)| (*key, *value))
+        .eq(expected.keys().map(|&key| (key, key))));
+    assert!(index
+        .range(&vec, 10..100)
+        .rev()
+        .map(|(_, value)| *value)
+        .eq(expected.range(10..100).rev().map(|(&key, _)| key)));
+}
+create_test_for_configs!(ordered_index_works_for);
+
+#[cfg(feature = "std")]
+fn indexed_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut indexed = <IndexedBucketVec<i32, String, _, C>>::new(|value: &i32| value.to_string());
+    let mut expected = std::collections::HashMap::new();
+    for &value in &test_values {
+        let index = indexed.push(value);
+        expected.insert(value.to_string(), index);
+    }
+    assert_eq!(indexed.len(), test_values.len());
+    assert_eq!(indexed.hash_index().len(), expected.len());
+    for (key, &index) in &expected {
+        assert_eq!(indexed.index_of(key.as_str()), Some(index));
+        assert!(core::ptr::eq(
+            indexed.get_by_key(key.as_str()).unwrap(),
+            indexed.get(index).unwrap()
+        ));
+    }
+    assert_eq!(indexed.get_by_key("missing"), None);
+    let rebuilt = HashIndex::build(indexed.as_bucket_vec(), |value: &i32| value.to_string());
+    for (key, &index) in &expected {
+        assert_eq!(rebuilt.index_of(key.as_str()), Some(index));
+    }
+    indexed.extend(vec![i32::MIN]);
+    assert_eq!(
+        indexed.get_by_key(i32::MIN.to_string().as_str()),
+        Some(&i32::MIN)
+    );
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(indexed_bucket_vec_works_for);
+
+#[cfg(feature = "std")]
+fn digest_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use core::hash::{Hash as _, Hasher as _};
+    use std::collections::hash_map::DefaultHasher;
+    let mut digested = <DigestBucketVec<i32, DefaultHasher, C>>::default();
+    let mut expected = DefaultHasher::new();
+    assert_eq!(digested.digest(), expected.finish());
+    let (head, tail) = test_values.split_at(test_values.len() / 2);
+    for (index, value) in head.iter().enumerate() {
+        assert_eq!(digested.push(*value), index);
+        value.hash(&mut expected);
+    }
+    assert_eq!(digested.digest(), expected.finish());
+    digested.extend(tail.iter().copied());
+    tail.iter().for_each(|value| value.hash(&mut expected));
+    assert_eq!(digested.digest(), expected.finish());
+    assert_iter_eq(digested.iter(), test_values.iter());
+    let (vec, hasher) = digested.into_parts();
+    assert_eq!(vec.len(), test_values.len());
+    assert_eq!(hasher.finish(), expected.finish());
+}
+#[cfg(feature = "std")]
+create_test_for_configs!(digest_bucket_vec_works_for);
+
+fn tracked_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let len = test_values.len();
+    let mut tracked = <TrackedBucketVec<i32, C>>::new();
+    assert!(!tracked.has_dirty());
+    let (head, tail) = test_values.split_at(len / 2);
+    for (index, value) in head.iter().enumerate() {
+        assert_eq!(tracked.push(*value), index);
+    }
+    tracked.extend(tail.iter().copied());
+    assert_iter_eq(tracked.iter(), test_values.iter());
+    assert_eq!(tracked.take_dirty(), expected_all(len));
+    assert!(!tracked.has_dirty());
+    assert_eq!(tracked.get_mut(len), None);
+    assert!(!tracked.has_dirty());
+    // Mutate every third element in reverse order.
+    for index in (0..len).step_by(3).rev() {
+        *tracked.get_mut(index).unwrap() += 1;
+    }
+    for index in 0..len {
+        assert_eq!(tracked.is_dirty(index), index % 3 == 0);
+    }
+    let expected: Vec<_> = (0..len).step_by(3).map(|index| index..index + 1).collect();
+    assert_eq!(tracked.take_dirty(), expected);
+    // Adjacent and overlapping changes are merged.
+    tracked.mark_dirty(4..6);
+    tracked.mark_dirty(0..2);
+    tracked.mark_dirty(2..4);
+    tracked.mark_dirty(8..10);
+    tracked.mark_dirty(1..9);
+    tracked.mark_dirty(20..20);
+    assert_eq!(tracked.take_dirty(), expected_all(10));
+    tracked.fill(0);
+    assert!(tracked.iter().all(|value| *value == 0));
+    assert_eq!(tracked.take_dirty(), expected_all(len));
+    tracked.iter_mut().for_each(|value| *value = 1);
+    assert_eq!(tracked.take_dirty(), expected_all(len));
+    let vec = tracked.into_bucket_vec();
+    assert_eq!(vec.len(), len);
+    assert!(<TrackedBucketVec<_, C>>::from(vec).take_dirty().is_empty());
+
+    fn expected_all(len: usize) -> Vec<core::ops::Range<usize>> {
+        (len > 0).then_some(0..len).into_iter().collect()
+    }
+}
+create_test_for_configs!(tracked_bucket_vec_works_for);
+
+fn bounded_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let capacity = test_values.len() / 3;
+    let mut bounded = <BoundedBucketVec<i32, C>>::with_capacity(capacity);
+    assert!(bounded.is_empty());
+    for (index, value) in test_values.iter().enumerate() {
+        let evicted = bounded.push(*value);
+        let expected = index
+            .checked_sub(capacity)
+            .map(|oldest| test_values[oldest]);
+        assert_eq!(evicted, expected);
+        let start = (index + 1).saturating_sub(capacity);
+        let window = &test_values[start..=index];
+        let window = &window[window.len() - bounded.len()..];
+        assert!(bounded.iter().eq(window.iter()));
+        assert!(bounded.iter().rev().eq(window.iter().rev()));
+        assert_eq!(bounded.first(), window.first());
+        assert_eq!(bounded.last(), window.last());
+        assert_eq!(bounded.get(bounded.len()), None);
+    }
+    assert!(bounded.as_bucket_vec().capacity() >= capacity);
+    // Slots never move when values rotate through them.
+    let ptrs = (0..bounded.len())
+        .map(|index| bounded.get(index).unwrap() as *const i32)
+        .collect::<BTreeSet<_>>();
+    bounded.extend(test_values.iter().copied());
+    for index in 0..bounded.len() {
+        assert!(ptrs.contains(&(bounded.get(index).unwrap() as *const i32)));
+        *bounded.get_mut(index).unwrap() += 1;
+    }
+    let start = test_values.len() - bounded.len();
+    assert!(bounded.iter().eq(test_values[start..]
+        .iter()
+        .map(|value| value + 1)
+        .collect::<Vec<_>>()
+        .iter()));
+    assert_eq!(
+        <BoundedBucketVec<i32, C>>::with_capacity(0).push(42),
+        Some(42)
+    );
+}
+create_test_for_configs!(bounded_bucket_vec_works_for);
+
+fn capped_bucket_vec_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut capped = <CappedBucketVec<i32, 10, C>>::new();
+    assert_eq!(<CappedBucketVec<i32, 10, C>>::max_len(), 10);
+    for (index, value) in test_values.iter().enumerate() {
+        match capped.try_push(*value) {
+            Ok(pushed) => assert_eq!(pushed, index),
+            Err(rejected) => {
+                assert_eq!(rejected, *value);
+                assert!(capped.is_full());
+            }
+        }
+    }
+    let len = test_values.len().min(10);
+    assert_eq!(capped.len(), len);
+    assert!(capped.iter().eq(&test_values[..len]));
+    assert_eq!(capped.pop(), test_values[..len].last().copied());
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let capped = <CappedBucketVec<i32, 10, C> as core::convert::TryFrom<_>>::try_from(vec);
+    assert_eq!(capped.is_ok(), test_values.len() <= 10);
+}
+create_test_for_configs!(capped_bucket_vec_works_for);
+
+#[cfg(feature = "persistence")]
+fn append_log_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut log = Vec::new();
+    let mut vec = <LoggedBucketVec<i32, C>>::new();
+    assert!(vec.is_synced());
+    for chunk in test_values.chunks(test_values.len() / 4 + 1) {
+        vec.extend(chunk.iter().copied());
+        let len_log = log.len();
+        vec.append_to_log(&mut log).unwrap();
+        assert!(vec.is_synced());
+        // Only the new elements are appended.
+        assert!(log.len() - len_log <= 1 + 9 + 5 * chunk.len());
+    }
+    // Appending without new elements adds an empty record.
+    vec.append_to_log(&mut log).unwrap();
+    // Changes to synced elements are persisted by a snapshot.
+    if let Some(first) = vec.get_mut(0) {
+        *first += 1;
+        assert!(!vec.is_synced());
+    }
+    vec.truncate(vec.len() / 2);
+    vec.push(42);
+    vec.push(43);
+    vec.append_to_log(&mut log).unwrap();
+    vec.push(44);
+    vec.append_to_log(&mut log).unwrap();
+    let (replayed, torn_tail) = <LoggedBucketVec<i32, C>>::replay_from_log(&log[..]).unwrap();
+    assert_eq!(torn_tail, None);
+    assert!(replayed.is_synced());
+    assert_eq!(replayed.as_bucket_vec(), vec.as_bucket_vec());
+}
+#[cfg(feature = "persistence")]
+create_test_for_configs!(append_log_works_for);
+
+#[cfg(feature = "persistence")]
+#[test]
+fn logged_bucket_vec_snapshots_after_truncation() {
+    let mut log = Vec::new();
+    let mut vec = <LoggedBucketVec<i32>>::new();
+    vec.extend([1, 2, 3]);
+    vec.append_to_log(&mut log).unwrap();
+    // Pushing past the synced length again must not hide the truncation.
+    vec.truncate(1);
+    vec.extend([4, 5, 6]);
+    assert!(!vec.is_synced());
+    vec.append_to_log(&mut log).unwrap();
+    assert!(vec.is_synced());
+    assert_eq!(
+        <BucketVec<i32>>::replay_from_log(&log[..]).unwrap().0,
+        [1, 4, 5, 6]
+    );
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn replay_from_log_rejects_invalid_records() {
+    let mut log = Vec::new();
+    let mut vec = <LoggedBucketVec<i32>>::new();
+    vec.extend([1, 2, 3]);
+    vec.append_to_log(&mut log).unwrap();
+    vec.pop();
+    vec.as_bucket_vec().snapshot_to_log(&mut log).unwrap();
+    assert_eq!(
+        <BucketVec<i32>>::replay_from_log(&log[..]).unwrap().0,
+        [1, 2]
+    );
+    // Unknown tags are rejected.
+    log.push(0xFF);
+    assert!(<BucketVec<i32>>::replay_from_log(&log[..]).is_err());
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn replay_from_log_stops_at_torn_tail() {
+    let mut log = Vec::new();
+    let mut vec = <LoggedBucketVec<i32>>::new();
+    vec.extend([1, 2, 3]);
+    vec.append_to_log(&mut log).unwrap();
+    let valid_len = log.len();
+    vec.extend([4, 5]);
+    vec.append_to_log(&mut log).unwrap();
+    let boundary = log.len();
+    vec.truncate(1);
+    vec.append_to_log(&mut log).unwrap();
+    // Every truncation within the last two records replays the valid prefix.
+    for len in (valid_len + 1..log.len()).filter(|&len| len != boundary) {
+        let (replayed, torn_tail) = <BucketVec<i32>>::replay_from_log(&log[..len]).unwrap();
+        let torn_tail = torn_tail.expect("missing torn tail of truncated log");
+        let complete = &log[..torn_tail.offset() as usize];
+        assert_eq!(
+            <BucketVec<i32>>::replay_from_log(complete).unwrap(),
+            (replayed.clone(), None)
+        );
+        match torn_tail.records() {
+            1 => assert_eq!(replayed, [1, 2, 3]),
+            2 => assert_eq!(replayed, [1, 2, 3, 4, 5]),
+            records => panic!("unexpected number of complete records {}", records),
+        }
+    }
+    // The logged bucket vector continues syncing to the truncated log.
+    let mut truncated = log[..log.len() - 1].to_vec();
+    let (mut replayed, torn_tail) =
+        <LoggedBucketVec<i32>>::replay_from_log(&truncated[..]).unwrap();
+    let torn_tail = torn_tail.unwrap();
+    assert_eq!(torn_tail.records(), 2);
+    truncated.truncate(torn_tail.offset() as usize);
+    replayed.push(6);
+    replayed.append_to_log(&mut truncated).unwrap();
+    assert_eq!(
+        <BucketVec<i32>>::replay_from_log(&truncated[..]).unwrap(),
+        (BucketVec::from([1, 2, 3, 4, 5, 6]), None)
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(crate::BucketSoA)]
+struct Particle {
+    id: i32,
+    name: String,
+}
+
+#[cfg(feature = "derive")]
+fn bucket_soa_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let mut particles = ParticleSoA::<C>::new();
+    assert!(particles.is_empty());
+    for (n, &value) in test_values.iter().enumerate() {
+        let index = particles.push(Particle {
+            id: value,
+            name: value.to_string(),
+        });
+        assert_eq!(index, n);
+    }
+    assert_eq!(particles.len(), test_values.len());
+    let first_id = particles.id_column().get(0).map(|id| id as *const i32);
+    for id in particles.id_iter_mut() {
+        *id += 1;
+    }
+    if let Some(name) = particles.name_get_mut(0) {
+        name.push('!');
+    }
+    particles.push(Particle {
+        id: 0,
+        name: String::new(),
+    });
+    assert_eq!(
+        particles.id_column().get(0).map(|id| id as *const i32),
+        first_id
+    );
+    assert!(particles
+        .id_column()
+        .iter()
+        .take(test_values.len())
+        .eq(test_values
+            .iter()
+            .map(|value| value + 1)
+            .collect::<Vec<_>>()
+            .iter()));
+    for (n, value) in test_values.iter().enumerate().skip(1) {
+        assert_eq!(particles.name_column().get(n), Some(&value.to_string()));
+    }
+    if let Some(value) = test_values.first() {
+        assert_eq!(particles.name_column().get(0), Some(&format!("{}!", value)));
+    }
+}
+#[cfg(feature = "derive")]
+create_test_for_configs!(bucket_soa_works_for);
+
+/// A struct whose field names match the methods of its container.
+#[cfg(feature = "derive")]
+#[derive(crate::BucketSoA)]
+struct Segment {
+    len: u32,
+    push: u8,
+}
+
+#[test]
+#[cfg(feature = "derive")]
+fn bucket_soa_works_for_fields_named_like_methods() {
+    let mut segments = <SegmentSoA>::new();
+    assert_eq!(segments.push(Segment { len: 10, push: 1 }), 0);
+    assert_eq!(segments.push(Segment { len: 20, push: 2 }), 1);
+    assert_eq!(segments.len(), 2);
+    if let Some(len) = segments.len_get_mut(1) {
+        *len += 1;
+    }
+    assert_eq!(segments.len_column().get(1), Some(&21));
+    assert!(segments.push_column().iter().eq([1, 2].iter()));
+}
+
+/// A configuration declared through the attribute macro.
+#[cfg(feature = "derive")]
+#[crate::bucket_config(starting_capacity = 3, growth_rate = 1.5)]
+#[derive(Debug)]
+enum AttributeConfig {}
+
+/// A capped and rounded configuration declared through the attribute macro.
+#[cfg(feature = "derive")]
+#[crate::bucket_config(
+    starting_capacity = 3,
+    growth_rate = 1.5,
+    max_bucket_capacity = 100,
+    round_to_multiple_of = 64
+)]
+#[derive(Debug)]
+enum AttributeQuantizedCappedConfig {}
+
+/// A configuration with an integral growth rate declared through the
+/// attribute macro.
+#[cfg(feature = "derive")]
+#[crate::bucket_config(starting_capacity = 5, growth_rate = 1, round_to_power_of_two)]
+#[derive(Debug)]
+enum AttributePowerOfTwoEqualSizeConfig {}
+
+#[test]
+#[cfg(feature = "derive")]
+fn bucket_config_attribute_works() {
+    fn assert_same_capacities<C1, C2>()
+    where
+        C1: BucketVecConfig,
+        C2: BucketVecConfig,
+    {
+        for index in 0..100 {
+            assert_eq!(
+                config::bucket_capacity::<C1>(index),
+                config::bucket_capacity::<C2>(index)
+            );
+        }
+        assert_eq!(
+            config::max_bucket_capacity::<C1>(),
+            config::max_bucket_capacity::<C2>()
+        );
+    }
+    assert_eq!(AttributeConfig::STARTING_CAPACITY, 3);
+    assert_eq!(AttributeConfig::GROWTH_RATE, 1.5);
+    assert_same_capacities::<AttributeConfig, ConstConfig<3, 3, 2>>();
+    assert_same_capacities::<AttributeQuantizedCappedConfig, QuantizedCappedConfig>();
+    assert_same_capacities::<AttributePowerOfTwoEqualSizeConfig, PowerOfTwoEqualSizeConfig>();
+    let vec = (0..1000).collect::<BucketVec<i32, AttributeQuantizedCappedConfig>>();
+    assert!(vec.iter().eq(&(0..1000).collect::<Vec<_>>()));
+}
+
+#[cfg(feature = "ink")]
+fn ink_storage_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig + 'static,
+{
+    use ink_metadata::layout::{Layout, LayoutKey};
+    use ink_storage_traits::{Storable, StorageLayout};
+    let vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let mut encoded = Vec::new();
+    Storable::encode(&vec, &mut encoded);
+    let decoded = <BucketVec<i32, C> as Storable>::decode(&mut &encoded[..]).unwrap();
+    assert_iter_eq(decoded.iter(), test_values.iter());
+    match <BucketVec<i32, C> as StorageLayout>::layout(&42) {
+        Layout::Leaf(leaf) => assert_eq!(leaf.key(), &LayoutKey::from(&42)),
+        layout => panic!("expected leaf layout but found {:?}", layout),
+    }
+}
+#[cfg(feature = "ink")]
+create_test_for_configs!(ink_storage_works_for);
+
+#[cfg(feature = "rand")]
+fn rand_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use rand::{rngs::StdRng, SeedableRng as _};
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut vec = test_values.iter().cloned().collect::<BucketVec<_, C>>();
+    let addresses = vec
+        .iter()
+        .map(|value| value as *const i32)
+        .collect::<Vec<_>>();
+    vec.shuffle(&mut rng);
+    assert!(vec
+        .iter()
+        .map(|value| value as *const i32)
+        .eq(addresses.iter().cloned()));
+    let mut shuffled = vec.to_vec();
+    let mut expected = test_values.clone();
+    shuffled.sort_unstable();
+    expected.sort_unstable();
+    assert_eq!(shuffled, expected);
+    match vec.choose(&mut rng) {
+        Some(chosen) => assert!(test_values.contains(chosen)),
+        None => assert!(test_values.is_empty()),
+    }
+    let chosen = vec.choose_multiple(&mut rng, 5).collect::<Vec<_>>();
+    assert_eq!(chosen.len(), core::cmp::min(5, test_values.len()));
+    for (n, lhs) in chosen.iter().enumerate() {
+        assert!(chosen[n + 1..].iter().all(|rhs| !core::ptr::eq(*lhs, *rhs)));
+    }
+    assert_eq!(
+        vec.choose_multiple(&mut rng, usize::MAX).count(),
+        test_values.len()
+    );
+}
+#[cfg(feature = "rand")]
+create_test_for_configs!(rand_works_for);
+
+#[test]
+fn const_new_works() {
+    static EMPTY: BucketVec<i32> = BucketVec::new();
+    const EMPTY_CONST: BucketVec<u8, TinyStartConfig> = BucketVec::new();
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY_CONST.len(), 0);
+}
+
+fn from_array_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    let vec = BucketVec::<_, C>::from([1, 2, 3, 4, 5]);
+    assert_iter_eq(vec.iter(), [1, 2, 3, 4, 5].iter());
+    let empty = BucketVec::<i32, C>::from([0; 0]);
+    assert!(empty.is_empty());
+    let strings = test_values
+        .iter()
+        .take(3)
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if let [a, b, c] = &strings[..] {
+        let vec = BucketVec::<_, C>::from([a.clone(), b.clone(), c.clone()]);
+        assert_iter_eq(vec.iter(), strings.iter());
+    }
+}
+create_test_for_configs!(from_array_works_for);
+
+#[cfg(all(feature = "serde", feature = "bincode", feature = "scale-3"))]
+fn with_layout_works_for<C>(test_values: Vec<i32>)
+where
+    C: BucketVecConfig,
+{
+    use ::scale::{Decode as _, Encode as _};
+    let vec = WithLayout(test_values.iter().cloned().collect::<BucketVec<_, C>>());
+    let json = serde_json::to_string(&vec).unwrap();
+    let decoded = serde_json::from_str::<WithLayout<i32, C>>(&json).unwrap();
+    assert_iter_eq(decoded.0.iter(), test_values.iter());
+    let config = ::bincode::config::standard();
+    let encoded = ::bincode::encode_to_vec(&vec, config).unwrap();
+    let (decoded, read) =
+        ::bincode::decode_from_slice::<WithLayout<i32, C>, _>(&encoded, config).unwrap();
+    assert_eq!(read, encoded.len());
+    assert_iter_eq(decoded.0.iter(), test_values.iter());
+    let encoded = vec.encode();
+    assert_eq!(encoded.len(), vec.size_hint());
+    let decoded = <WithLayout<i32, C>>::decode(&mut &encoded[..]).unwrap();
+    assert_iter_eq(decoded.0.iter(), test_values.iter());
+}
+#[cfg(all(feature = "serde", feature = "bincode", feature = "scale-3"))]
+create_test_for_configs!(with_layout_works_for);
+
+#[cfg(all(feature = "serde", feature = "bincode", feature = "scale-3"))]
+#[test]
+fn with_layout_rejects_mismatching_configs() {
+    use ::scale::{Decode as _, Encode as _};
+    let vec = WithLayout([1, 2, 3].iter().cloned().collect::<BucketVec<i32>>());
+    let json = serde_json::to_string(&vec).unwrap();
+    let error = serde_json::from_str::<WithLayout<i32, TinyStartConfig>>(&json).unwrap_err();
+    let expected = LayoutMismatch::StartingCapacity {
+        expected: TinyStartConfig::STARTING_CAPACITY as u64,
+        found: DefaultConfig::STARTING_CAPACITY as u64,
+    };
+    assert!(error.to_string().contains(&expected.to_string()));
+    let config = ::bincode::config::standard();
+    let encoded = ::bincode::encode_to_vec(&vec, config).unwrap();
+    assert!(
+        ::bincode::decode_from_slice::<WithLayout<i32, TinyStartConfig>, _>(&encoded, config)
+            .is_err()
+    );
+    let encoded = vec.encode();
+    assert!(<WithLayout<i32, TinyStartConfig>>::decode(&mut &encoded[..]).is_err());
+    // Bucket vectors serialized without layout are rejected as well.
+    let json = serde_json::to_string(&vec.0).unwrap();
+    assert!(BucketVec::<i32>::deserialize_with_layout_check(
+        &mut serde_json::Deserializer::from_str(&json)
+    )
+    .is_err());
+}
+
+#[cfg(all(feature = "serde", feature = "bincode", feature = "scale-3"))]
+#[test]
+fn with_layout_rejects_mismatching_rounding_and_schedules() {
+    use ::scale::{Decode as _, Encode as _};
+    /// The Fibonacci configuration without capacity rounding.
+    #[derive(Debug)]
+    enum ExactFibonacciConfig {}
+    impl BucketVecConfig for ExactFibonacciConfig {
+        const STARTING_CAPACITY: usize = 4;
+        const GROWTH_RATE: f64 = 1.618_033_988_749_895;
+    }
+    /// The geometric schedule that `PlateauConfig` deviates from.
+    #[derive(Debug)]
+    enum DoublingFromOneConfig {}
+    impl BucketVecConfig for DoublingFromOneConfig {
+        const STARTING_CAPACITY: usize = 1;
+        const GROWTH_RATE: f64 = 2.0;
+    }
+    let vec = WithLayout(
+        [1, 2, 3]
+            .iter()
+            .cloned()
+            .collect::<BucketVec<u8, PowerOfTwoFibonacciConfig>>(),
+    );
+    let json = serde_json::to_string(&vec).unwrap();
+    let error = serde_json::from_str::<WithLayout<u8, ExactFibonacciConfig>>(&json).unwrap_err();
+    let expected = LayoutMismatch::CapacityRounding {
+        expected: CapacityRounding::Exact,
+        found: Some(CapacityRounding::PowerOfTwo),
+    };
+    assert!(error.to_string().contains(&expected.to_string()));
+    let config = ::bincode::config::standard();
+    let encoded = ::bincode::encode_to_vec(&vec, config).unwrap();
+    assert!(
+        ::bincode::decode_from_slice::<WithLayout<u8, ExactFibonacciConfig>, _>(&encoded, config)
+            .is_err()
+    );
+    let encoded = vec.encode();
+    assert!(<WithLayout<u8, ExactFibonacciConfig>>::decode(&mut &encoded[..]).is_err());
+    let vec = WithLayout(
+        [1, 2, 3]
+            .iter()
+            .cloned()
+            .collect::<BucketVec<u8, PlateauConfig>>(),
+    );
+    let json = serde_json::to_string(&vec).unwrap();
+    let error = serde_json::from_str::<WithLayout<u8, DoublingFromOneConfig>>(&json).unwrap_err();
+    let expected = LayoutMismatch::Geometric {
+        expected: true,
+        found: false,
+    };
+    assert!(error.to_string().contains(&expected.to_string()));
+    let encoded = ::bincode::encode_to_vec(&vec, config).unwrap();
+    assert!(
+        ::bincode::decode_from_slice::<WithLayout<u8, DoublingFromOneConfig>, _>(&encoded, config)
+            .is_err()
+    );
+    let encoded = vec.encode();
+    assert!(<WithLayout<u8, DoublingFromOneConfig>>::decode(&mut &encoded[..]).is_err());
+}
+
+/// A Fibonacci configuration whose bucket capacities are powers of two.
+#[derive(Debug)]
+enum PowerOfTwoFibonacciConfig {}
+
+impl BucketVecConfig for PowerOfTwoFibonacciConfig {
+    const STARTING_CAPACITY: usize = 4;
+    const GROWTH_RATE: f64 = 1.618_033_988_749_895;
+    const CAPACITY_ROUNDING: CapacityRounding = CapacityRounding::PowerOfTwo;
+}
+
+/// A fractional configuration whose bucket capacities are multiples of 16.
+#[derive(Debug)]
+enum QuantizedConfig {}
+
+impl BucketVecConfig for QuantizedConfig {
+    const STARTING_CAPACITY: usize = 3;
+    const GROWTH_RATE: f64 = 1.5;
+    const CAPACITY_ROUNDING: CapacityRounding = CapacityRounding::MultipleOf(16);
+}
+
+/// A capped fractional configuration whose bucket capacities are multiples
+/// of 64 and therefore exceed the cap.
+#[derive(Debug)]
+enum QuantizedCappedConfig {}
+
+impl BucketVecConfig for QuantizedCappedConfig {
+    const STARTING_CAPACITY: usize = 3;
+    const GROWTH_RATE: f64 = 1.5;
+    const MAX_BUCKET_CAPACITY: Option<usize> = Some(100);
+    const CAPACITY_ROUNDING: CapacityRounding = CapacityRounding::MultipleOf(64);
+}
+
+/// A configuration with equally sized buckets rounded to powers of two.
+#[derive(Debug)]
+enum PowerOfTwoEqualSizeConfig {}
+
+impl BucketVecConfig for PowerOfTwoEqualSizeConfig {
+    const STARTING_CAPACITY: usize = 5;
+    const GROWTH_RATE: f64 = 1.0;
+    const CAPACITY_ROUNDING: CapacityRounding = CapacityRounding::PowerOfTwo;
+}
+
+/// A configuration with rounded capacities that grows too slowly to be
+/// tabulated.
+#[derive(Debug)]
+enum SlowQuantizedConfig {}
+
+impl BucketVecConfig for SlowQuantizedConfig {
+    const STARTING_CAPACITY: usize = 3;
+    const GROWTH_RATE: f64 = 1.01;
+    const CAPACITY_ROUNDING: CapacityRounding = CapacityRounding::MultipleOf(8);
+}
+
+#[test]
+fn capacity_rounding_works() {
+    assert_eq!(CapacityRounding::Exact.round(1013), Some(1013));
+    assert_eq!(CapacityRounding::PowerOfTwo.round(1013), Some(1024));
+    assert_eq!(CapacityRounding::PowerOfTwo.round(1024), Some(1024));
+    assert_eq!(CapacityRounding::MultipleOf(16).round(1013), Some(1024));
+    assert_eq!(CapacityRounding::MultipleOf(16).round(1008), Some(1008));
+    assert_eq!(CapacityRounding::PowerOfTwo.round(usize::MAX), None);
+    assert_eq!(CapacityRounding::MultipleOf(16).round(usize::MAX), None);
+}
+
+#[test]
+fn rounded_bucket_capacities_are_size_classes() {
+    for index in 0..40 {
+        let exact = config::bucket_capacity::<FibonacciConfig>(index);
+        assert_eq!(
+            config::bucket_capacity::<PowerOfTwoFibonacciConfig>(index),
+            exact.next_power_of_two()
+        );
+        let exact = config::bucket_capacity::<ConstConfig<3, 3, 2>>(index);
+        let rounded = config::bucket_capacity::<QuantizedConfig>(index);
+        assert!(rounded.is_multiple_of(16) && rounded >= exact && rounded - exact < 16);
+    }
+    let capacities = (0..12)
+        .map(config::bucket_capacity::<QuantizedCappedConfig>)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        capacities,
+        vec![64, 64, 64, 64, 64, 64, 64, 64, 128, 128, 128, 128]
+    );
+    assert_eq!(
+        config::max_bucket_capacity::<QuantizedCappedConfig>(),
+        Some(128)
+    );
+    assert_eq!(config::bucket_capacity::<PowerOfTwoEqualSizeConfig>(7), 8);
+    assert_eq!(
+        config::max_bucket_capacity::<PowerOfTwoEqualSizeConfig>(),
+        Some(8)
+    );
+    assert_eq!(
+        config::integral_growth_rate::<PowerOfTwoEqualSizeConfig>(),
+        None
+    );
+}
+
+#[test]
+fn rounded_bucket_entry_indices_are_exact() {
+    bucket_entry_indices_are_exact_for::<PowerOfTwoFibonacciConfig>();
+    bucket_entry_indices_are_exact_for::<QuantizedConfig>();
+    bucket_entry_indices_are_exact_for::<QuantizedCappedConfig>();
+    bucket_entry_indices_are_exact_for::<PowerOfTwoEqualSizeConfig>();
+    capacity_math_near_overflow_works_for::<PowerOfTwoFibonacciConfig>();
+    capacity_math_near_overflow_works_for::<QuantizedConfig>();
+    // Walks its buckets since it is not tabulated.
+    let mut total = 0;
+    for bucket_index in 0..500 {
+        let capacity = config::bucket_capacity::<SlowQuantizedConfig>(bucket_index);
+        assert!(capacity.is_multiple_of(8));
+        assert_eq!(
+            config::total_capacity::<SlowQuantizedConfig>(bucket_index),
+            total
+        );
+        assert_eq!(
+            config::bucket_entry_indices::<SlowQuantizedConfig>(total + capacity - 1),
+            (bucket_index, capacity - 1)
+        );
+        total += capacity;
+    }
+}
+
+#[test]
+fn rounded_index_math_agrees_with_allocations() {
+    index_math_agrees_with_allocations_for::<PowerOfTwoFibonacciConfig>();
+    index_math_agrees_with_allocations_for::<QuantizedCappedConfig>();
+}
+
+#[test]
+#[should_panic(expected = "custom, capped or rounded bucket capacity schedules are not supported")]
+fn dyn_bucket_vec_rejects_rounded_config() {
+    let _ = DynBucketVec::<i32>::from_config::<QuantizedConfig>();
+}
@@ -0,0 +1,204 @@
+//! Bucket vector that records which of its elements have been changed.
+
+use super::{BucketVec, BucketVecConfig, DefaultConfig, Iter, IterMut};
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// A bucket vector that records the indices of all elements that have been
+/// pushed or mutably accessed since the last call to
+/// [`take_dirty`](TrackedBucketVec::take_dirty).
+///
+/// This allows to replicate or persist only the changed elements instead of
+/// diffing full snapshots. Elements are considered changed as soon as they
+/// are mutably accessed, whether or not they are actually written to.
+#[derive(Debug, Clone)]
+pub struct TrackedBucketVec<T, C = DefaultConfig> {
+    /// The tracked elements.
+    values: BucketVec<T, C>,
+    /// The disjoint and non-adjacent ranges of changed indices by their start.
+    dirty: BTreeMap<usize, usize>,
+}
+
+impl<T, C> Default for TrackedBucketVec<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> From<BucketVec<T, C>> for TrackedBucketVec<T, C> {
+    /// Starts tracking the bucket vector with none of its elements changed.
+    fn from(values: BucketVec<T, C>) -> Self {
+        Self {
+            values,
+            dirty: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T, C> TrackedBucketVec<T, C> {
+    /// Creates a new empty tracked bucket vector.
+    ///
+    /// # Note
+    ///
+    /// This does not allocate any heap memory.
+    pub fn new() -> Self {
+        Self::from(BucketVec::new())
+    }
+
+    /// Returns the number of elements stored in the tracked bucket vector.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the tracked bucket vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over shared references to the elements.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.values.iter()
+    }
+
+    /// Returns an iterator over exclusive references to the elements.
+    ///
+    /// # Note
+    ///
+    /// Marks all elements as changed.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.mark_dirty(0..self.values.len());
+        self.values.iter_mut()
+    }
+
+    /// Returns the underlying bucket vector.
+    pub fn as_bucket_vec(&self) -> &BucketVec<T, C> {
+        &self.values
+    }
+
+    /// Returns the underlying bucket vector and stops tracking it.
+    pub fn into_bucket_vec(self) -> BucketVec<T, C> {
+        self.values
+    }
+
+    /// Returns `true` if any element has been changed since the last call to
+    /// [`take_dirty`](TrackedBucketVec::take_dirty).
+    pub fn has_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Returns `true` if the element at the given index has been changed since
+    /// the last call to [`take_dirty`](TrackedBucketVec::take_dirty).
+    pub fn is_dirty(&self, index: usize) -> bool {
+        self.dirty
+            .range(..=index)
+            .next_back()
+            .map(|(_, &end)| index < end)
+            .unwrap_or(false)
+    }
+
+    /// Returns the ranges of all indices that have been changed since the last
+    /// call and resets the tracking.
+    ///
+    /// # Note
+    ///
+    /// The returned ranges are sorted, non-empty and neither overlap nor
+    /// touch each other.
+    pub fn take_dirty(&mut self) -> Vec<Range<usize>> {
+        core::mem::take(&mut self.dirty)
+            .into_iter()
+            .map(|(start, end)| start..end)
+            .collect()
+    }
+
+    /// Marks all elements within the range of indices as changed.
+    ///
+    /// This is useful to record changes that have been applied through other
+    /// means, e.g. through interior mutability.
+    pub fn mark_dirty(&mut self, range: Range<usize>) {
+        let Range { mut start, mut end } = range;
+        if start >= end {
+            return;
+        }
+        if let Some((&prev_start, &prev_end)) = self.dirty.range(..start).next_back() {
+            if prev_end >= start {
+                start = prev_start;
+                end = end.max(prev_end);
+            }
+        }
+        while let Some((&next_start, &next_end)) = self.dirty.range(start..=end).next() {
+            end = end.max(next_end);
+            self.dirty.remove(&next_start);
+        }
+        self.dirty.insert(start, end);
+    }
+}
+
+impl<T, C> TrackedBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    /// Returns a shared reference to the element at the given index if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    ///
+    /// # Note
+    ///
+    /// Marks the element as changed.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.values.len() {
+            return None;
+        }
+        self.mark_dirty(index..index + 1);
+        self.values.get_mut(index)
+    }
+
+    /// Pushes the value and returns its index.
+    ///
+    /// # Note
+    ///
+    /// Marks the pushed element as changed.
+    ///
+    /// This operation will never move other elements, reallocates or otherwise
+    /// invalidate pointers of elements contained by the tracked bucket vector.
+    pub fn push(&mut self, new_value: T) -> usize {
+        let index = self.values.len();
+        self.values.push(new_value);
+        self.mark_dirty(index..index + 1);
+        index
+    }
+
+    /// Overwrites all elements with clones of the value.
+    ///
+    /// # Note
+    ///
+    /// Marks all elements as changed.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.mark_dirty(0..self.values.len());
+        self.values.fill(value);
+    }
+}
+
+impl<T, C> Extend<T> for TrackedBucketVec<T, C>
+where
+    C: BucketVecConfig,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let start = self.values.len();
+        self.values.extend(iter);
+        self.mark_dirty(start..self.values.len());
+    }
+}
@@ -0,0 +1,210 @@
+//! Serialization of bucket vectors together with their layout parameters.
+
+use super::{BucketVec, BucketVecConfig, CapacityRounding, DefaultConfig};
+use core::{convert::TryFrom, fmt};
+
+/// A bucket vector that is serialized together with the layout parameters
+/// of its configuration.
+///
+/// Deserialization fails with a [`LayoutMismatch`] before any element is
+/// decoded if the serialized parameters differ from those of `C`. This
+/// guards indices stored elsewhere against silently referring to a
+/// differently laid out bucket vector.
+///
+/// # Note
+///
+/// The layout header consists of a format version, the starting capacity,
+/// the growth rate, the maximum bucket capacity, the capacity rounding and
+/// whether the schedule is geometric. Custom bucket capacity schedules are
+/// not part of the header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithLayout<T, C = DefaultConfig>(pub BucketVec<T, C>);
+
+/// The layout parameters stored in front of a self-describing bucket vector.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct LayoutHeader {
+    /// The version of the header format.
+    pub version: u8,
+    /// The capacity of the first bucket.
+    pub starting_capacity: u64,
+    /// The growth rate of bucket capacities.
+    pub growth_rate: f64,
+    /// The largest capacity of any bucket if any.
+    pub max_bucket_capacity: Option<u64>,
+    /// The kind of capacity rounding.
+    ///
+    /// `0` is exact, `1` is power of two and `2` is multiple of the quantum.
+    pub rounding: u8,
+    /// The quantum of the capacity rounding or `0` if it has none.
+    pub rounding_quantum: u64,
+    /// Whether the bucket capacities follow the geometric schedule.
+    pub geometric: bool,
+}
+
+impl LayoutHeader {
+    /// The current version of the header format.
+    pub const VERSION: u8 = 2;
+
+    /// Returns the layout header of the configuration.
+    pub fn of<C>() -> Self
+    where
+        C: BucketVecConfig,
+    {
+        let (rounding, rounding_quantum) = match <C as BucketVecConfig>::CAPACITY_ROUNDING {
+            CapacityRounding::Exact => (0, 0),
+            CapacityRounding::PowerOfTwo => (1, 0),
+            CapacityRounding::MultipleOf(quantum) => (2, quantum as u64),
+        };
+        Self {
+            version: Self::VERSION,
+            starting_capacity: <C as BucketVecConfig>::STARTING_CAPACITY as u64,
+            growth_rate: <C as BucketVecConfig>::GROWTH_RATE,
+            max_bucket_capacity: <C as BucketVecConfig>::MAX_BUCKET_CAPACITY
+                .map(|capacity| capacity as u64),
+            rounding,
+            rounding_quantum,
+            geometric: <C as BucketVecConfig>::GEOMETRIC,
+        }
+    }
+
+    /// Returns the capacity rounding of the header or `None` if its kind is unknown.
+    pub fn capacity_rounding(&self) -> Option<CapacityRounding> {
+        match (self.rounding, self.rounding_quantum) {
+            (0, 0) => Some(CapacityRounding::Exact),
+            (1, 0) => Some(CapacityRounding::PowerOfTwo),
+            (2, quantum) => usize::try_from(quantum)
+                .ok()
+                .map(CapacityRounding::MultipleOf),
+            _ => None,
+        }
+    }
+
+    /// Checks that the layout header matches the configuration.
+    ///
+    /// # Errors
+    ///
+    /// If the header has an unsupported version or any of its layout
+    /// parameters differs from the configuration.
+    pub fn check<C>(&self) -> Result<(), LayoutMismatch>
+    where
+        C: BucketVecConfig,
+    {
+        if self.version != Self::VERSION {
+            return Err(LayoutMismatch::UnsupportedVersion(self.version));
+        }
+        let expected = Self::of::<C>();
+        if self.starting_capacity != expected.starting_capacity {
+            return Err(LayoutMismatch::StartingCapacity {
+                expected: expected.starting_capacity,
+                found: self.starting_capacity,
+            });
+        }
+        if self.growth_rate.to_bits() != expected.growth_rate.to_bits() {
+            return Err(LayoutMismatch::GrowthRate {
+                expected: expected.growth_rate,
+                found: self.growth_rate,
+            });
+        }
+        if self.max_bucket_capacity != expected.max_bucket_capacity {
+            return Err(LayoutMismatch::MaxBucketCapacity {
+                expected: expected.max_bucket_capacity,
+                found: self.max_bucket_capacity,
+            });
+        }
+        let found = self.capacity_rounding();
+        if found != Some(<C as BucketVecConfig>::CAPACITY_ROUNDING) {
+            return Err(LayoutMismatch::CapacityRounding {
+                expected: <C as BucketVecConfig>::CAPACITY_ROUNDING,
+                found,
+            });
+        }
+        if self.geometric != expected.geometric {
+            return Err(LayoutMismatch::Geometric {
+                expected: expected.geometric,
+                found: self.geometric,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Errors returned upon deserializing a [`WithLayout`] whose serialized
+/// layout does not match its configuration.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LayoutMismatch {
+    /// The layout header has been written in an unsupported format version.
+    UnsupportedVersion(u8),
+    /// The starting capacity differs from the configuration.
+    StartingCapacity {
+        /// The starting capacity of the configuration.
+        expected: u64,
+        /// The serialized starting capacity.
+        found: u64,
+    },
+    /// The growth rate differs from the configuration.
+    GrowthRate {
+        /// The growth rate of the configuration.
+        expected: f64,
+        /// The serialized growth rate.
+        found: f64,
+    },
+    /// The maximum bucket capacity differs from the configuration.
+    MaxBucketCapacity {
+        /// The maximum bucket capacity of the configuration.
+        expected: Option<u64>,
+        /// The serialized maximum bucket capacity.
+        found: Option<u64>,
+    },
+    /// The capacity rounding differs from the configuration.
+    CapacityRounding {
+        /// The capacity rounding of the configuration.
+        expected: CapacityRounding,
+        /// The serialized capacity rounding or `None` if it is unknown.
+        found: Option<CapacityRounding>,
+    },
+    /// Whether the schedule is geometric differs from the configuration.
+    Geometric {
+        /// Whether the schedule of the configuration is geometric.
+        expected: bool,
+        /// Whether the serialized schedule is geometric.
+        found: bool,
+    },
+}
+
+impl fmt::Display for LayoutMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported bucket vector layout version {}", version)
+            }
+            Self::StartingCapacity { expected, found } => write!(
+                f,
+                "serialized starting capacity {} does not match the configured {}",
+                found, expected
+            ),
+            Self::GrowthRate { expected, found } => write!(
+                f,
+                "serialized growth rate {} does not match the configured {}",
+                found, expected
+            ),
+            Self::MaxBucketCapacity { expected, found } => write!(
+                f,
+                "serialized maximum bucket capacity {:?} does not match the configured {:?}",
+                found, expected
+            ),
+            Self::CapacityRounding { expected, found } => write!(
+                f,
+                "serialized capacity rounding {:?} does not match the configured {:?}",
+                found, expected
+            ),
+            Self::Geometric { expected, found } => write!(
+                f,
+                "serialized geometric schedule flag {} does not match the configured {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutMismatch {}